@@ -12,8 +12,9 @@ use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::subcommands::{
-    describe::DescribeCommand, init::InitCommand, render::RenderCommand, rm_cache::RmCacheCommand,
-    run::RunCommand, spawn::SpawnCommand, version::VersionCommand,
+    cache::CacheCommand, describe::DescribeCommand, graph::GraphCommand, init::InitCommand,
+    logs::LogsCommand, render::RenderCommand, rm_cache::RmCacheCommand, run::RunCommand,
+    spawn::SpawnCommand, version::VersionCommand, worker::WorkerCommand,
 };
 
 pub trait BlazeSubCommandExecution: Debug {
@@ -38,9 +39,13 @@ impl BlazeSubCommand {
             SubCommandKind::Run => Self(Box::new(RunCommand::from_arg_matches(args)?)),
             SubCommandKind::Spawn => Self(Box::new(SpawnCommand::from_arg_matches(args)?)),
             SubCommandKind::Describe => Self(Box::new(DescribeCommand::from_arg_matches(args)?)),
+            SubCommandKind::Graph => Self(Box::new(GraphCommand::from_arg_matches(args)?)),
             SubCommandKind::Version => Self(Box::new(VersionCommand::from_arg_matches(args)?)),
             SubCommandKind::Render => Self(Box::new(RenderCommand::from_arg_matches(args)?)),
             SubCommandKind::RmCache => Self(Box::new(RmCacheCommand::from_arg_matches(args)?)),
+            SubCommandKind::Logs => Self(Box::new(LogsCommand::from_arg_matches(args)?)),
+            SubCommandKind::Worker => Self(Box::new(WorkerCommand::from_arg_matches(args)?)),
+            SubCommandKind::Cache => Self(Box::new(CacheCommand::from_arg_matches(args)?)),
         })
     }
 }
@@ -49,9 +54,13 @@ const INIT: &str = "init";
 const RUN: &str = "run";
 const SPAWN: &str = "spawn";
 const DESCRIBE: &str = "describe";
+const GRAPH: &str = "graph";
 const VERSION: &str = "version";
 const RENDER: &str = "render";
 const RM_CACHE: &str = "rm-cache";
+const LOGS: &str = "logs";
+const WORKER: &str = "worker";
+const CACHE: &str = "cache";
 
 #[derive(Debug, EnumIter)]
 pub enum SubCommandKind {
@@ -59,8 +68,12 @@ pub enum SubCommandKind {
     Run,
     Spawn,
     Describe,
+    Graph,
     Render,
     RmCache,
+    Cache,
+    Logs,
+    Worker,
     Version,
 }
 
@@ -71,9 +84,13 @@ impl SubCommandKind {
             Self::Run => RunCommand::augment_args,
             Self::Spawn => SpawnCommand::augment_args,
             Self::Describe => DescribeCommand::augment_args,
+            Self::Graph => GraphCommand::augment_args,
             Self::Version => VersionCommand::augment_args,
             Self::Render => RenderCommand::augment_args,
             Self::RmCache => RmCacheCommand::augment_args,
+            Self::Logs => LogsCommand::augment_args,
+            Self::Worker => WorkerCommand::augment_args,
+            Self::Cache => CacheCommand::augment_args,
         };
         augment_args(clap::Command::new(self.as_str()))
     }
@@ -84,9 +101,13 @@ impl SubCommandKind {
             Self::Run => RUN,
             Self::Spawn => SPAWN,
             Self::Describe => DESCRIBE,
+            Self::Graph => GRAPH,
             Self::Version => VERSION,
             Self::Render => RENDER,
             Self::RmCache => RM_CACHE,
+            Self::Logs => LOGS,
+            Self::Worker => WORKER,
+            Self::Cache => CACHE,
         }
     }
 }
@@ -106,9 +127,13 @@ impl FromStr for SubCommandKind {
             RUN => Self::Run,
             SPAWN => Self::Spawn,
             DESCRIBE => Self::Describe,
+            GRAPH => Self::Graph,
             VERSION => Self::Version,
             RENDER => Self::Render,
             RM_CACHE => Self::RmCache,
+            LOGS => Self::Logs,
+            WORKER => Self::Worker,
+            CACHE => Self::Cache,
             _ => bail!("invalid sub command \"{s}\""),
         })
     }
@@ -0,0 +1,247 @@
+use std::{
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, Context};
+use blaze_common::error::{Error, Result};
+use blaze_core::{
+    explain_cache, gc_cache, list_cache_entries, show_cache_entries, CacheEntry, CacheEntryDetails,
+    CacheStatus, ExplainCacheOptions, GcCacheOptions, GlobalOptions,
+};
+use clap::{Parser, Subcommand};
+
+use crate::subcommand::BlazeSubCommandExecution;
+
+#[derive(Debug, Parser)]
+#[command(
+    display_name = "cache",
+    name = "cache",
+    about("Inspect and clean up the workspace cache.")
+)]
+pub struct CacheCommand {
+    #[command(subcommand)]
+    action: CacheAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheAction {
+    #[command(
+        about("Explain why a target would be executed instead of being restored from the cache."),
+        long_about(
+            "Explain why a target would be executed instead of being restored from the cache. \
+Cache invalidation checks of the target and of its dependencies are evaluated, but no target is executed."
+        )
+    )]
+    Explain {
+        #[arg(
+            help = "The target to explain, in the <project>:<target> format.",
+            index = 1
+        )]
+        double: String,
+    },
+    #[command(
+        about("List cache entries."),
+        long_about(
+            "List cache entries, with their size, the last time they were used and the checks stored in target execution states."
+        )
+    )]
+    Ls,
+    #[command(about("Display the cache entries of a target."))]
+    Show {
+        #[arg(
+            help = "The target to display the cache entries of, in the <project>:<target> format.",
+            index = 1
+        )]
+        double: String,
+    },
+    #[command(
+        about("Remove least recently used cache entries."),
+        long_about(
+            "Remove least recently used cache entries. \
+Entries which were not used for a given duration are removed, then entries are removed from the least recently used one until the cache size is below the given limit."
+        )
+    )]
+    Gc {
+        #[arg(
+            help = "Remove entries which were not used for this duration (for e.g 12h or 7d).",
+            long,
+            required_unless_present = "max_size"
+        )]
+        older_than: Option<Age>,
+        #[arg(
+            help = "Remove least recently used entries until the cache size is below this size (for e.g 500M or 2G).",
+            long
+        )]
+        max_size: Option<Size>,
+    },
+}
+
+/// A duration made of an amount and a unit (`ms`, `s`, `m`, `h` or `d`).
+#[derive(Debug, Clone)]
+struct Age(Duration);
+
+impl FromStr for Age {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (amount, unit) = split_amount(s)?;
+        Ok(Self(match unit {
+            "ms" => Duration::from_millis(amount),
+            "s" => Duration::from_secs(amount),
+            "m" => Duration::from_secs(amount * 60),
+            "h" => Duration::from_secs(amount * 60 * 60),
+            "d" => Duration::from_secs(amount * 60 * 60 * 24),
+            _ => bail!("invalid duration unit in {s} (expected ms, s, m, h or d)"),
+        }))
+    }
+}
+
+/// A size in bytes, with an optional binary unit (`K`, `M` or `G`).
+#[derive(Debug, Clone)]
+struct Size(u64);
+
+impl FromStr for Size {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (amount, unit) = split_amount(s)?;
+        let multiplier: u64 = match unit.to_ascii_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" => 1 << 10,
+            "M" | "MB" => 1 << 20,
+            "G" | "GB" => 1 << 30,
+            _ => bail!("invalid size unit in {s} (expected K, M or G)"),
+        };
+        Ok(Self(amount.saturating_mul(multiplier)))
+    }
+}
+
+fn split_amount(s: &str) -> Result<(u64, &str)> {
+    let unit_start = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (amount, unit) = s.split_at(unit_start);
+    Ok((
+        amount
+            .parse()
+            .with_context(|| format!("invalid amount in {s}"))?,
+        unit.trim(),
+    ))
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1048575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        1048576..=1073741823 => format!("{:.1} MiB", bytes as f64 / 1048576.0),
+        _ => format!("{:.1} GiB", bytes as f64 / 1073741824.0),
+    }
+}
+
+fn format_age(time: SystemTime) -> String {
+    let seconds = SystemTime::now()
+        .duration_since(time)
+        .unwrap_or_default()
+        .as_secs();
+    match seconds {
+        0..=59 => format!("{seconds}s ago"),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+/// The double of the target the entry belongs to (with its fingerprint if any), or the raw entry key.
+fn entry_name(details: &CacheEntryDetails) -> String {
+    match (details.double(), details.fingerprint()) {
+        (Some(double), Some(fingerprint)) => format!("{double} ({fingerprint})"),
+        (Some(double), None) => double.to_owned(),
+        (None, _) => details.entry().key().to_owned(),
+    }
+}
+
+/// Names of the checks and data stored in an execution state.
+fn metadata_summary(details: &CacheEntryDetails) -> String {
+    details
+        .state()
+        .and_then(|state| state.metadata.as_object())
+        .map(|metadata| metadata.keys().copied().collect::<Vec<_>>().join(", "))
+        .unwrap_or_default()
+}
+
+impl BlazeSubCommandExecution for CacheCommand {
+    fn execute(&self, root: &Path, globals: GlobalOptions) -> Result<()> {
+        match &self.action {
+            CacheAction::Explain { double } => {
+                let results = explain_cache(root, ExplainCacheOptions::new(double), globals)?;
+                for (double, node) in results.execution() {
+                    match &node.result {
+                        Some(Ok(CacheStatus::NoCache)) => {
+                            println!("{double}: has no cache, would be executed")
+                        }
+                        Some(Ok(CacheStatus::Cached)) => println!("{double}: cached"),
+                        Some(Ok(CacheStatus::Invalidated(reasons))) => {
+                            println!("{double}: would be executed");
+                            for reason in reasons {
+                                println!("  - {reason}");
+                            }
+                        }
+                        Some(Err(err)) => println!("{double}: could not be evaluated ({err:#})"),
+                        None => println!("{double}: not evaluated"),
+                    }
+                }
+                Ok(())
+            }
+            CacheAction::Ls => {
+                for details in list_cache_entries(root, globals)? {
+                    let entry = details.entry();
+                    println!(
+                        "{}\t{}\tused {}\t{}",
+                        entry_name(&details),
+                        format_size(entry.size()),
+                        format_age(entry.last_used()),
+                        metadata_summary(&details)
+                    );
+                }
+                Ok(())
+            }
+            CacheAction::Show { double } => {
+                for details in show_cache_entries(root, double, globals)? {
+                    let entry = details.entry();
+                    println!("{}", entry_name(&details));
+                    println!("  key: {}", entry.key());
+                    println!("  size: {}", format_size(entry.size()));
+                    println!("  created: {}", format_age(entry.created()));
+                    println!("  last used: {}", format_age(entry.last_used()));
+                    if let Some(state) = details.state() {
+                        println!("  hash: {:016x}", state.hash);
+                        println!("  metadata: {}", state.metadata);
+                    }
+                }
+                Ok(())
+            }
+            CacheAction::Gc {
+                older_than,
+                max_size,
+            } => {
+                let mut options = GcCacheOptions::new();
+                if let Some(Age(older_than)) = older_than {
+                    options = options.with_older_than(*older_than);
+                }
+                if let Some(Size(max_size)) = max_size {
+                    options = options.with_max_size(*max_size);
+                }
+                let removed = gc_cache(root, options, globals)?;
+                for entry in &removed {
+                    println!("removed {} ({})", entry.key(), format_size(entry.size()));
+                }
+                println!(
+                    "{} entries removed, {} freed",
+                    removed.len(),
+                    format_size(removed.iter().map(CacheEntry::size).sum())
+                );
+                Ok(())
+            }
+        }
+    }
+}
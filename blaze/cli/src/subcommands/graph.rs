@@ -0,0 +1,114 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use blaze_common::{error::Result, selector::ProjectSelector};
+use blaze_core::{export_graph, ExportGraphOptions, GlobalOptions, GraphFormat, SelectorSource};
+use clap::Parser;
+
+use crate::subcommand::BlazeSubCommandExecution;
+
+use super::{
+    double::Double,
+    selection_args::{project_selection_opts_without, SelectionArgs},
+};
+
+#[derive(Parser, Debug)]
+#[command(
+    display_name = "graph",
+    name = "graph",
+    about("Export the execution graph of a target."),
+    long_about(
+        "Export the execution graph of a target on a selection of projects, without executing anything. \
+The graph is built exactly as with the run command, including all target dependencies. \
+Each dependency edge carries its cache propagation and optional attributes."
+    )
+)]
+pub struct GraphCommand {
+    #[arg(
+        help = "The target name.",
+        long_help = "The target name. Must be a valid target name. For example, `build`, or `test`. \
+Selected projects that don't have any target matching this value will be ignored.",
+        short = 't',
+        long = "target",
+        required_unless_present = "double"
+    )]
+    target: Option<String>,
+
+    #[command(flatten)]
+    selection: SelectionArgs,
+
+    #[arg(
+        help = "An execution double consisting of an optional project name and a target name (in that specific order).",
+        long_help = "An execution double consisting of an optional project name and a target name (in that specific order). \
+Parts of the execution double must be separated with a colon. For e.g : build, or app:build. \
+Only the target name is mandatory, if the project name is not provided, then the default project selector will be used.",
+        index = 1,
+        required_unless_present = "target",
+        conflicts_with_all = vec![project_selection_opts_without([]), vec!["target"]].concat()
+    )]
+    double: Option<Double>,
+
+    #[arg(
+        help = "Set a maximum depth of dependencies when building the graph.",
+        long_help = "Set a maximum depth of dependencies when building the graph. \
+By default, every target dependencies are resolved resursively, no matter how deep. \
+When providing zero, then no dependencies will be resolved for each target.",
+        long = "depth"
+    )]
+    dependencies_depth: Option<usize>,
+
+    #[arg(
+        short,
+        long,
+        help = "Graph output format.",
+        long_help = "Graph output format. \
+Can be either <code>Dot</code> (Graphviz), <code>Mermaid</code> or <code>Json</code>.",
+        default_value = "Dot"
+    )]
+    format: GraphFormat,
+
+    #[arg(
+        short,
+        long,
+        help = "Write the graph to a file instead of the standard output."
+    )]
+    output: Option<PathBuf>,
+}
+
+impl BlazeSubCommandExecution for GraphCommand {
+    fn execute(&self, root: &Path, globals: GlobalOptions) -> Result<()> {
+        let target = if let Some(double) = &self.double {
+            &double.target
+        } else if let Some(target) = &self.target {
+            target
+        } else {
+            unreachable!()
+        };
+
+        let mut options = ExportGraphOptions::new(target).with_format(self.format);
+
+        if let Some(project) = self.double.as_ref().and_then(|t| t.project.as_ref()) {
+            options = options
+                .with_selector_source(SelectorSource::Provided(ProjectSelector::array([project])))
+        } else if let Some(selector) = self.selection.get_selector_source() {
+            options = options.with_selector_source(selector);
+        }
+
+        if let Some(max_depth) = self.dependencies_depth {
+            options = options.with_dependencies_depth(max_depth);
+        }
+
+        let output: Box<dyn Write> = match &self.output {
+            Some(path) => Box::new(BufWriter::new(File::create(path).with_context(|| {
+                format!("could not create output file at {}", path.display())
+            })?)),
+            None => Box::new(std::io::stdout()),
+        };
+
+        export_graph(root, options, output, globals)
+    }
+}
@@ -0,0 +1,34 @@
+use std::{io::Write, path::Path};
+
+use blaze_common::error::Result;
+use blaze_core::{target_logs, GlobalOptions, LogsOptions};
+use clap::Parser;
+
+use crate::subcommand::BlazeSubCommandExecution;
+
+#[derive(Debug, Parser)]
+#[command(
+    display_name = "logs",
+    name = "logs",
+    about("Display the output of the last run of a target."),
+    long_about(
+        "Display the output of the last run of a target. \
+The output of each executed target is captured under the .blaze/logs folder. \
+When the target execution was cached, the output of the original execution is displayed."
+    )
+)]
+pub struct LogsCommand {
+    #[arg(
+        help = "The target to display the output of, in the <project>:<target> format.",
+        index = 1
+    )]
+    double: String,
+}
+
+impl BlazeSubCommandExecution for LogsCommand {
+    fn execute(&self, root: &Path, globals: GlobalOptions) -> Result<()> {
+        let logs = target_logs(root, LogsOptions::new(&self.double), globals)?;
+        std::io::stdout().write_all(logs.content())?;
+        Ok(())
+    }
+}
@@ -1,10 +1,14 @@
+pub mod cache;
 pub mod describe;
 pub mod double;
+pub mod graph;
 mod help;
 pub mod init;
+pub mod logs;
 pub mod render;
 pub mod rm_cache;
 pub mod run;
 pub mod selection_args;
 pub mod spawn;
 pub mod version;
+pub mod worker;
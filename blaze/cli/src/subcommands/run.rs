@@ -1,8 +1,14 @@
-use std::path::Path;
+use std::{
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
 
 use anyhow::bail;
 use blaze_common::{error::Result, parallelism::Parallelism, selector::ProjectSelector};
-use blaze_core::{run, GlobalOptions, RunOptions, SelectorSource};
+use blaze_core::{
+    run, watch, ExecutedGraph, ExecutionDetails, GlobalOptions, ReportFormat, RunOptions,
+    SelectorSource,
+};
 use clap::Parser;
 use possibly::possibly;
 
@@ -11,6 +17,7 @@ use crate::{subcommand::BlazeSubCommandExecution, subcommands::help::parallelism
 use super::{
     double::Double,
     selection_args::{project_selection_opts_without, SelectionArgs},
+    worker::worker_token,
 };
 
 const PARALLELISM_HELP: &str =
@@ -24,9 +31,10 @@ fn parallelism_long_help() -> String {
 #[command(
     display_name = "run",
     name = "run",
-    about("Run a target on a selection of projects."),
+    about("Run one or more targets on a selection of projects."),
     long_about(
-        "Run a target on a selection of projects and report the results. \
+        "Run one or more targets on a selection of projects and report the results. \
+When multiple targets are provided, they are all executed in a single execution graph, where targets shared between them are executed only once. \
 For each selected project, the target will be executed (if available) using its corresponding executor. \
 By default, each execution might be skipped and its result get directly retrieved from cache. \
 The run command will fail and return a non-zero status code if any of the targets that correspond to a selected project fail (or cannot be executed due to unfullfilled dependencies). \
@@ -37,12 +45,13 @@ pub struct RunCommand {
     #[arg(
         help = "The target name.",
         long_help = "The target name. Must be a valid target name. For example, `build`, or `test`. \
-Selected projects that don't have any target matching this value will be ignored.",
+Selected projects that don't have any target matching this value will be ignored. \
+This option can be repeated in order to run multiple targets at once (for e.g `-t lint -t test`), in a single execution graph.",
         short = 't',
         long = "target",
         required_unless_present = "double"
     )]
-    target: Option<String>,
+    target: Vec<String>,
 
     #[arg(
         help = PARALLELISM_HELP,
@@ -72,12 +81,13 @@ Selected projects that don't have any target matching this value will be ignored
         long_help = "An execution double consisting of an optional project name and a target name (in that specific order). \
 Parts of the execution double must be separated with a colon. For e.g : build, or app:build. \
 Using an execution double only allows to execute the target on a single project. \
-Only the target name is mandatory, if the project name is not provided, then the default project selector will be used.",
+Only the target name is mandatory, if the project name is not provided, then the default project selector will be used. \
+Multiple execution doubles can be provided (for e.g : app:build lib:test), they will be executed in a single execution graph.",
         index = 1,
         required_unless_present = "target",
         conflicts_with_all = vec![project_selection_opts_without([]), vec!["target"]].concat()
     )]
-    double: Option<Double>,
+    double: Vec<Double>,
 
     #[arg(
         help = "Set a maximum depth of dependencies when executing targets.",
@@ -87,24 +97,106 @@ When providing zero, then no dependencies will be resolved for each target.",
         long = "depth"
     )]
     dependencies_depth: Option<usize>,
+
+    #[arg(
+        help = "Keep running and execute targets again when their input files change.",
+        long_help = "Keep running and execute targets again when their input files change. \
+Files are matched using the `inputChanges` cache invalidation strategy of each target. \
+When changes are detected, only the targets whose input files have changed are executed again, along with the targets that depend on them.",
+        long = "watch",
+        conflicts_with = "dry_run"
+    )]
+    watch: bool,
+
+    #[arg(
+        help = "Write a report of the run results to a file.",
+        long_help = "Write a report of the run results to a file. \
+The report contains every target of the execution graph, along with its dependencies, its execution state, its execution time and its errors (if any). \
+When using the --watch flag, the report is written again after each run.",
+        long = "report"
+    )]
+    report: Option<PathBuf>,
+
+    #[arg(
+        help = "The format of the report file.",
+        long_help = "The format of the report file (see the --report option). \
+Can be either <code>Json</code> or <code>Junit</code>.",
+        long = "report-format",
+        default_value = "Json",
+        requires = "report"
+    )]
+    report_format: ReportFormat,
+
+    #[arg(
+        help = "Stop executing targets as soon as one of them fails.",
+        long_help = "Stop executing targets as soon as one of them fails. \
+No more targets will be executed, even if they do not depend on the failed target, and processes of running targets are killed. \
+Overrides the `failFast` workspace setting.",
+        long = "fail-fast",
+        conflicts_with = "keep_going"
+    )]
+    fail_fast: bool,
+
+    #[arg(
+        help = "Keep executing targets when one of them fails.",
+        long_help = "Keep executing targets when one of them fails. \
+Only targets that depend on the failed target are skipped. This is the default behavior, unless the `failFast` workspace setting is enabled.",
+        long = "keep-going"
+    )]
+    keep_going: bool,
+
+    #[arg(
+        help = "Display running targets in an interactive terminal UI.",
+        long_help = "Display running targets in an interactive terminal UI. \
+Each running target is displayed with its elapsed time and the last lines of its output, which are collapsed once the target is done (unless it has failed). \
+When the standard output is not a terminal, logs are displayed as usual.",
+        long = "tui"
+    )]
+    tui: bool,
+
+    #[arg(
+        help = "The number of output lines displayed for each running target in the terminal UI.",
+        long_help = "The number of output lines displayed for each running target in the terminal UI (see the --tui flag). \
+Use 0 in order to only display the status of each target.",
+        long = "tui-lines",
+        default_value_t = 5,
+        requires = "tui"
+    )]
+    tui_lines: usize,
+
+    #[arg(
+        help = "Execute targets on remote workers instead of the local machine.",
+        long_help = "Execute targets on remote workers instead of the local machine. \
+Each address must point to a worker started with the `worker` command, and executes one target at a time. \
+The same address can be provided multiple times in order to execute more targets at once on the same worker. \
+The token of the workers must be set in the BLAZE_WORKER_TOKEN environment variable. \
+Remote executions are never cached.",
+        long = "workers",
+        value_name = "ADDRESS",
+        num_args = 1..
+    )]
+    workers: Vec<String>,
 }
 
 impl BlazeSubCommandExecution for RunCommand {
     fn execute(&self, root: &Path, globals: GlobalOptions) -> Result<()> {
-        let target = if let Some(double) = &self.double {
-            &double.target
-        } else if let Some(target) = &self.target {
-            target
-        } else {
-            unreachable!()
-        };
-
-        let mut options = RunOptions::new(target);
-
-        if let Some(project) = self.double.as_ref().and_then(|t| t.project.as_ref()) {
-            options = options
-                .with_selector_source(SelectorSource::Provided(ProjectSelector::array([project])))
-        } else if let Some(selector) = self.selection.get_selector_source() {
+        let mut options = RunOptions::default();
+
+        for target in &self.target {
+            options = options.and_target(target);
+        }
+
+        for double in &self.double {
+            options = match &double.project {
+                Some(project) => options.and_target_with_selector_source(
+                    &double.target,
+                    SelectorSource::Provided(ProjectSelector::array([project])),
+                ),
+                None => options.and_target(&double.target),
+            };
+        }
+
+        if let Some(selector) = self.selection.get_selector_source() {
             options = options.with_selector_source(selector);
         }
 
@@ -124,13 +216,40 @@ impl BlazeSubCommandExecution for RunCommand {
             options = options.with_dependencies_depth(max_depth);
         }
 
+        if let Some(report) = &self.report {
+            options = options.with_report(report, self.report_format);
+        }
+
+        if self.fail_fast {
+            options = options.with_fail_fast(true);
+        } else if self.keep_going {
+            options = options.with_fail_fast(false);
+        }
+
+        if self.tui {
+            options = options.with_tui(self.tui_lines);
+        }
+
+        if !self.workers.is_empty() {
+            options = options.with_workers(&self.workers, worker_token()?);
+        }
+
+        if self.watch {
+            return watch(root, options, globals, |run_result| {
+                let root_failures = get_root_failures(run_result);
+                if !root_failures.is_empty() {
+                    eprintln!(
+                        "run failed for target(s): \n\n{}\n",
+                        root_failures.join("\n")
+                    );
+                }
+                ControlFlow::Continue(())
+            });
+        }
+
         let run_result = run(root, options, globals)?;
 
-        let root_failures = run_result
-            .root_executions()
-            .values()
-            .filter_map(|execution_result| possibly!(&execution_result.result, Some(Err(_))|None => execution_result.execution.get_double()))
-            .collect::<Vec<_>>();
+        let root_failures = get_root_failures(&run_result);
 
         if !self.dry_run && !root_failures.is_empty() {
             bail!(
@@ -141,3 +260,11 @@ impl BlazeSubCommandExecution for RunCommand {
         Ok(())
     }
 }
+
+fn get_root_failures(run_result: &ExecutedGraph<ExecutionDetails>) -> Vec<String> {
+    run_result
+        .root_executions()
+        .values()
+        .filter_map(|execution_result| possibly!(&execution_result.result, Some(Err(_))|None => execution_result.execution.get_double()))
+        .collect()
+}
@@ -5,10 +5,12 @@ use clap::Parser;
 use crate::subcommands::help::{exclude_projects_input_hint, include_projects_input_hint};
 
 pub fn project_selection_opts_without<const N: usize>(names: [&str; N]) -> Vec<&'static str> {
-    vec!["projects", "all", "selector", "include", "exclude"]
-        .into_iter()
-        .filter(|n| !names.contains(n))
-        .collect()
+    vec![
+        "projects", "all", "selector", "include", "exclude", "affected",
+    ]
+    .into_iter()
+    .filter(|n| !names.contains(n))
+    .collect()
 }
 
 const INCLUDE_HELP: &str = "A pattern to use for including projects.";
@@ -84,6 +86,26 @@ Will be parsed as a comma-separated list of project names (for e.g project1,proj
         conflicts_with_all(project_selection_opts_without(["tags"]))
     )]
     pub tags: Option<Vec<String>>,
+
+    #[arg(
+        help = "Select projects affected by changes since a base git revision.",
+        long_help = "Select projects affected by changes since a base git revision (see the --base option). \
+Projects containing changed files (including uncommitted and untracked files) are selected, \
+as well as all projects that depend on them through target dependencies.",
+        long = "affected",
+        conflicts_with_all(project_selection_opts_without(["affected"]))
+    )]
+    pub affected: bool,
+
+    #[arg(
+        help = "The base git revision to use with the --affected option.",
+        long_help = "The base git revision to use with the --affected option. \
+Changes are computed from the merge base of this revision and HEAD. Can be any revision that git understands (for e.g main, origin/main or a commit hash).",
+        long = "base",
+        default_value = "HEAD",
+        requires = "affected"
+    )]
+    pub base: String,
 }
 
 impl SelectionArgs {
@@ -93,6 +115,7 @@ impl SelectionArgs {
             .map(ProjectSelector::array)
             .or_else(|| self.all.then_some(ProjectSelector::all()))
             .or_else(|| self.tags.as_ref().map(ProjectSelector::tagged))
+            .or_else(|| self.affected.then(|| ProjectSelector::affected(&self.base)))
             .or_else(|| {
                 self.include.as_ref().map(|patterns| {
                     ProjectSelector::include_exclude(
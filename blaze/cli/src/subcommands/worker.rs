@@ -0,0 +1,54 @@
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use blaze_common::error::Result;
+use blaze_core::{worker, GlobalOptions, WorkerOptions};
+use clap::Parser;
+
+use crate::subcommand::BlazeSubCommandExecution;
+
+/// The worker token is read from the environment, so that it does not appear in process listings.
+const WORKER_TOKEN_ENVIRONMENT_VARIABLE: &str = "BLAZE_WORKER_TOKEN";
+
+/// Get the token shared by workers and coordinators.
+pub fn worker_token() -> Result<String> {
+    let token = std::env::var(WORKER_TOKEN_ENVIRONMENT_VARIABLE).with_context(|| {
+        format!("{WORKER_TOKEN_ENVIRONMENT_VARIABLE} environment variable must be set")
+    })?;
+    if token.is_empty() {
+        bail!("{WORKER_TOKEN_ENVIRONMENT_VARIABLE} environment variable must not be empty");
+    }
+    Ok(token)
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    display_name = "worker",
+    name = "worker",
+    about("Execute targets on behalf of a remote `run` command."),
+    long_about(
+        "Execute targets on behalf of a remote `run` command (see the --workers option of the `run` command). \
+Targets are executed in the workspace where the worker is started, one per coordinator connection. \
+Coordinators must provide the token set in the BLAZE_WORKER_TOKEN environment variable. \
+Traffic is not encrypted, the worker must only be reachable from trusted networks."
+    )
+)]
+pub struct WorkerCommand {
+    #[arg(
+        help = "The address to listen on.",
+        long = "listen",
+        default_value = "127.0.0.1:7780"
+    )]
+    listen: String,
+}
+
+impl BlazeSubCommandExecution for WorkerCommand {
+    fn execute(&self, root: &Path, globals: GlobalOptions) -> Result<()> {
+        worker(
+            root,
+            WorkerOptions::new(&self.listen, worker_token()?),
+            globals,
+            |_| {},
+        )
+    }
+}
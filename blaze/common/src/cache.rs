@@ -4,6 +4,7 @@ use std::{
     num::NonZeroUsize,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
@@ -20,14 +21,41 @@ use crate::{
 pub struct TargetCache {
     #[serde(default)]
     invalidate_when: InvalidationStrategy,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    outputs: BTreeSet<FileChangesMatcher>,
+    #[serde(default)]
+    mode: CacheMode,
 }
 
 impl TargetCache {
     pub fn invalidate_when(&self) -> &InvalidationStrategy {
         &self.invalidate_when
     }
+
+    pub fn outputs(&self) -> &BTreeSet<FileChangesMatcher> {
+        &self.outputs
+    }
+
+    pub fn mode(&self) -> CacheMode {
+        self.mode
+    }
+}
+
+/// How cache entries are stored and looked up for a target.
+#[derive(
+    Default, Clone, Copy, Debug, Hash, EnumIter, PartialEq, Eq, PartialOrd, Ord, Display, Serialize,
+)]
+pub enum CacheMode {
+    /// A single entry is kept, and validated against the current state.
+    #[default]
+    LastState,
+    /// Entries are keyed by a fingerprint of the target inputs, so that any previously seen state is a cache hit.
+    Fingerprint,
 }
 
+unit_enum_from_str!(CacheMode);
+unit_enum_deserialize!(CacheMode);
+
 #[derive(Debug, Default, Clone, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InvalidationStrategy {
@@ -98,6 +126,8 @@ pub enum MatchingBehavior {
     #[default]
     Mixed,
     Hash,
+    /// Use git object ids of tracked files, and hash only modified or untracked files.
+    Git,
 }
 
 unit_enum_from_str!(MatchingBehavior);
@@ -267,6 +297,20 @@ pub enum TimeUnit {
 unit_enum_from_str!(TimeUnit);
 unit_enum_deserialize!(TimeUnit);
 
+impl TimeUnit {
+    /// Get the duration of an amount of this unit.
+    pub fn duration(self, amount: usize) -> Duration {
+        let amount = amount as u64;
+        match self {
+            Self::Milliseconds => Duration::from_millis(amount),
+            Self::Seconds => Duration::from_secs(amount),
+            Self::Minutes => Duration::from_secs(amount * 60),
+            Self::Hours => Duration::from_secs(amount * 60 * 60),
+            Self::Days => Duration::from_secs(amount * 60 * 60 * 24),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct TtlOptions {
     unit: TimeUnit,
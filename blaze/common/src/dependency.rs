@@ -97,6 +97,11 @@ pub enum CachePropagation {
 
     // Dependencies will not be considered when invalidating cache
     Never,
+
+    /// Cache will invalidate only if the declared output files of the dependency have changed
+    /// (or if the dependency state changes, like [`CachePropagation::Always`]).
+    /// Dependencies without declared outputs are considered like [`CachePropagation::Always`].
+    OnOutputChange,
 }
 
 unit_enum_from_str!(CachePropagation);
@@ -126,13 +126,21 @@ impl<'de> Deserialize<'de> for ExecutorReference {
                         options: GitOptions::deserialize(&root).map_err(D::Error::custom)?,
                     },
                     "npm" => Location::Npm {
-                        options: NpmOptions::deserialize(&root).map_err(D::Error::custom)?,
+                        options: if is_single_url {
+                            NpmOptions::default()
+                        } else {
+                            NpmOptions::deserialize(&root).map_err(D::Error::custom)?
+                        },
                     },
                     "cargo" => Location::Cargo {
-                        options: CargoOptions::deserialize(&root).map_err(D::Error::custom)?,
+                        options: if is_single_url {
+                            CargoOptions::default()
+                        } else {
+                            CargoOptions::deserialize(&root).map_err(D::Error::custom)?
+                        },
                     },
                     invalid_scheme => {
-                        return Err(serde::de::Error::custom(&format!(
+                        return Err(serde::de::Error::custom(format!(
                             "invalid url scheme \"{invalid_scheme}\""
                         )))
                     }
@@ -215,9 +223,28 @@ impl Serialize for Location {
                 }
                 value.serialize(serializer)
             }
+            Self::TarballOverHttp {
+                transport,
+                tarball_options,
+                authentication,
+            } => {
+                let mut value = to_value(transport).map_err(S::Error::custom)?;
+                value.overwrite(to_value(tarball_options).map_err(S::Error::custom)?);
+                value.overwrite(Value::object([(
+                    FORMAT_KEY,
+                    Value::string(HttpFormatIdentifier::Tarball.to_string()),
+                )]));
+                if let Some(authentication) = authentication {
+                    value.overwrite(Value::object([(
+                        AUTHENTICATION_KEY,
+                        to_value(authentication).map_err(S::Error::custom)?,
+                    )]));
+                }
+                value.serialize(serializer)
+            }
+            Self::Git { options } => options.serialize(serializer),
             Self::Npm { options } => options.serialize(serializer),
             Self::Cargo { options } => options.serialize(serializer),
-            _ => todo!(),
         }
     }
 }
@@ -279,6 +306,7 @@ const HTTP_AUTH_MODE_KEY: &str = "mode";
 #[derive(Serialize, EnumIter, Display, Hash, PartialEq, Eq, Clone)]
 pub enum HttpAuthenticationMode {
     Basic,
+    Digest,
     Bearer,
 }
 
@@ -331,6 +359,7 @@ impl HttpBearerAuthentication {
 #[derive(Hash, Debug, PartialEq, Eq, Clone)]
 pub enum HttpAuthentication {
     Basic(HttpBasicAuthentication),
+    Digest(HttpDigestAuthentication),
     Bearer(HttpBearerAuthentication),
 }
 
@@ -349,6 +378,9 @@ impl<'de> Deserialize<'de> for HttpAuthentication {
             HttpAuthenticationMode::Basic => HttpAuthentication::Basic(
                 HttpBasicAuthentication::deserialize(root).map_err(D::Error::custom)?,
             ),
+            HttpAuthenticationMode::Digest => HttpAuthentication::Digest(
+                HttpDigestAuthentication::deserialize(root).map_err(D::Error::custom)?,
+            ),
             HttpAuthenticationMode::Bearer => HttpAuthentication::Bearer(
                 HttpBearerAuthentication::deserialize(root).map_err(D::Error::custom)?,
             ),
@@ -365,6 +397,7 @@ impl Serialize for HttpAuthentication {
             HTTP_AUTH_MODE_KEY,
             to_value(match self {
                 HttpAuthentication::Basic(_) => HttpAuthenticationMode::Basic,
+                HttpAuthentication::Digest(_) => HttpAuthenticationMode::Digest,
                 HttpAuthentication::Bearer(_) => HttpAuthenticationMode::Bearer,
             })
             .map_err(S::Error::custom)?,
@@ -373,6 +406,7 @@ impl Serialize for HttpAuthentication {
         value.overwrite(
             match self {
                 HttpAuthentication::Basic(basic) => to_value(basic),
+                HttpAuthentication::Digest(digest) => to_value(digest),
                 HttpAuthentication::Bearer(bearer) => to_value(bearer),
             }
             .map_err(S::Error::custom)?,
@@ -627,7 +661,7 @@ pub enum SshAuthentication {
     },
 }
 
-#[derive(Deserialize, Serialize, Hash, Debug, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Hash, Debug, Default, PartialEq, Eq, Clone)]
 pub struct CargoOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     version: Option<String>,
@@ -651,7 +685,7 @@ impl CargoOptions {
     }
 }
 
-#[derive(Deserialize, Serialize, Hash, Debug, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Hash, Debug, Default, PartialEq, Eq, Clone)]
 pub struct NpmOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     version: Option<String>,
@@ -15,6 +15,22 @@ pub enum ProjectSelector {
     },
     #[serde(untagged)]
     Tagged(BTreeSet<String>),
+    #[serde(untagged)]
+    Affected {
+        affected: AffectedOptions,
+    },
+}
+
+/// Options for selecting projects that were affected by changes since a base revision.
+#[derive(Clone, Debug, Hash, Serialize, Deserialize)]
+pub struct AffectedOptions {
+    base: String,
+}
+
+impl AffectedOptions {
+    pub fn base(&self) -> &str {
+        &self.base
+    }
 }
 
 impl Display for ProjectSelector {
@@ -26,6 +42,9 @@ impl Display for ProjectSelector {
                 format!("projects matching expressions {include:?}, excluding {exclude:?}").into()
             }
             Self::Tagged(tags) => format!("projects tagged with: {tags:?}").into(),
+            Self::Affected { affected } => {
+                format!("projects affected by changes since {}", affected.base).into()
+            }
         })
     }
 }
@@ -55,6 +74,14 @@ impl ProjectSelector {
         }
     }
 
+    pub fn affected<S: AsRef<str>>(base: S) -> Self {
+        Self::Affected {
+            affected: AffectedOptions {
+                base: base.as_ref().to_owned(),
+            },
+        }
+    }
+
     pub fn tagged<S: AsRef<str>, I: IntoIterator<Item = S>>(tags: I) -> Self {
         Self::Tagged(tags.into_iter().map(|s| s.as_ref().to_owned()).collect())
     }
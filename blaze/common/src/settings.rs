@@ -1,7 +1,14 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, num::NonZeroUsize, path::PathBuf};
 
-use crate::{logger::LogLevel, parallelism::Parallelism, selector::ProjectSelector};
+use crate::{
+    executor::{HttpAuthentication, HttpTransport},
+    logger::LogLevel,
+    parallelism::Parallelism,
+    selector::ProjectSelector,
+    value::Value,
+};
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 /// Global settings for the workspace.
 #[derive(Default, Debug, Serialize, Deserialize)]
@@ -17,6 +24,14 @@ pub struct GlobalSettings {
     log_level: Option<LogLevel>,
     #[serde(skip_serializing_if = "Option::is_none")]
     resolution_parallelism: Option<Parallelism>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache: Option<CacheBackendSettings>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    resource_pools: BTreeMap<String, NonZeroUsize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fail_fast: Option<bool>,
+    #[serde(default, skip_serializing_if = "RunHooks::is_empty")]
+    hooks: RunHooks,
 }
 
 impl GlobalSettings {
@@ -39,4 +54,85 @@ impl GlobalSettings {
     pub fn resolution_parallelism(&self) -> Option<Parallelism> {
         self.resolution_parallelism
     }
+
+    pub fn cache(&self) -> Option<&CacheBackendSettings> {
+        self.cache.as_ref()
+    }
+
+    /// Named resource pools, with the maximum amount of resources that can be used by targets at the same time.
+    pub fn resource_pools(&self) -> &BTreeMap<String, NonZeroUsize> {
+        &self.resource_pools
+    }
+
+    pub fn fail_fast(&self) -> Option<bool> {
+        self.fail_fast
+    }
+
+    /// Hooks executed around runs.
+    pub fn hooks(&self) -> &RunHooks {
+        &self.hooks
+    }
+}
+
+/// Hooks executed around runs, in the order they are declared.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunHooks {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    before_run: Vec<RunHook>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    after_run: Vec<RunHook>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    on_failure: Vec<RunHook>,
+}
+
+impl RunHooks {
+    /// Hooks executed before targets, the run is aborted if one of them fails.
+    pub fn before_run(&self) -> &[RunHook] {
+        &self.before_run
+    }
+
+    /// Hooks executed once all targets were executed, whether the run was successful or not.
+    pub fn after_run(&self) -> &[RunHook] {
+        &self.after_run
+    }
+
+    /// Hooks executed once all targets were executed, only if at least one of them has failed.
+    pub fn on_failure(&self) -> &[RunHook] {
+        &self.on_failure
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.before_run.is_empty() && self.after_run.is_empty() && self.on_failure.is_empty()
+    }
+}
+
+/// Something to execute around a run.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum RunHook {
+    /// A target to execute (along with its dependencies), formatted as `<project>:<target>`.
+    Target(String),
+    /// Commands executed from the workspace root, with the same options as the `std:commands` executor.
+    Commands {
+        commands: Vec<Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        shell: Option<Value>,
+    },
+}
+
+/// Where target execution cache states are stored.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum CacheBackendSettings {
+    /// Store cache entries in a local directory (relative paths are resolved from the workspace root).
+    Local { path: PathBuf },
+    /// Store cache entries on an HTTP server, using GET/PUT/DELETE requests.
+    Http {
+        url: Url,
+        #[serde(flatten)]
+        transport: HttpTransport,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        authentication: Option<HttpAuthentication>,
+    },
 }
@@ -1,13 +1,24 @@
-use std::hash::Hash;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
+    num::NonZeroUsize,
+    time::Duration,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use strum_macros::{Display, EnumIter};
 
 use hash_value::Value;
 
-use crate::{cache::TargetCache, dependency::Dependency, executor::ExecutorReference};
+use crate::{
+    cache::{TargetCache, TimeUnit},
+    dependency::Dependency,
+    enums::{unit_enum_deserialize, unit_enum_from_str},
+    executor::ExecutorReference,
+};
 
 /// A single target description
-#[derive(Debug, Serialize, Deserialize, Hash)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Target {
     #[serde(skip_serializing_if = "Option::is_none")]
     executor: Option<ExecutorReference>,
@@ -21,6 +32,12 @@ pub struct Target {
     cache: Option<TargetCache>,
     #[serde(default)]
     stateless: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<TimeSpan>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    resources: BTreeMap<String, NonZeroUsize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry: Option<RetryPolicy>,
 }
 
 impl Target {
@@ -47,4 +64,143 @@ impl Target {
     pub fn stateless(&self) -> bool {
         self.stateless
     }
+
+    pub fn timeout(&self) -> Option<&TimeSpan> {
+        self.timeout.as_ref()
+    }
+
+    /// Amounts of resources used by this target, indexed by resource pool name.
+    pub fn resources(&self) -> &BTreeMap<String, NonZeroUsize> {
+        &self.resources
+    }
+
+    pub fn retry(&self) -> Option<&RetryPolicy> {
+        self.retry.as_ref()
+    }
+}
+
+/// Options that only change how a target is scheduled (`timeout`, `resources` and `retry`) are not hashed,
+/// so that changing them does not invalidate the target cache.
+impl Hash for Target {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Self {
+            executor,
+            options,
+            description,
+            dependencies,
+            cache,
+            stateless,
+            timeout: _,
+            resources: _,
+            retry: _,
+        } = self;
+        executor.hash(state);
+        options.hash(state);
+        description.hash(state);
+        dependencies.hash(state);
+        cache.hash(state);
+        stateless.hash(state);
+    }
+}
+
+/// A duration expressed as an amount of time units, used for timeouts and retry delays.
+#[derive(Debug, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct TimeSpan {
+    unit: TimeUnit,
+    amount: NonZeroUsize,
+}
+
+impl TimeSpan {
+    pub fn unit(&self) -> TimeUnit {
+        self.unit
+    }
+
+    pub fn amount(&self) -> usize {
+        self.amount.get()
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.unit.duration(self.amount.get())
+    }
 }
+
+/// How a failed target execution is attempted again.
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    max_attempts: NonZeroUsize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delay: Option<TimeSpan>,
+    #[serde(default)]
+    backoff: Backoff,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_exit_codes"
+    )]
+    exit_codes: Option<BTreeSet<i32>>,
+}
+
+/// Exit codes are signed (they can be negative on Windows), but positive values are parsed as unsigned integers.
+fn deserialize_exit_codes<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<BTreeSet<i32>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Some(
+        Vec::<Value>::deserialize(deserializer)?
+            .into_iter()
+            .map(|code| {
+                code.as_signed()
+                    .or_else(|| code.as_unsigned().and_then(|code| i64::try_from(code).ok()))
+                    .and_then(|code| i32::try_from(code).ok())
+                    .ok_or_else(|| D::Error::custom("exit codes must be 32-bit integers"))
+            })
+            .collect::<std::result::Result<_, _>>()?,
+    ))
+}
+
+impl RetryPolicy {
+    /// Maximum number of executions, including the first one.
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts.get()
+    }
+
+    pub fn delay(&self) -> Option<&TimeSpan> {
+        self.delay.as_ref()
+    }
+
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    /// When provided, only failures of processes exiting with one of these codes are retried.
+    pub fn exit_codes(&self) -> Option<&BTreeSet<i32>> {
+        self.exit_codes.as_ref()
+    }
+
+    /// Get the duration to wait before the next attempt, after the provided attempt has failed (starting at 1).
+    pub fn delay_after(&self, attempt: usize) -> Duration {
+        let delay = self.delay.map(|delay| delay.duration()).unwrap_or_default();
+        match self.backoff {
+            Backoff::Fixed => delay,
+            Backoff::Exponential => {
+                delay.saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1) as u32))
+            }
+        }
+    }
+}
+
+/// How the delay between two attempts evolves.
+#[derive(Debug, Default, Clone, Copy, Hash, Display, Serialize, EnumIter)]
+pub enum Backoff {
+    /// The same delay is used between each attempt.
+    #[default]
+    Fixed,
+    /// The delay is doubled after each attempt.
+    Exponential,
+}
+
+unit_enum_from_str!(Backoff);
+unit_enum_deserialize!(Backoff);
@@ -1,9 +1,15 @@
-use std::time::SystemTime;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
 use blaze_common::{error::Result, time::system_time_as_timestamps, value::Value};
 
 use serde::{Deserialize, Serialize};
 
+use crate::system::file_changes::{FileChange, FileChangeType};
+
 use super::execution::TargetExecution;
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -24,12 +30,187 @@ pub trait CacheInvalidationCheck {
         Ok(None)
     }
 
-    /// Try to validate the cache state. Returns false if cache is invalidated.
+    /// Try to validate the cache state. Returns the reason why the cache is invalidated, or [`None`] if it is still valid.
     /// The implementation can internally store the new state between the invocation of this method and the invocation of the [`Self::state()`] method.
     /// This method might be called if cache does not exist yet for this [`CacheInvalidationCheck`].
     fn validate(
         &mut self,
         execution: &TargetExecution,
         current_state: &ExecutionCacheState,
-    ) -> Result<bool>;
+    ) -> Result<Option<InvalidationReason>>;
+}
+
+/// Why the cache state of a target execution cannot be used.
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "reason",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum InvalidationReason {
+    /// No cache state was found for the target.
+    NotCached,
+    /// No cache state was found for the fingerprint of the target (see [`blaze_common::cache::CacheMode::Fingerprint`]).
+    FingerprintNotCached { fingerprint: String },
+    /// The cache state was created by a version of Blaze which did not store the state of a check.
+    MissingState { check: String },
+    /// The target configuration has changed since the last execution.
+    ConfigurationChanged,
+    /// Some dependencies were executed again, or are not dependencies anymore.
+    DependenciesChanged { dependencies: Vec<String> },
+    /// The executor of the target was updated.
+    ExecutorUpdated,
+    /// The cache state is older than its time to live.
+    TtlExpired {
+        /// Number of seconds elapsed since the expiration.
+        expired_for: f64,
+    },
+    /// Some files are missing.
+    FilesMissing { files: Vec<PathBuf> },
+    /// Some input files have changed.
+    InputFilesChanged { files: Vec<ChangedFile> },
+    /// Some output files have changed.
+    OutputFilesChanged { files: Vec<ChangedFile> },
+    /// The cache invalidation command has failed.
+    CommandFailed { code: Option<i32> },
+    /// Some watched environment variables have changed.
+    EnvironmentChanged { variables: Vec<ChangedVariable> },
+    /// The output files of the last execution were not archived.
+    OutputsNotArchived,
+    /// The output files of the last execution could not be restored.
+    OutputsNotRestored,
+}
+
+/// A file that has changed since the last execution.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedFile {
+    pub path: PathBuf,
+    pub change: FileChangeKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// An environment variable that has changed since the last execution.
+/// Values are not kept, since they might contain secrets.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedVariable {
+    pub name: String,
+    pub change: VariableChangeKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VariableChangeKind {
+    Set,
+    Unset,
+    Modified,
+}
+
+impl ChangedFile {
+    /// Create from a detected file change, with a path relative to `root` when possible.
+    pub(super) fn new(change: &FileChange, root: &Path) -> Self {
+        Self {
+            path: change
+                .path
+                .strip_prefix(root)
+                .unwrap_or(&change.path)
+                .to_path_buf(),
+            change: match change.change_type {
+                FileChangeType::Created => FileChangeKind::Created,
+                FileChangeType::Modified => FileChangeKind::Modified,
+                FileChangeType::Removed => FileChangeKind::Removed,
+            },
+        }
+    }
+}
+
+impl Display for FileChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Created => "created",
+            Self::Modified => "modified",
+            Self::Removed => "removed",
+        })
+    }
+}
+
+impl Display for VariableChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Set => "set",
+            Self::Unset => "unset",
+            Self::Modified => "modified",
+        })
+    }
+}
+
+impl Display for InvalidationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn list<T, F: Fn(&T) -> String>(items: &[T], f: F) -> String {
+            items.iter().map(f).collect::<Vec<_>>().join(", ")
+        }
+
+        match self {
+            Self::NotCached => f.write_str("no cache state was found"),
+            Self::FingerprintNotCached { fingerprint } => write!(
+                f,
+                "no cache state was found for fingerprint {fingerprint} (dependencies, executor, input files or environment variables have changed)"
+            ),
+            Self::MissingState { check } => write!(f, "no previous state for the {check} check"),
+            Self::ConfigurationChanged => f.write_str("target configuration has changed"),
+            Self::DependenciesChanged { dependencies } => {
+                write!(f, "dependencies have changed: {}", dependencies.join(", "))
+            }
+            Self::ExecutorUpdated => f.write_str("executor was updated"),
+            Self::TtlExpired { expired_for } => write!(
+                f,
+                "TTL has expired {:?} ago",
+                Duration::from_secs(*expired_for as u64)
+            ),
+            Self::FilesMissing { files } => write!(
+                f,
+                "files are missing: {}",
+                list(files, |file| file.display().to_string())
+            ),
+            Self::InputFilesChanged { files } | Self::OutputFilesChanged { files } => write!(
+                f,
+                "{} files have changed: {}",
+                if matches!(self, Self::InputFilesChanged { .. }) {
+                    "input"
+                } else {
+                    "output"
+                },
+                list(files, |file| format!(
+                    "{} ({})",
+                    file.path.display(),
+                    file.change
+                ))
+            ),
+            Self::CommandFailed { code: Some(code) } => write!(
+                f,
+                "cache invalidation command has failed with status code {code}"
+            ),
+            Self::CommandFailed { code: None } => {
+                f.write_str("cache invalidation command has failed")
+            }
+            Self::EnvironmentChanged { variables } => write!(
+                f,
+                "environment variables have changed: {}",
+                list(variables, |variable| format!(
+                    "{} ({})",
+                    variable.name, variable.change
+                ))
+            ),
+            Self::OutputsNotArchived => f.write_str("output files were not archived"),
+            Self::OutputsNotRestored => f.write_str("output files could not be restored"),
+        }
+    }
 }
@@ -2,10 +2,10 @@ use std::{collections::HashMap, time::UNIX_EPOCH};
 
 use blaze_common::{cache::CommandFailsOptions, error::Result, value::Value};
 
-use crate::system::process::{Process, ProcessOptions};
+use crate::system::process::{Process, ProcessOptions, ProcessStatus};
 
 use super::{
-    check::{CacheInvalidationCheck, ExecutionCacheState},
+    check::{CacheInvalidationCheck, ExecutionCacheState, InvalidationReason},
     execution::TargetExecution,
 };
 
@@ -32,7 +32,7 @@ impl<'a> CommandFailsCheck<'a> {
         &self,
         execution: &TargetExecution,
         maybe_state: Option<&ExecutionCacheState>,
-    ) -> Result<bool> {
+    ) -> Result<ProcessStatus> {
         let mut environment = HashMap::from([
             (
                 OPTIONS.into(),
@@ -69,12 +69,11 @@ impl<'a> CommandFailsCheck<'a> {
                         .unwrap_or(execution.get_project().root())
                         .to_owned(),
                 ),
+                ..Default::default()
             },
         )?;
 
-        let status = process.wait()?;
-
-        Ok(status.success)
+        process.wait()
     }
 }
 
@@ -83,10 +82,10 @@ impl CacheInvalidationCheck for CommandFailsCheck<'_> {
         &mut self,
         execution: &TargetExecution,
         state: &ExecutionCacheState,
-    ) -> Result<bool> {
-        let invalidated = self.launch(execution, Some(state));
+    ) -> Result<Option<InvalidationReason>> {
+        let status = self.launch(execution, Some(state))?;
         self.is_launched = true;
-        invalidated
+        Ok((!status.success).then_some(InvalidationReason::CommandFailed { code: status.code }))
     }
 
     fn state(&self, execution: &TargetExecution) -> Result<Option<Value>> {
@@ -3,7 +3,6 @@ use std::collections::HashMap;
 use blaze_common::{
     cache::EnvChangesOptions,
     error::Result,
-    logger::Logger,
     value::{to_value, Value},
 };
 use serde::{Deserialize, Serialize};
@@ -11,25 +10,26 @@ use serde::{Deserialize, Serialize};
 use crate::system::env::Env;
 
 use super::{
-    check::{CacheInvalidationCheck, ExecutionCacheState},
+    check::{
+        CacheInvalidationCheck, ChangedVariable, ExecutionCacheState, InvalidationReason,
+        VariableChangeKind,
+    },
     execution::TargetExecution,
 };
 
 pub struct EnvChangesCheck<'a> {
     options: &'a EnvChangesOptions,
-    logger: &'a Logger,
     computed_state: Option<WatchedVariablesState>,
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Deserialize, Serialize)]
 struct WatchedVariablesState(HashMap<String, String>);
 
 const WATCHED_VARIABLES_STATE_KEY: &str = "env";
 
 impl<'a> EnvChangesCheck<'a> {
-    pub fn new(options: &'a EnvChangesOptions, logger: &'a Logger) -> Self {
+    pub fn new(options: &'a EnvChangesOptions) -> Self {
         Self {
-            logger,
             options,
             computed_state: None,
         }
@@ -51,7 +51,11 @@ impl<'a> EnvChangesCheck<'a> {
 }
 
 impl CacheInvalidationCheck for EnvChangesCheck<'_> {
-    fn validate(&mut self, _: &TargetExecution, state: &ExecutionCacheState) -> Result<bool> {
+    fn validate(
+        &mut self,
+        _: &TargetExecution,
+        state: &ExecutionCacheState,
+    ) -> Result<Option<InvalidationReason>> {
         let maybe_old_state = state
             .metadata
             .at(WATCHED_VARIABLES_STATE_KEY)
@@ -60,39 +64,40 @@ impl CacheInvalidationCheck for EnvChangesCheck<'_> {
 
         let old_state = match maybe_old_state {
             Some(state) => state,
-            None => return Ok(false),
+            None => {
+                return Ok(Some(InvalidationReason::MissingState {
+                    check: "environment variables changes".into(),
+                }))
+            }
         };
 
         let new_state = self.get_state()?;
 
-        let is_state_unchanged = new_state == old_state;
-
-        if !is_state_unchanged {
-            for name in old_state.0.keys() {
-                if !new_state.0.contains_key(name) {
-                    self.logger
-                        .debug(format!("{name} was unset (previously set)"));
-                }
-            }
-            for (name, value) in &new_state.0 {
-                let old_value = match old_state.0.get(name) {
-                    Some(old_value) => old_value,
-                    None => {
-                        self.logger
-                            .debug(format!("{name} was set (previously unset)"));
-                        continue;
-                    }
-                };
-                if old_value == value {
-                    continue;
+        let mut variables = old_state
+            .0
+            .keys()
+            .filter(|name| !new_state.0.contains_key(*name))
+            .map(|name| ChangedVariable {
+                name: name.to_owned(),
+                change: VariableChangeKind::Unset,
+            })
+            .chain(new_state.0.iter().filter_map(|(name, value)| {
+                match old_state.0.get(name) {
+                    None => Some(VariableChangeKind::Set),
+                    Some(old_value) if old_value != value => Some(VariableChangeKind::Modified),
+                    Some(_) => None,
                 }
-                self.logger.debug(format!("{name} has new value"));
-            }
-        }
+                .map(|change| ChangedVariable {
+                    name: name.to_owned(),
+                    change,
+                })
+            }))
+            .collect::<Vec<_>>();
+        variables.sort_by(|a, b| a.name.cmp(&b.name));
 
         let _ = self.computed_state.insert(new_state);
 
-        Ok(is_state_unchanged)
+        Ok((!variables.is_empty()).then_some(InvalidationReason::EnvironmentChanged { variables }))
     }
 
     fn state(&self, _: &TargetExecution) -> Result<Option<Value>> {
@@ -1,28 +1,36 @@
 use anyhow::bail;
 use rand::{thread_rng, RngCore};
 use std::{
-    convert::identity,
     fmt::Display,
     hash::{Hash, Hasher},
     sync::Arc,
 };
 
 use blaze_common::{
-    dependency::Dependency, error::Result, logger::Logger, project::Project, target::Target,
-    value::Value, workspace::Workspace,
+    cache::{CacheMode, TargetCache},
+    dependency::Dependency,
+    error::Result,
+    logger::Logger,
+    project::Project,
+    target::Target,
+    value::Value,
+    workspace::Workspace,
 };
 
 use crate::{
     executions::{
-        check::{CacheInvalidationCheck, ExecutionCacheState},
+        check::{CacheInvalidationCheck, ExecutionCacheState, InvalidationReason},
         command_fails::CommandFailsCheck,
         file_changes::InputFileChangesCheck,
         files_missing::FilesMissingCheck,
+        fingerprint::fingerprint,
+        logs::TargetLog,
+        outputs::{restore_outputs, OutputsCheck},
         propagating_children::PropagatingChildrenCheck,
         ttl::TtlCheck,
     },
     executors::ExecutorCacheState,
-    system::{hash::hasher, time::now},
+    system::{hash::hasher, hash_cache::FileHashCache, time::now},
     workspace::cache_store::CacheStore,
 };
 
@@ -53,12 +61,26 @@ impl PartialEq for TargetExecution {
 
 pub enum CachedExecutionState<T> {
     Cached(u64),
-    New(u64, T),
+    /// A new execution, along with the reasons why the previous cache state could not be used.
+    New(u64, T, Vec<InvalidationReason>),
     NoCache(T),
 }
 
+/// Cache state of an execution, evaluated before executing it.
+struct CacheEvaluation<'a> {
+    /// Key of the execution cache state in the cache store.
+    key: String,
+    last_state: Option<ExecutionCacheState>,
+    checks: Vec<Box<dyn CacheInvalidationCheck + 'a>>,
+    /// Why the last cache state cannot be used, empty if it is valid.
+    reasons: Vec<InvalidationReason>,
+    /// Compute the hash of an execution from its nonce.
+    execution_hash: Box<dyn Fn(u64) -> u64>,
+}
+
 pub struct CachedDependencyExecution<'a, T> {
     pub double: String,
+    pub execution: &'a TargetExecution,
     pub state: Option<&'a Result<CachedExecutionState<T>>>,
     pub source: &'a Dependency,
 }
@@ -68,6 +90,8 @@ pub struct CachedExecutionContext<'a> {
     pub cache: &'a CacheStore,
     pub logger: &'a Logger,
     pub workspace: &'a Workspace,
+    /// Captured output of the execution, archived with its cache state and replayed when it is cached.
+    pub logs: Option<&'a TargetLog>,
 }
 
 impl TargetExecution {
@@ -86,6 +110,23 @@ impl TargetExecution {
         format!("{EXECUTIONS_STATE_KEY_PREFIX}/{double}")
     }
 
+    /// Get the double and the fingerprint (when the target uses the fingerprint mode) from an execution cache key.
+    /// Returns [`None`] if the key does not belong to a target execution state.
+    pub fn parse_cache_key(key: &str) -> Option<(&str, Option<&str>)> {
+        let state = key
+            .strip_prefix(EXECUTIONS_STATE_KEY_PREFIX)?
+            .strip_prefix('/')?;
+        Some(match state.rsplit_once('/') {
+            Some((double, fingerprint))
+                if fingerprint.len() == 16
+                    && fingerprint.chars().all(|c| c.is_ascii_hexdigit()) =>
+            {
+                (double, Some(fingerprint))
+            }
+            _ => (state, None),
+        })
+    }
+
     /// Run the function *f* if this execution is not cached.
     /// The function must return a result so that this wrapper can update the target execution cache state according to success or failure.
     pub fn cached<T, F>(
@@ -98,88 +139,40 @@ impl TargetExecution {
     where
         F: FnOnce() -> Result<T>,
     {
-        let target = self.get_target();
-
-        let target_cache = match target.cache() {
+        let target_cache = match self.get_target().cache() {
             Some(c) => c,
             None => return Ok(CachedExecutionState::NoCache(f()?)),
         };
 
-        let cache_state_key = self.get_cache_key();
-
-        let invalidation_strategy = target_cache.invalidate_when();
-        let mut checks: Vec<(&str, Box<dyn CacheInvalidationCheck>)> = vec![(
-            "child cache propagation",
-            Box::new(PropagatingChildrenCheck::new(child_executions)),
-        )];
-        checks.extend(
-            vec![
-                executor_cache.map(|(state, nonce)| {
-                    (
-                        "executor was updated",
-                        Box::new(ExecutorUpdateCheck::new(state, nonce, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.expired().map(|options| {
-                    (
-                        "ttl expired",
-                        Box::new(TtlCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.files_missing().map(|options| {
-                    (
-                        "files were missing",
-                        Box::new(FilesMissingCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.input_changes().map(|options| {
-                    (
-                        "input file(s) changed",
-                        Box::new(InputFileChangesCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.output_changes().map(|options| {
-                    (
-                        "output file(s) changed",
-                        Box::new(OutputFileChangesCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.command_fails().map(|options| {
-                    (
-                        "cache invalidation command failed",
-                        Box::new(CommandFailsCheck::new(options))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-                invalidation_strategy.env_changes().map(|options| {
-                    (
-                        "environment variables changed",
-                        Box::new(EnvChangesCheck::new(options, context.logger))
-                            as Box<dyn CacheInvalidationCheck>,
-                    )
-                }),
-            ]
-            .into_iter()
-            .flatten(),
-        );
-
-        let mut hasher = hasher();
-        self.project.root().hash(&mut hasher);
-        self.get_target().hash(&mut hasher);
+        let CacheEvaluation {
+            key,
+            last_state,
+            checks,
+            mut reasons,
+            execution_hash,
+        } = self.evaluate_cache(target_cache, child_executions, executor_cache, context)?;
+
+        if let (true, Some(last_state)) = (reasons.is_empty(), &last_state) {
+            if !target_cache.outputs().is_empty()
+                && !restore_outputs(self, last_state, context.cache, context.logger)?
+            {
+                reasons.push(InvalidationReason::OutputsNotRestored);
+            }
+        }
 
-        let mut hasher_before_nonce = hasher.clone();
+        let last_state = match last_state {
+            Some(last_state) if reasons.is_empty() => last_state,
+            _ => {
+                for reason in &reasons {
+                    context
+                        .logger
+                        .debug(format!("{self} cache will be invalidated ({reason})"));
+                }
 
-        let execute_and_cache =
-            |checks: Vec<Box<dyn CacheInvalidationCheck>>| -> Result<CachedExecutionState<T>> {
                 let execution_result = match f() {
                     Ok(value) => value,
                     Err(err) => {
-                        context.cache.invalidate(&cache_state_key)?;
+                        context.cache.invalidate(&key)?;
                         bail!(err)
                     }
                 };
@@ -191,11 +184,19 @@ impl TargetExecution {
                     }
                 }
 
+                if let Some(logs) = context.logs {
+                    match logs.archive(context.cache) {
+                        Ok(state) => metadata.overwrite(state),
+                        Err(err) => context
+                            .logger
+                            .warn(format!("could not archive output of {self}: {err:?}")),
+                    }
+                }
+
                 let nonce = thread_rng().next_u64();
-                nonce.hash(&mut hasher_before_nonce);
-                let new_hash = hasher_before_nonce.finish();
+                let new_hash = execution_hash(nonce);
                 context.cache.cache(
-                    &cache_state_key,
+                    &key,
                     &ExecutionCacheState {
                         nonce,
                         hash: new_hash,
@@ -204,52 +205,182 @@ impl TargetExecution {
                     },
                 )?;
 
-                Ok(CachedExecutionState::New(new_hash, execution_result))
-            };
-
-        let maybe_last_execution = context
-            .cache
-            .restore::<ExecutionCacheState>(&cache_state_key)?;
+                return Ok(CachedExecutionState::New(
+                    new_hash,
+                    execution_result,
+                    reasons,
+                ));
+            }
+        };
 
-        if maybe_last_execution.is_none() {
-            context.logger.debug(format!("{self} was not cached."));
-            return execute_and_cache(checks.into_iter().map(|(_, check)| check).collect());
+        if let Some(logs) = context.logs {
+            if !logs.replay(&last_state, context.cache)? {
+                context
+                    .logger
+                    .debug(format!("{self} has no archived output to replay"));
+            }
         }
 
-        let last_execution_state = maybe_last_execution.unwrap();
+        Ok(CachedExecutionState::Cached(execution_hash(
+            last_state.nonce,
+        )))
+    }
+
+    /// Evaluate the cache of this execution without executing it, nor restoring its output files.
+    /// Returns [`None`] if the target has no cache, or the reasons why the cache would be invalidated (empty if it is valid) along with the hash of the cached execution.
+    pub fn explain_cache<T>(
+        &self,
+        child_executions: &[CachedDependencyExecution<T>],
+        executor_cache: Option<(ExecutorCacheState, u64)>,
+        context: CachedExecutionContext<'_>,
+    ) -> Result<Option<(Vec<InvalidationReason>, Option<u64>)>> {
+        let target_cache = match self.get_target().cache() {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        let evaluation =
+            self.evaluate_cache(target_cache, child_executions, executor_cache, context)?;
 
-        let is_cache_valid = checks
-            .iter_mut()
-            .map(|(reason, check)| {
-                let validated = check.validate(self, &last_execution_state)?;
+        let hash = evaluation
+            .last_state
+            .as_ref()
+            .map(|last_state| (evaluation.execution_hash)(last_state.nonce));
 
-                if !validated {
-                    context
-                        .logger
-                        .debug(format!("{self} cache will be invalidated ({reason})"))
-                }
+        Ok(Some((evaluation.reasons, hash)))
+    }
+
+    /// Find the last cache state of this execution and run all invalidation checks against it.
+    fn evaluate_cache<'a, T>(
+        &'a self,
+        target_cache: &'a TargetCache,
+        child_executions: &'a [CachedDependencyExecution<T>],
+        executor_cache: Option<(ExecutorCacheState, u64)>,
+        context: CachedExecutionContext<'a>,
+    ) -> Result<CacheEvaluation<'a>> {
+        let mut hasher = hasher();
+        // the project path is hashed relatively to the workspace root, so that cache states can be shared across machines.
+        self.project
+            .root()
+            .strip_prefix(context.workspace.root())
+            .unwrap_or(self.project.root())
+            .hash(&mut hasher);
+        self.get_target().hash(&mut hasher);
+
+        let file_hashes = FileHashCache::for_workspace(context.workspace.root())?;
+
+        let fingerprint = match target_cache.mode() {
+            CacheMode::LastState => None,
+            CacheMode::Fingerprint => {
+                let fingerprint = fingerprint(
+                    self,
+                    hasher.clone(),
+                    child_executions,
+                    executor_cache.map(|(_, digest)| digest),
+                    &file_hashes,
+                )?;
+                context
+                    .logger
+                    .debug(format!("{self} fingerprint is {fingerprint:016x}"));
+                Some(fingerprint)
+            }
+        };
+
+        let key = match fingerprint {
+            Some(fingerprint) => format!("{}/{fingerprint:016x}", self.get_cache_key()),
+            None => self.get_cache_key(),
+        };
 
-                Ok(validated)
+        // in fingerprint mode, dependencies, executor, input files and environment variables are part of the cache key.
+        let is_state_mode = fingerprint.is_none();
+
+        let invalidation_strategy = target_cache.invalidate_when();
+        let mut checks: Vec<Box<dyn CacheInvalidationCheck + 'a>> = vec![
+            is_state_mode.then(|| {
+                Box::new(PropagatingChildrenCheck::new(
+                    child_executions,
+                    file_hashes.clone(),
+                )) as Box<dyn CacheInvalidationCheck>
+            }),
+            executor_cache
+                .filter(|_| is_state_mode)
+                .map(|(state, digest)| {
+                    Box::new(ExecutorUpdateCheck::new(state, digest))
+                        as Box<dyn CacheInvalidationCheck>
+                }),
+            invalidation_strategy
+                .expired()
+                .map(|options| Box::new(TtlCheck::new(options)) as Box<dyn CacheInvalidationCheck>),
+            invalidation_strategy.files_missing().map(|options| {
+                Box::new(FilesMissingCheck::new(options)) as Box<dyn CacheInvalidationCheck>
+            }),
+            invalidation_strategy
+                .input_changes()
+                .filter(|_| is_state_mode)
+                .map(|options| {
+                    Box::new(InputFileChangesCheck::new(options, file_hashes.clone()))
+                        as Box<dyn CacheInvalidationCheck>
+                }),
+            invalidation_strategy.output_changes().map(|options| {
+                Box::new(OutputFileChangesCheck::new(options, file_hashes.clone()))
+                    as Box<dyn CacheInvalidationCheck>
+            }),
+            invalidation_strategy.command_fails().map(|options| {
+                Box::new(CommandFailsCheck::new(options)) as Box<dyn CacheInvalidationCheck>
+            }),
+            invalidation_strategy
+                .env_changes()
+                .filter(|_| is_state_mode)
+                .map(|options| {
+                    Box::new(EnvChangesCheck::new(options)) as Box<dyn CacheInvalidationCheck>
+                }),
+            (!target_cache.outputs().is_empty()).then(|| {
+                Box::new(OutputsCheck::new(
+                    target_cache.outputs(),
+                    context.cache,
+                    context.logger,
+                )) as Box<dyn CacheInvalidationCheck>
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let execution_hash = Box::new(move |nonce: u64| {
+            fingerprint.unwrap_or_else(|| {
+                let mut hasher = hasher.clone();
+                nonce.hash(&mut hasher);
+                hasher.finish()
             })
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .all(identity);
+        });
 
-        if !is_cache_valid {
-            return execute_and_cache(checks.into_iter().map(|(_, check)| check).collect());
-        }
+        let last_state = context.cache.restore::<ExecutionCacheState>(&key)?;
 
-        last_execution_state.nonce.hash(&mut hasher);
-        let current_execution_hash = hasher.finish();
+        let mut reasons = vec![];
 
-        if last_execution_state.hash != current_execution_hash {
-            context.logger.debug(format!(
-                "{self} configuration changed, cache will be invalidated"
-            ));
-            return execute_and_cache(checks.into_iter().map(|(_, check)| check).collect());
+        match (&last_state, fingerprint) {
+            (None, None) => reasons.push(InvalidationReason::NotCached),
+            (None, Some(fingerprint)) => reasons.push(InvalidationReason::FingerprintNotCached {
+                fingerprint: format!("{fingerprint:016x}"),
+            }),
+            (Some(last_state), _) => {
+                // all checks are validated, since they might compute their new state at the same time.
+                for check in &mut checks {
+                    reasons.extend(check.validate(self, last_state)?);
+                }
+                if reasons.is_empty() && last_state.hash != execution_hash(last_state.nonce) {
+                    reasons.push(InvalidationReason::ConfigurationChanged);
+                }
+            }
         }
 
-        Ok(CachedExecutionState::Cached(current_execution_hash))
+        Ok(CacheEvaluation {
+            key,
+            last_state,
+            checks,
+            reasons,
+            execution_hash,
+        })
     }
 
     /// Synchronized pointer to the project data.
@@ -257,6 +388,11 @@ impl TargetExecution {
         self.project.clone()
     }
 
+    /// The name of the project for this execution.
+    pub fn get_project_name(&self) -> &str {
+        self.project.name()
+    }
+
     /// The name of the target for this execution.
     pub fn get_target_name(&self) -> &str {
         &self.target_name
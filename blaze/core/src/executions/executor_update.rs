@@ -1,65 +1,56 @@
-use blaze_common::{error::Result, logger::Logger, value::Value};
-use serde::{Deserialize, Serialize};
+use blaze_common::{error::Result, value::Value};
+use serde::Deserialize;
 
 use crate::executors::ExecutorCacheState;
 
 use super::{
-    check::{CacheInvalidationCheck, ExecutionCacheState},
+    check::{CacheInvalidationCheck, ExecutionCacheState, InvalidationReason},
     execution::TargetExecution,
 };
 
-pub struct ExecutorUpdateCheck<'a> {
+pub struct ExecutorUpdateCheck {
     state: ExecutorCacheState,
-    nonce: u64,
-    logger: &'a Logger,
-}
-
-#[derive(Serialize, Deserialize)]
-struct State {
-    nonce: u64,
+    digest: u64,
 }
 
 const EXECUTOR_STATE_KEY: &str = "executor_state";
 
-impl<'a> ExecutorUpdateCheck<'a> {
-    pub fn new(state: ExecutorCacheState, nonce: u64, logger: &'a Logger) -> Self {
-        Self {
-            logger,
-            state,
-            nonce,
-        }
+impl ExecutorUpdateCheck {
+    pub fn new(state: ExecutorCacheState, digest: u64) -> Self {
+        Self { state, digest }
     }
 }
 
-impl CacheInvalidationCheck for ExecutorUpdateCheck<'_> {
+impl CacheInvalidationCheck for ExecutorUpdateCheck {
     fn state(&self, _: &TargetExecution) -> Result<Option<Value>> {
         Ok(Some(Value::object([(
             EXECUTOR_STATE_KEY,
-            Value::unsigned(self.nonce),
+            Value::unsigned(self.digest),
         )])))
     }
 
-    fn validate(&mut self, _: &TargetExecution, cache_state: &ExecutionCacheState) -> Result<bool> {
-        let maybe_state = cache_state
+    fn validate(
+        &mut self,
+        _: &TargetExecution,
+        cache_state: &ExecutionCacheState,
+    ) -> Result<Option<InvalidationReason>> {
+        let maybe_digest = cache_state
             .metadata
             .at(EXECUTOR_STATE_KEY)
-            .map(State::deserialize)
-            .and_then(|state| state.ok());
-
-        if maybe_state.is_none() {
-            return Ok(false);
-        }
-
-        let current_state = maybe_state.unwrap();
+            .map(u64::deserialize)
+            .and_then(|digest| digest.ok());
 
-        let valid =
-            matches!(self.state, ExecutorCacheState::Cached) && self.nonce == current_state.nonce;
+        let last_digest = match maybe_digest {
+            Some(state) => state,
+            None => {
+                return Ok(Some(InvalidationReason::MissingState {
+                    check: "executor update".into(),
+                }))
+            }
+        };
 
-        if !valid {
-            self.logger
-                .debug("executor was updated, target cache will be invalidated");
-        }
+        let valid = matches!(self.state, ExecutorCacheState::Cached) && self.digest == last_digest;
 
-        Ok(valid)
+        Ok((!valid).then_some(InvalidationReason::ExecutorUpdated))
     }
 }
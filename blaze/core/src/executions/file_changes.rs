@@ -1,31 +1,50 @@
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, sync::Arc};
 
 use blaze_common::{
     cache::FileChangesMatcher,
     error::Result,
-    logger::Logger,
     value::{to_value, Value},
 };
 
-use crate::system::file_changes::{FileChangeType, MatchedFiles, MatchedFilesState};
+use crate::system::{
+    file_changes::{MatchedFiles, MatchedFilesState, MergeResult},
+    hash_cache::FileHashCache,
+};
 use serde::Deserialize;
 
 use super::{
-    check::{CacheInvalidationCheck, ExecutionCacheState},
+    check::{CacheInvalidationCheck, ChangedFile, ExecutionCacheState, InvalidationReason},
     execution::TargetExecution,
 };
 
 const INPUT_FILE_CHANGES_STATE_KEY: &str = "input-file-changes";
 const OUTPUT_FILE_CHANGES_STATE_KEY: &str = "output-file-changes";
 
+/// Files that have changed according to a merge result, relative to the project root and sorted by path.
+fn changed_files(execution: &TargetExecution, merge_result: &MergeResult) -> Vec<ChangedFile> {
+    let mut files = merge_result
+        .changes
+        .iter()
+        .map(|change| ChangedFile::new(change, execution.get_project().root()))
+        .collect::<Vec<_>>();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
 pub struct OutputFileChangesCheck<'a> {
-    logger: &'a Logger,
     matchers: &'a BTreeSet<FileChangesMatcher>,
+    file_hashes: Arc<FileHashCache>,
 }
 
 impl<'a> OutputFileChangesCheck<'a> {
-    pub fn new(matchers: &'a BTreeSet<FileChangesMatcher>, logger: &'a Logger) -> Self {
-        Self { matchers, logger }
+    pub fn new(
+        matchers: &'a BTreeSet<FileChangesMatcher>,
+        file_hashes: Arc<FileHashCache>,
+    ) -> Self {
+        Self {
+            matchers,
+            file_hashes,
+        }
     }
 }
 
@@ -33,10 +52,10 @@ impl CacheInvalidationCheck for OutputFileChangesCheck<'_> {
     fn state(&self, execution: &TargetExecution) -> Result<Option<Value>> {
         Ok(Some(Value::object([(
             OUTPUT_FILE_CHANGES_STATE_KEY,
-            to_value(MatchedFilesState::from_files(MatchedFiles::try_new(
-                execution.get_project().root(),
-                self.matchers,
-            )?)?)?,
+            to_value(MatchedFilesState::from_files(
+                MatchedFiles::try_new(execution.get_project().root(), self.matchers)?,
+                &self.file_hashes,
+            )?)?,
         )])))
     }
 
@@ -44,7 +63,7 @@ impl CacheInvalidationCheck for OutputFileChangesCheck<'_> {
         &mut self,
         execution: &TargetExecution,
         cached: &ExecutionCacheState,
-    ) -> Result<bool> {
+    ) -> Result<Option<InvalidationReason>> {
         let maybe_last_state = cached
             .metadata
             .at(OUTPUT_FILE_CHANGES_STATE_KEY)
@@ -53,37 +72,40 @@ impl CacheInvalidationCheck for OutputFileChangesCheck<'_> {
 
         let last_state = match maybe_last_state {
             Some(state) => state,
-            None => return Ok(false),
+            None => {
+                return Ok(Some(InvalidationReason::MissingState {
+                    check: "output file changes".into(),
+                }))
+            }
         };
 
         let current_matched_files =
             MatchedFiles::try_new(execution.get_project().root(), self.matchers)?;
 
-        let merge_result = last_state.merge(current_matched_files)?;
-
-        for change in &merge_result.changes {
-            self.logger.debug(match change.change_type {
-                FileChangeType::Created => format!("{} was created", change.path.display()),
-                FileChangeType::Modified => format!("{} was modified", change.path.display()),
-                FileChangeType::Removed => format!("{} was removed", change.path.display()),
-            });
-        }
+        let merge_result = last_state.merge(current_matched_files, &self.file_hashes)?;
 
-        Ok(merge_result.changes.is_empty())
+        Ok(
+            (!merge_result.changes.is_empty()).then(|| InvalidationReason::OutputFilesChanged {
+                files: changed_files(execution, &merge_result),
+            }),
+        )
     }
 }
 
 pub struct InputFileChangesCheck<'a> {
-    logger: &'a Logger,
     matchers: &'a BTreeSet<FileChangesMatcher>,
+    file_hashes: Arc<FileHashCache>,
     computed_state: Option<MatchedFilesState>,
 }
 
 impl<'a> InputFileChangesCheck<'a> {
-    pub fn new(matchers: &'a BTreeSet<FileChangesMatcher>, logger: &'a Logger) -> Self {
+    pub fn new(
+        matchers: &'a BTreeSet<FileChangesMatcher>,
+        file_hashes: Arc<FileHashCache>,
+    ) -> Self {
         Self {
             matchers,
-            logger,
+            file_hashes,
             computed_state: None,
         }
     }
@@ -96,10 +118,10 @@ impl CacheInvalidationCheck for InputFileChangesCheck<'_> {
             if let Some(files) = self.computed_state.as_ref() {
                 to_value(files)?
             } else {
-                to_value(MatchedFilesState::from_files(MatchedFiles::try_new(
-                    execution.get_project().root(),
-                    self.matchers,
-                )?)?)?
+                to_value(MatchedFilesState::from_files(
+                    MatchedFiles::try_new(execution.get_project().root(), self.matchers)?,
+                    &self.file_hashes,
+                )?)?
             },
         )])))
     }
@@ -108,7 +130,7 @@ impl CacheInvalidationCheck for InputFileChangesCheck<'_> {
         &mut self,
         execution: &TargetExecution,
         cached: &ExecutionCacheState,
-    ) -> Result<bool> {
+    ) -> Result<Option<InvalidationReason>> {
         let maybe_last_state = cached
             .metadata
             .at(INPUT_FILE_CHANGES_STATE_KEY)
@@ -117,24 +139,25 @@ impl CacheInvalidationCheck for InputFileChangesCheck<'_> {
 
         let last_state = match maybe_last_state {
             Some(state) => state,
-            None => return Ok(false),
+            None => {
+                return Ok(Some(InvalidationReason::MissingState {
+                    check: "input file changes".into(),
+                }))
+            }
         };
 
         let current_matched_files =
             MatchedFiles::try_new(execution.get_project().root(), self.matchers)?;
 
-        let merge_result = last_state.merge(current_matched_files)?;
-
-        let _ = self.computed_state.insert(merge_result.state);
+        let merge_result = last_state.merge(current_matched_files, &self.file_hashes)?;
 
-        for change in &merge_result.changes {
-            self.logger.debug(match change.change_type {
-                FileChangeType::Created => format!("{} was created", change.path.display()),
-                FileChangeType::Modified => format!("{} was modified", change.path.display()),
-                FileChangeType::Removed => format!("{} was removed", change.path.display()),
+        let reason =
+            (!merge_result.changes.is_empty()).then(|| InvalidationReason::InputFilesChanged {
+                files: changed_files(execution, &merge_result),
             });
-        }
 
-        Ok(merge_result.changes.is_empty())
+        let _ = self.computed_state.insert(merge_result.state);
+
+        Ok(reason)
     }
 }
@@ -0,0 +1,29 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::Context;
+use blaze_common::error::Result;
+
+use crate::{system::hash_cache::FileHashCache, workspace::cache_store::CacheStore};
+
+const FILE_HASHES_CACHE_KEY: &str = "file-hashes";
+
+/// Load file checksums computed by previous runs, so that unchanged files are not read again.
+pub fn load_file_hashes(cache: &CacheStore, root: &Path) -> Result<()> {
+    let hashes = cache
+        .restore::<HashMap<_, _>>(FILE_HASHES_CACHE_KEY)
+        .context("could not restore file hashes")?;
+    if let Some(hashes) = hashes {
+        FileHashCache::for_workspace(root)?.extend(hashes)?;
+    }
+    Ok(())
+}
+
+/// Store checksums of the workspace files, if some were computed since they were last stored.
+pub fn save_file_hashes(cache: &CacheStore, root: &Path) -> Result<()> {
+    match FileHashCache::for_workspace(root)?.updated_hashes(root)? {
+        Some(hashes) => cache
+            .cache(FILE_HASHES_CACHE_KEY, &hashes)
+            .context("could not store file hashes"),
+        None => Ok(()),
+    }
+}
@@ -1,26 +1,30 @@
 use std::{borrow::Cow, collections::BTreeSet, path::PathBuf};
 
 use anyhow::bail;
-use blaze_common::{error::Result, logger::Logger};
+use blaze_common::error::Result;
 
 use super::{
-    check::{CacheInvalidationCheck, ExecutionCacheState},
+    check::{CacheInvalidationCheck, ExecutionCacheState, InvalidationReason},
     execution::TargetExecution,
 };
 
 pub struct FilesMissingCheck<'a> {
-    logger: &'a Logger,
     files: &'a BTreeSet<PathBuf>,
 }
 
 impl<'a> FilesMissingCheck<'a> {
-    pub fn new(files: &'a BTreeSet<PathBuf>, logger: &'a Logger) -> Self {
-        Self { files, logger }
+    pub fn new(files: &'a BTreeSet<PathBuf>) -> Self {
+        Self { files }
     }
 }
 
 impl CacheInvalidationCheck for FilesMissingCheck<'_> {
-    fn validate(&mut self, execution: &TargetExecution, _: &ExecutionCacheState) -> Result<bool> {
+    fn validate(
+        &mut self,
+        execution: &TargetExecution,
+        _: &ExecutionCacheState,
+    ) -> Result<Option<InvalidationReason>> {
+        let mut missing = vec![];
         for path in self.files {
             let normalized_path = if path.is_relative() {
                 Cow::Owned(execution.get_project().root().join(path))
@@ -35,11 +39,9 @@ impl CacheInvalidationCheck for FilesMissingCheck<'_> {
             };
 
             if !exists {
-                self.logger
-                    .debug(format!("{} is missing", normalized_path.display()));
-                return Ok(false);
+                missing.push(normalized_path.into_owned());
             }
         }
-        Ok(true)
+        Ok((!missing.is_empty()).then_some(InvalidationReason::FilesMissing { files: missing }))
     }
 }
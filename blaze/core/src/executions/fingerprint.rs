@@ -0,0 +1,97 @@
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
+
+use anyhow::Context;
+use blaze_common::{cache::MatchingBehavior, error::Result};
+
+use crate::system::{env::Env, glob::glob, hash_cache::FileHashCache, repository::GitFileIds};
+
+use super::{
+    execution::{CachedDependencyExecution, TargetExecution},
+    propagating_children::propagated_hash,
+};
+
+/// Compute a deterministic fingerprint for a target execution.
+/// The provided hasher must already contain the target configuration.
+/// Input files (by content), watched environment variables, the executor resolution and dependency states are added to it.
+pub fn fingerprint<H, T>(
+    execution: &TargetExecution,
+    mut hasher: H,
+    children: &[CachedDependencyExecution<T>],
+    executor_digest: Option<u64>,
+    file_hashes: &FileHashCache,
+) -> Result<u64>
+where
+    H: Hasher,
+{
+    let project = execution.get_project();
+    let invalidation_strategy = execution
+        .get_target()
+        .cache()
+        .map(|cache| cache.invalidate_when());
+
+    executor_digest.hash(&mut hasher);
+
+    let mut children_hashes = BTreeMap::new();
+    for child in children {
+        if let Some(hash) = propagated_hash(child, file_hashes)? {
+            let _ = children_hashes.insert(child.double.as_str(), hash);
+        }
+    }
+    children_hashes.hash(&mut hasher);
+
+    if let Some(matchers) = invalidation_strategy.and_then(|s| s.input_changes()) {
+        // paths matched by a matcher using the git behavior are identified by their git object id.
+        let mut paths = BTreeMap::<PathBuf, bool>::new();
+        for matcher in matchers {
+            let use_git = matcher.behavior() == MatchingBehavior::Git;
+            for path in glob(
+                matcher.root().unwrap_or(project.root()),
+                matcher.pattern(),
+                matcher.exclude().iter().map(String::as_str),
+            )
+            .context("failure while walking across input files")?
+            {
+                *paths.entry(path).or_default() |= use_git;
+            }
+        }
+
+        let git_ids = if paths.values().any(|use_git| *use_git) {
+            GitFileIds::discover(project.root())
+                .context("could not read git repository of input files")?
+        } else {
+            None
+        };
+
+        for (path, use_git) in paths {
+            path.strip_prefix(project.root())
+                .unwrap_or(&path)
+                .hash(&mut hasher);
+            match git_ids
+                .as_ref()
+                .filter(|_| use_git)
+                .map(|ids| ids.get(&path))
+                .transpose()?
+                .flatten()
+            {
+                Some(id) => id.hash(&mut hasher),
+                None => file_hashes
+                    .hash_file(&path)
+                    .with_context(|| format!("could not compute hash of {}", path.display()))?
+                    .hash(&mut hasher),
+            }
+        }
+    }
+
+    if let Some(options) = invalidation_strategy.and_then(|s| s.env_changes()) {
+        for variable in options.variables() {
+            variable.hash(&mut hasher);
+            Env::get_as_str(variable.as_str())?.hash(&mut hasher);
+        }
+    }
+
+    Ok(hasher.finish())
+}
@@ -1,8 +1,11 @@
 use std::{
+    cmp::Reverse,
     collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt::Display,
     panic::{RefUnwindSafe, UnwindSafe},
     sync::Arc,
     thread::scope,
+    time::Duration,
 };
 
 use anyhow::{anyhow, bail, Context};
@@ -16,7 +19,7 @@ use blaze_common::{
 };
 
 use crate::{
-    system::parallel_executor::ParallelRunner,
+    system::parallel_executor::{ParallelRunner, Resources},
     workspace::{
         configurations::DeserializationContext,
         project_handle::{ProjectHandle, ProjectOptions},
@@ -24,16 +27,37 @@ use crate::{
     },
 };
 
-use super::execution::TargetExecution;
+use super::{execution::TargetExecution, timings::ExecutionTimings};
 
 type DependencyGraph = HashMap<String, DependencyGraphNode>;
 
+/// Error returned by an execution routine when a target was not executed because another target has failed.
+/// The target is then reported as skipped instead of failed.
+#[derive(Debug)]
+pub struct Skipped;
+
+impl Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not executed because another target has failed")
+    }
+}
+
+impl std::error::Error for Skipped {}
+
+/// Check if the error means that the target was skipped.
+pub fn is_skipped(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<Skipped>().is_some()
+}
+
 /// Provides our main graph execution logic.
 /// It handles dependencies resolution on instanciation as well as parallel execution model.
 /// The execution routine for each target is user-provided.
 #[derive(Debug)]
 pub struct ExecutionGraph {
     dependency_graph: DependencyGraph,
+    priorities: HashMap<String, Duration>,
+    resource_pools: Resources,
+    fail_fast: bool,
 }
 
 /// Data needed when instanciating an [`ExecutionGraph`].
@@ -56,6 +80,7 @@ impl<T> ExecutedNode<T> {
         execution: TargetExecution,
         dependencies: HashSet<String>,
         return_value: Option<Result<T>>,
+        skipped: bool,
     ) -> Self {
         Self {
             root,
@@ -64,6 +89,7 @@ impl<T> ExecutedNode<T> {
                 dependencies.into_iter().map(|double| double.to_string()),
             ),
             result: return_value,
+            skipped,
         }
     }
 }
@@ -125,6 +151,7 @@ impl<T> ExecutedGraph<T> {
                             dependencies: execution_result.dependencies,
                             execution: execution_result.execution,
                             result: execution_result.result.map(|result| result.map(&mut f)),
+                            skipped: execution_result.skipped,
                         },
                     )
                 })
@@ -133,6 +160,15 @@ impl<T> ExecutedGraph<T> {
     }
 }
 
+/// A single target in an [`ExecutionGraph`], before execution.
+pub struct GraphNode<'a> {
+    pub double: &'a str,
+    pub root: bool,
+    pub execution: &'a TargetExecution,
+    /// Execution doubles of the direct dependencies, along with the dependency declarations they originate from.
+    pub dependencies: Vec<(&'a str, &'a Dependency)>,
+}
+
 /// A single target after execution.
 #[derive(Debug)]
 pub struct ExecutedNode<T> {
@@ -140,6 +176,8 @@ pub struct ExecutedNode<T> {
     pub execution: TargetExecution,
     pub dependencies: HashSet<String>,
     pub result: Option<Result<T>>,
+    /// Whether the target was not executed because of a failure (of one of its dependencies, or of any target when failing fast).
+    pub skipped: bool,
 }
 
 pub struct InternalChildExecutionResult<T> {
@@ -157,21 +195,24 @@ pub struct ChildExecutionResult<'a, 'b, T> {
 
 impl ExecutionGraph {
     /// Create a new execution graph using the provided options and context.
+    /// Each root is a target name along with the projects selection it applies to. Targets that are shared between roots are only executed once.
     /// All dependencies will be resolved recursively.
-    pub fn try_new(
-        selection: &Selection,
-        target: &str,
+    pub fn try_new<'r, R: IntoIterator<Item = (&'r Selection, &'r str)>>(
+        roots: R,
         options: ExecutionGraphOptions<'_>,
     ) -> Result<Self> {
         let mut dependency_graph = HashMap::<String, DependencyGraphNode>::new();
 
         let mut projects = HashMap::<String, Arc<Project>>::new();
-        let mut resolutions = VecDeque::from([DependenciesResolution {
-            selection: Some(selection.clone()),
-            target: target.to_owned(),
-            ancestor: None,
-            depth: 0,
-        }]);
+        let mut resolutions = roots
+            .into_iter()
+            .map(|(selection, target)| DependenciesResolution {
+                selection: Some(selection.clone()),
+                target: target.to_owned(),
+                ancestor: None,
+                depth: 0,
+            })
+            .collect::<VecDeque<_>>();
 
         while let Some(DependenciesResolution {
             selection,
@@ -185,6 +226,7 @@ impl ExecutionGraph {
                     let refs = selection
                         .select(SelectionContext {
                             workspace: options.workspace,
+                            deserialization_context: options.deserialization_context,
                         })
                         .context("could not select projects")?;
 
@@ -276,7 +318,12 @@ impl ExecutionGraph {
 
         check_for_circular_dependencies(&dependency_graph)?;
 
-        Ok(Self { dependency_graph })
+        Ok(Self {
+            dependency_graph,
+            priorities: HashMap::new(),
+            resource_pools: Resources::new(),
+            fail_fast: false,
+        })
     }
 
     /// Get a list of all executor URLs required to execute this graph.
@@ -293,6 +340,155 @@ impl ExecutionGraph {
         self.dependency_graph.keys().map(String::as_str).collect()
     }
 
+    /// Iterate over all target executions in this graph.
+    pub fn executions(&self) -> impl Iterator<Item = &TargetExecution> {
+        self.dependency_graph
+            .values()
+            .map(|node| node.target_execution.as_ref())
+    }
+
+    /// Get all nodes of this graph along with their dependencies, sorted by execution double.
+    pub fn nodes(&self) -> Vec<GraphNode<'_>> {
+        let mut nodes = self
+            .dependency_graph
+            .iter()
+            .map(|(double, node)| GraphNode {
+                double,
+                root: node.root,
+                execution: node.target_execution.as_ref(),
+                dependencies: node
+                    .dependencies
+                    .iter()
+                    .map(|(dependency_double, accessor)| {
+                        (
+                            dependency_double.as_str(),
+                            AsRef::<Dependency>::as_ref(accessor.as_ref()),
+                        )
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|node| node.double);
+        nodes
+    }
+
+    /// Get the provided execution doubles along with all the doubles that depend on them, recursively.
+    pub fn with_dependents<'a, I: IntoIterator<Item = &'a str>>(
+        &self,
+        doubles: I,
+    ) -> HashSet<String> {
+        let inverted_dependencies = self.create_inverted_dependency_graph();
+        let mut next = doubles
+            .into_iter()
+            .filter_map(|double| self.dependency_graph.get_key_value(double))
+            .map(|(double, _)| double)
+            .collect::<Vec<_>>();
+        let mut selected = HashSet::<String>::with_capacity(next.len());
+
+        while let Some(double) = next.pop() {
+            if selected.insert(double.to_owned()) {
+                next.extend(inverted_dependencies[double].keys());
+            }
+        }
+
+        selected
+    }
+
+    /// Create a copy of this graph, so that it can be executed again.
+    pub fn duplicate(&self) -> Result<Self> {
+        Ok(Self {
+            dependency_graph: self
+                .dependency_graph
+                .iter()
+                .map(|(double, node)| {
+                    let execution = &node.target_execution;
+                    Ok((
+                        double.to_owned(),
+                        DependencyGraphNode {
+                            root: node.root,
+                            target_execution: Arc::new(
+                                TargetExecution::try_new(
+                                    execution.get_project(),
+                                    execution.get_target_name(),
+                                )
+                                .ok_or_else(|| anyhow!("could not duplicate {double}"))?,
+                            ),
+                            dependencies: node.dependencies.clone(),
+                        },
+                    ))
+                })
+                .collect::<Result<_>>()?,
+            priorities: self.priorities.clone(),
+            resource_pools: self.resource_pools.clone(),
+            fail_fast: self.fail_fast,
+        })
+    }
+
+    /// When enabled, no more targets are executed as soon as one target fails, even if they do not depend on it.
+    /// Otherwise, only targets that depend on the failed one are skipped.
+    pub fn fail_fast(&mut self, enabled: bool) {
+        self.fail_fast = enabled;
+    }
+
+    /// Limit concurrent target executions according to the resources they use.
+    /// An error is returned if a target uses an unknown resource pool, or more resources than its pool capacity.
+    pub fn limit_resources(&mut self, pools: &Resources) -> Result<()> {
+        for (double, node) in &self.dependency_graph {
+            for (name, amount) in node.target_execution.get_target().resources() {
+                match pools.get(name) {
+                    None => bail!("target {double} uses unknown resource pool \"{name}\""),
+                    Some(capacity) if amount > capacity => bail!(
+                        "target {double} uses {amount} resource(s) from pool \"{name}\", which has a capacity of {capacity}"
+                    ),
+                    _ => {}
+                }
+            }
+        }
+        pools.clone_into(&mut self.resource_pools);
+        Ok(())
+    }
+
+    /// Prioritize targets that are on the longest remaining path, according to their historical execution times.
+    /// The priority of a target is its own execution time plus the highest priority among the targets that depend on it.
+    /// Targets without any known execution time are considered instantaneous.
+    pub fn prioritize(&mut self, timings: &ExecutionTimings) {
+        fn priority<'a>(
+            double: &'a String,
+            inverted_dependencies: &HashMap<
+                &'a String,
+                HashMap<&'a String, Arc<DependencyAccessor>>,
+            >,
+            timings: &ExecutionTimings,
+            priorities: &mut HashMap<&'a String, Duration>,
+        ) -> Duration {
+            if let Some(priority) = priorities.get(double) {
+                return *priority;
+            }
+            let remaining = inverted_dependencies[double]
+                .keys()
+                .map(|parent_double| {
+                    priority(parent_double, inverted_dependencies, timings, priorities)
+                })
+                .max()
+                .unwrap_or_default();
+            let priority = timings.get(double).unwrap_or_default() + remaining;
+            priorities.insert(double, priority);
+            priority
+        }
+
+        let inverted_dependencies = self.create_inverted_dependency_graph();
+        let mut priorities = HashMap::with_capacity(self.dependency_graph.len());
+
+        for double in self.dependency_graph.keys() {
+            priority(double, &inverted_dependencies, timings, &mut priorities);
+        }
+
+        self.priorities = priorities
+            .into_iter()
+            .map(|(double, priority)| (double.to_owned(), priority))
+            .collect();
+    }
+
     /// Execute all targets using this graph with the specified parallelism level and execution routine.
     pub fn execute<
         T: Send + Sync + UnwindSafe + RefUnwindSafe,
@@ -303,7 +499,8 @@ impl ExecutionGraph {
         execution_routine: F,
     ) -> Result<ExecutedGraph<T>> {
         scope(|scope| {
-            let mut parallel_executor = ParallelRunner::new(scope, parallelism)?;
+            let mut parallel_executor =
+                ParallelRunner::new(scope, parallelism)?.with_resource_pools(&self.resource_pools);
 
             let mut pending = self.dependency_graph.keys().collect::<HashSet<&String>>();
 
@@ -342,8 +539,24 @@ impl ExecutionGraph {
                     })
                     .collect::<Vec<_>>();
 
-                parallel_executor.push_available(|| {
-                    let double = next_doubles.pop()?;
+                // doubles are popped from the end, so that targets with the highest priority are executed first.
+                next_doubles.sort_by_cached_key(|double| {
+                    (
+                        self.priorities.get(double).copied().unwrap_or_default(),
+                        Reverse(double.to_owned()),
+                    )
+                });
+
+                parallel_executor.push_available_with_resources(|is_available| {
+                    let index = next_doubles.iter().rposition(|double| {
+                        is_available(
+                            self.dependency_graph[double]
+                                .target_execution
+                                .get_target()
+                                .resources(),
+                        )
+                    })?;
+                    let double = next_doubles.remove(index);
                     let node = &self.dependency_graph[&double];
 
                     let internal_child_executions = node
@@ -365,21 +578,26 @@ impl ExecutionGraph {
 
                     pending.remove(&double);
 
-                    Some(move || {
-                        let result = execution_routine_clone(
-                            target_execution_1.as_ref(),
-                            internal_child_executions
-                                .iter()
-                                .map(|child| ChildExecutionResult {
-                                    execution: child.execution.as_ref(),
-                                    dependency: child.dependency.as_ref(),
-                                    result: child.result.as_ref().map(|arc| arc.as_ref()),
-                                })
-                                .collect::<Vec<_>>()
-                                .as_slice(),
-                        );
-                        (double.to_owned(), result)
-                    })
+                    let resources = node.target_execution.get_target().resources().clone();
+
+                    Some((
+                        move || {
+                            let result = execution_routine_clone(
+                                target_execution_1.as_ref(),
+                                internal_child_executions
+                                    .iter()
+                                    .map(|child| ChildExecutionResult {
+                                        execution: child.execution.as_ref(),
+                                        dependency: child.dependency.as_ref(),
+                                        result: child.result.as_ref().map(|arc| arc.as_ref()),
+                                    })
+                                    .collect::<Vec<_>>()
+                                    .as_slice(),
+                            );
+                            (double.to_owned(), result)
+                        },
+                        resources,
+                    ))
                 });
 
                 if !parallel_executor.is_running() && pending.is_empty() {
@@ -389,12 +607,26 @@ impl ExecutionGraph {
                 for (done_double, result) in parallel_executor.drain()? {
                     let is_ok = result.is_ok();
 
-                    results.insert(done_double.to_owned(), Arc::new(result));
+                    match result {
+                        Err(err) if is_skipped(&err) => {
+                            canceled.insert(
+                                self.dependency_graph.get_key_value(&done_double).unwrap().0,
+                            );
+                        }
+                        result => {
+                            results.insert(done_double.to_owned(), Arc::new(result));
+                        }
+                    }
 
                     if is_ok {
                         continue;
                     }
 
+                    if self.fail_fast {
+                        canceled.extend(pending.drain());
+                        continue;
+                    }
+
                     let mut to_cancel = HashSet::<&String>::with_capacity(pending.len());
 
                     let mut next_children = HashSet::<&String>::from_iter([&done_double]);
@@ -427,6 +659,8 @@ impl ExecutionGraph {
                 }
             }
 
+            let canceled = canceled.into_iter().cloned().collect::<HashSet<_>>();
+
             Ok(ExecutedGraph {
                 executions: self
                     .dependency_graph
@@ -441,6 +675,7 @@ impl ExecutionGraph {
                                 .map(Arc::try_unwrap)
                                 .transpose()
                                 .map_err(arc_error)?,
+                            canceled.contains(&double),
                         );
                         Ok((double, execution_result))
                     })
@@ -464,6 +699,7 @@ impl ExecutionGraph {
                             Arc::try_unwrap(node.target_execution).map_err(arc_error)?,
                             node.dependencies.keys().cloned().collect(),
                             None,
+                            false,
                         ),
                     ))
                 })
@@ -0,0 +1,202 @@
+use std::{
+    fs::File,
+    hash::{Hash, Hasher},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::UNIX_EPOCH,
+};
+
+use anyhow::Context;
+use blaze_common::{
+    error::Result,
+    logger::{LogLevel, Logger, LoggingStrategy},
+    value::Value,
+};
+
+use crate::{
+    system::{
+        hash::hasher,
+        process::{OutputSink, OutputStream},
+        time::now,
+    },
+    workspace::cache_store::CacheStore,
+};
+
+use super::{check::ExecutionCacheState, outputs::sha256_hex};
+
+const LOGS_PATH: &str = ".blaze/logs";
+const LOGS_STATE_KEY: &str = "logs";
+const LOGS_CONTENT_KEY_PREFIX: &str = "logs";
+
+/// Prefix of the first line of each log file, which holds the target double.
+const LOG_HEADER_PREFIX: &str = "# target: ";
+
+/// Number of runs whose logs are kept for each target.
+const MAX_RUNS_PER_TARGET: usize = 10;
+
+/// Generate a new run identifier. Identifiers of successive runs are sorted in lexicographic order.
+pub fn new_run_id() -> String {
+    let elapsed = now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    format!("{:020}", elapsed.as_nanos())
+}
+
+/// Get the identifier and the log file path of the last run of a target, if any.
+pub fn last_run_log(root: &Path, double: &str) -> Result<Option<(String, PathBuf)>> {
+    let directory = logs_directory(root, double);
+    if !directory.try_exists()? {
+        return Ok(None);
+    }
+    Ok(run_ids(&directory)?
+        .pop()
+        .map(|run_id| (run_id.clone(), directory.join(run_id))))
+}
+
+/// Read a log file, without its header.
+pub fn read_log(path: &Path) -> Result<Vec<u8>> {
+    let mut content = std::fs::read(path)
+        .with_context(|| format!("could not read log file {}", path.display()))?;
+    if content.starts_with(LOG_HEADER_PREFIX.as_bytes()) {
+        let header_length = content
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(content.len(), |position| position + 1);
+        let _ = content.drain(..header_length);
+    }
+    Ok(content)
+}
+
+/// Output of a target execution, captured to a file under `.blaze/logs/<double hash>/<run-id>`.
+/// The first line of the file holds the target double, since it cannot be used as a file name on all platforms.
+#[derive(Clone)]
+pub struct TargetLog {
+    path: PathBuf,
+    file: Arc<Mutex<File>>,
+    output: Option<OutputSink>,
+}
+
+impl TargetLog {
+    /// Create the log file of a target for a run, and remove logs of its oldest runs.
+    /// Captured output is forwarded to `output` when provided, or to the standard streams otherwise.
+    pub fn create(
+        root: &Path,
+        double: &str,
+        run_id: &str,
+        output: Option<OutputSink>,
+    ) -> Result<Self> {
+        let directory = logs_directory(root, double);
+        std::fs::create_dir_all(&directory)
+            .with_context(|| format!("could not create directory {}", directory.display()))?;
+
+        let path = directory.join(run_id);
+        let mut file = File::create(&path)
+            .with_context(|| format!("could not create log file {}", path.display()))?;
+        writeln!(file, "{LOG_HEADER_PREFIX}{double}")
+            .with_context(|| format!("could not write log file {}", path.display()))?;
+
+        let run_ids = run_ids(&directory)?;
+        for run_id in &run_ids[..run_ids.len().saturating_sub(MAX_RUNS_PER_TARGET)] {
+            std::fs::remove_file(directory.join(run_id))?;
+        }
+
+        Ok(Self {
+            path,
+            file: Arc::new(Mutex::new(file)),
+            output,
+        })
+    }
+
+    /// Sink capturing the output of processes to the log file.
+    pub fn sink(&self) -> OutputSink {
+        let log = self.clone();
+        Arc::new(move |stream, bytes| log.write(stream, bytes))
+    }
+
+    /// Wrap a logger so that its entries are also captured in the log file, except debug ones.
+    pub fn logger(&self, logger: Logger) -> Logger {
+        Logger::new(CapturingLoggingStrategy {
+            log: self.clone(),
+            logger,
+        })
+    }
+
+    /// Archive the log file content in the cache store, so that it can be replayed when the execution is cached.
+    pub fn archive(&self, cache: &CacheStore) -> Result<Value> {
+        let content = read_log(&self.path)?;
+        let digest = sha256_hex(&content);
+        cache.cache_blob(&content_key(&digest), &content)?;
+        Ok(Value::object([(LOGS_STATE_KEY, Value::string(digest))]))
+    }
+
+    /// Replay the output archived with a cached execution, which is captured again in the log file.
+    /// Returns `false` if no output was archived.
+    pub fn replay(&self, cached: &ExecutionCacheState, cache: &CacheStore) -> Result<bool> {
+        let digest = match cached.metadata.at(LOGS_STATE_KEY).and_then(Value::as_str) {
+            Some(digest) => digest,
+            None => return Ok(false),
+        };
+        match cache.restore_blob(&content_key(digest))? {
+            Some(content) => {
+                self.write(OutputStream::Stdout, &content);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn write(&self, stream: OutputStream, bytes: &[u8]) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_all(bytes);
+        }
+        match (&self.output, stream) {
+            (Some(output), _) => output(stream, bytes),
+            (None, OutputStream::Stdout) => {
+                let _ = std::io::stdout().write_all(bytes);
+            }
+            (None, OutputStream::Stderr) => {
+                let _ = std::io::stderr().write_all(bytes);
+            }
+        }
+    }
+}
+
+struct CapturingLoggingStrategy {
+    log: TargetLog,
+    logger: Logger,
+}
+
+impl LoggingStrategy for CapturingLoggingStrategy {
+    fn log(&self, message: &str, level: LogLevel) {
+        self.logger.log(message, level);
+
+        if level == LogLevel::Debug {
+            return;
+        }
+
+        if let Ok(mut file) = self.log.file.lock() {
+            let _ = writeln!(file, "[{}] {message}", level.to_string().to_uppercase());
+        }
+    }
+}
+
+/// Doubles are hashed, since `:` is not allowed in Windows file names.
+fn logs_directory(root: &Path, double: &str) -> PathBuf {
+    let mut hasher = hasher();
+    double.hash(&mut hasher);
+    root.join(LOGS_PATH)
+        .join(format!("{:016x}", hasher.finish()))
+}
+
+/// Identifiers of all runs with a log file in the directory, from the oldest to the latest.
+fn run_ids(directory: &Path) -> Result<Vec<String>> {
+    let mut run_ids = std::fs::read_dir(directory)
+        .with_context(|| format!("could not read directory {}", directory.display()))?
+        .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+        .collect::<Result<Vec<_>>>()?;
+    run_ids.sort();
+    Ok(run_ids)
+}
+
+fn content_key(digest: &str) -> String {
+    format!("{LOGS_CONTENT_KEY_PREFIX}/{digest}")
+}
@@ -4,7 +4,14 @@ pub mod env_changes;
 pub mod execution;
 pub mod executor_update;
 pub mod file_changes;
+pub mod file_hashes;
 pub mod files_missing;
+pub mod fingerprint;
 pub mod graph;
+pub mod logs;
+pub mod outputs;
 pub mod propagating_children;
+pub mod remote;
+pub mod timeout;
+pub mod timings;
 pub mod ttl;
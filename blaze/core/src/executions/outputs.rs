@@ -0,0 +1,249 @@
+use std::{
+    collections::BTreeSet,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use blaze_common::{
+    cache::FileChangesMatcher,
+    error::Result,
+    logger::Logger,
+    value::{to_value, Value},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    system::{glob::glob, hash::hasher, hash_cache::FileHashCache},
+    workspace::cache_store::CacheStore,
+};
+
+use super::{
+    check::{CacheInvalidationCheck, ExecutionCacheState, InvalidationReason},
+    execution::TargetExecution,
+};
+
+const OUTPUTS_STATE_KEY: &str = "outputs";
+const OUTPUTS_CONTENT_KEY_PREFIX: &str = "outputs";
+
+#[derive(Serialize, Deserialize)]
+struct OutputFile {
+    /// Path of the file, relative to the project root when possible.
+    path: PathBuf,
+    /// SHA-256 digest of the file content, used as the content cache key.
+    digest: String,
+    #[serde(default)]
+    mode: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct OutputsState {
+    files: Vec<OutputFile>,
+}
+
+/// Archives target output files in the cache store after a successful execution.
+/// Cache is invalidated when the outputs were not archived with the last execution.
+pub struct OutputsCheck<'a> {
+    matchers: &'a BTreeSet<FileChangesMatcher>,
+    cache: &'a CacheStore,
+    logger: &'a Logger,
+}
+
+impl<'a> OutputsCheck<'a> {
+    pub fn new(
+        matchers: &'a BTreeSet<FileChangesMatcher>,
+        cache: &'a CacheStore,
+        logger: &'a Logger,
+    ) -> Self {
+        Self {
+            matchers,
+            cache,
+            logger,
+        }
+    }
+}
+
+impl CacheInvalidationCheck for OutputsCheck<'_> {
+    fn state(&self, execution: &TargetExecution) -> Result<Option<Value>> {
+        let project = execution.get_project();
+        let project_root = project.root();
+
+        let paths = output_paths(execution, self.matchers)?;
+
+        let mut state = OutputsState::default();
+
+        for path in paths {
+            let content = std::fs::read(&path)
+                .with_context(|| format!("could not read output file {}", path.display()))?;
+            let digest = sha256_hex(&content);
+
+            self.cache
+                .cache_blob(&content_key(&digest), &content)
+                .with_context(|| format!("could not archive output file {}", path.display()))?;
+
+            state.files.push(OutputFile {
+                mode: file_mode(&path)?,
+                path: path
+                    .strip_prefix(project_root)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(path),
+                digest,
+            });
+        }
+
+        self.logger.debug(format!(
+            "archived {} output file(s) for {execution}",
+            state.files.len()
+        ));
+
+        Ok(Some(Value::object([(OUTPUTS_STATE_KEY, to_value(state)?)])))
+    }
+
+    fn validate(
+        &mut self,
+        _: &TargetExecution,
+        cached: &ExecutionCacheState,
+    ) -> Result<Option<InvalidationReason>> {
+        Ok(cached
+            .metadata
+            .at(OUTPUTS_STATE_KEY)
+            .is_none()
+            .then_some(InvalidationReason::OutputsNotArchived))
+    }
+}
+
+/// Restore the output files archived with a cached execution.
+/// Files that already exist with the same content are left untouched.
+/// Returns `false` if some file content could not be found in the cache store.
+pub fn restore_outputs(
+    execution: &TargetExecution,
+    cached: &ExecutionCacheState,
+    cache: &CacheStore,
+    logger: &Logger,
+) -> Result<bool> {
+    let state = match cached.metadata.at(OUTPUTS_STATE_KEY) {
+        Some(value) => OutputsState::deserialize(value)
+            .context("could not deserialize archived outputs state")?,
+        None => return Ok(false),
+    };
+
+    let project = execution.get_project();
+    let mut restored = 0_usize;
+
+    for file in &state.files {
+        let path = project.root().join(&file.path);
+
+        match std::fs::read(&path) {
+            Ok(content) if sha256_hex(&content) == file.digest => continue,
+            Ok(_) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("could not read output file {}", path.display()))
+            }
+        }
+
+        let content = match cache.restore_blob(&content_key(&file.digest))? {
+            Some(content) => content,
+            None => {
+                logger.debug(format!(
+                    "archived content of {} is missing from cache",
+                    path.display()
+                ));
+                return Ok(false);
+            }
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("could not restore output file {}", path.display()))?;
+        set_file_mode(&path, file.mode)?;
+
+        logger.debug(format!("{} was restored", path.display()));
+        restored += 1;
+    }
+
+    logger.debug(format!(
+        "restored {restored} output file(s) for {execution}"
+    ));
+
+    Ok(true)
+}
+
+/// Compute a fingerprint of the output files of a target execution, from their paths and their content.
+pub fn outputs_fingerprint(
+    execution: &TargetExecution,
+    matchers: &BTreeSet<FileChangesMatcher>,
+    file_hashes: &FileHashCache,
+) -> Result<u64> {
+    let project = execution.get_project();
+    let project_root = project.root();
+    let mut hasher = hasher();
+    for path in output_paths(execution, matchers)? {
+        path.strip_prefix(project_root)
+            .unwrap_or(&path)
+            .hash(&mut hasher);
+        file_hashes
+            .hash_file(&path)
+            .with_context(|| format!("could not compute hash of {}", path.display()))?
+            .hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
+/// Get the existing output files of a target execution, matched by the provided matchers.
+pub fn output_paths(
+    execution: &TargetExecution,
+    matchers: &BTreeSet<FileChangesMatcher>,
+) -> Result<BTreeSet<PathBuf>> {
+    let project = execution.get_project();
+    let project_root = project.root();
+    let mut paths = BTreeSet::new();
+    for matcher in matchers {
+        paths.extend(
+            glob(
+                matcher.root().unwrap_or(project_root),
+                matcher.pattern(),
+                matcher.exclude().iter().map(String::as_str),
+            )
+            .context("failure while walking across output files")?,
+        );
+    }
+    Ok(paths)
+}
+
+fn content_key(digest: &str) -> String {
+    format!("{OUTPUTS_CONTENT_KEY_PREFIX}/{digest}")
+}
+
+pub(super) fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[cfg(unix)]
+fn file_mode(path: &Path) -> Result<Option<u32>> {
+    use std::os::unix::fs::PermissionsExt;
+    Ok(Some(std::fs::metadata(path)?.permissions().mode()))
+}
+
+#[cfg(not(unix))]
+fn file_mode(_: &Path) -> Result<Option<u32>> {
+    Ok(None)
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: Option<u32>) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    if let Some(mode) = mode {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_: &Path, _: Option<u32>) -> Result<()> {
+    Ok(())
+}
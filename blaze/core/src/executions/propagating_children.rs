@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
+use anyhow::Context;
 use blaze_common::{
     dependency::CachePropagation,
     error::Result,
@@ -8,45 +9,77 @@ use blaze_common::{
 use possibly::possibly;
 use serde::{Deserialize, Serialize};
 
-use crate::executions::execution::CachedExecutionState;
+use crate::{executions::execution::CachedExecutionState, system::hash_cache::FileHashCache};
 
 use super::{
-    check::{CacheInvalidationCheck, ExecutionCacheState},
+    check::{CacheInvalidationCheck, ExecutionCacheState, InvalidationReason},
     execution::{CachedDependencyExecution, TargetExecution},
+    outputs::outputs_fingerprint,
 };
 
 pub struct PropagatingChildrenCheck<'a, T> {
     children: &'a [CachedDependencyExecution<'a, T>],
+    file_hashes: Arc<FileHashCache>,
     computed_state: Option<State>,
 }
 
 impl<'a, T> PropagatingChildrenCheck<'a, T> {
-    pub fn new(children: &'a [CachedDependencyExecution<T>]) -> Self {
+    pub fn new(
+        children: &'a [CachedDependencyExecution<T>],
+        file_hashes: Arc<FileHashCache>,
+    ) -> Self {
         Self {
             children,
+            file_hashes,
             computed_state: None,
         }
     }
 
-    fn get_state(&self) -> State {
-        State {
-            children: self.children.iter()
-            .filter_map(|child| {
-
-                if child.source.cache_propagation() == CachePropagation::Never {
-                    return None
-                }
-
-                possibly!(
-                    child.state,
-                    Some(Ok(CachedExecutionState::Cached(hash)|CachedExecutionState::New(hash, _))) => (child.double.to_owned(), *hash)
-                )
-            })
-            .collect()
+    fn get_state(&self) -> Result<State> {
+        let mut children = HashMap::with_capacity(self.children.len());
+        for child in self.children {
+            if let Some(hash) = propagated_hash(child, &self.file_hashes)? {
+                let _ = children.insert(child.double.to_owned(), hash);
+            }
         }
+        Ok(State { children })
     }
 }
 
+/// Get the value which represents a dependency when checking if it invalidates the cache of its dependent,
+/// or [`None`] if the dependency must not be considered.
+pub fn propagated_hash<T>(
+    child: &CachedDependencyExecution<T>,
+    file_hashes: &FileHashCache,
+) -> Result<Option<u64>> {
+    let hash = match possibly!(
+        child.state,
+        Some(Ok(CachedExecutionState::Cached(hash)|CachedExecutionState::New(hash, ..))) => *hash
+    ) {
+        Some(hash) => hash,
+        None => return Ok(None),
+    };
+
+    Ok(match child.source.cache_propagation() {
+        CachePropagation::Never => None,
+        CachePropagation::Always => Some(hash),
+        CachePropagation::OnOutputChange => match child
+            .execution
+            .get_target()
+            .cache()
+            .map(|cache| cache.outputs())
+            .filter(|outputs| !outputs.is_empty())
+        {
+            Some(outputs) => Some(
+                outputs_fingerprint(child.execution, outputs, file_hashes).with_context(|| {
+                    format!("could not compute outputs fingerprint of {}", child.double)
+                })?,
+            ),
+            None => Some(hash),
+        },
+    })
+}
+
 const CHILD_EXECUTIONS_KEY: &str = "child-executions";
 
 #[derive(Serialize, Deserialize)]
@@ -60,29 +93,46 @@ impl<'a, T> CacheInvalidationCheck for PropagatingChildrenCheck<'a, T> {
             CHILD_EXECUTIONS_KEY,
             match &self.computed_state {
                 Some(state) => to_value(state)?,
-                None => to_value(self.get_state())?,
+                None => to_value(self.get_state()?)?,
             },
         )])))
     }
 
-    fn validate(&mut self, _: &TargetExecution, cache_state: &ExecutionCacheState) -> Result<bool> {
+    fn validate(
+        &mut self,
+        _: &TargetExecution,
+        cache_state: &ExecutionCacheState,
+    ) -> Result<Option<InvalidationReason>> {
         let maybe_state = cache_state
             .metadata
             .at(CHILD_EXECUTIONS_KEY)
             .map(State::deserialize)
             .and_then(|state| state.ok());
 
-        if maybe_state.is_none() {
-            return Ok(false);
-        }
-
-        let old_state = maybe_state.unwrap();
-        let new_state = self.get_state();
-
-        let valid = new_state.children == old_state.children;
+        let old_state = match maybe_state {
+            Some(state) => state,
+            None => {
+                return Ok(Some(InvalidationReason::MissingState {
+                    check: "child cache propagation".into(),
+                }))
+            }
+        };
+
+        let new_state = self.get_state()?;
+
+        let mut dependencies = old_state
+            .children
+            .keys()
+            .chain(new_state.children.keys())
+            .filter(|double| old_state.children.get(*double) != new_state.children.get(*double))
+            .cloned()
+            .collect::<Vec<_>>();
+        dependencies.sort();
+        dependencies.dedup();
 
         self.computed_state = Some(new_state);
 
-        Ok(valid)
+        Ok((!dependencies.is_empty())
+            .then_some(InvalidationReason::DependenciesChanged { dependencies }))
     }
 }
@@ -0,0 +1,280 @@
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{Shutdown, TcpStream},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    error::Result,
+    logger::{LogLevel, Logger, LoggingStrategy},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::system::process::{
+    process_failure, OutputSink, OutputStream, ProcessFailure, ProcessGroup,
+};
+
+/// Interval at which a running remote execution checks if it must be interrupted.
+const INTERRUPT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// First message sent by the coordinator to a worker, as a single JSON line.
+/// The worker does not accept any request if the token does not match its own.
+#[derive(Serialize, Deserialize)]
+pub struct RemoteHandshake {
+    pub token: String,
+}
+
+impl RemoteHandshake {
+    /// Compare tokens in constant time, so that the worker token cannot be guessed from response times.
+    pub fn is_valid(&self, token: &str) -> bool {
+        self.token.len() == token.len()
+            && self
+                .token
+                .bytes()
+                .zip(token.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+/// Request sent by the coordinator to a worker after the handshake, as a single JSON line, in order to execute a target.
+/// Target options are resolved by the worker from its own copy of the workspace.
+#[derive(Serialize, Deserialize)]
+pub struct RemoteExecutionRequest {
+    pub project: String,
+    pub target: String,
+}
+
+impl RemoteExecutionRequest {
+    pub fn get_double(&self) -> String {
+        [self.project.as_str(), self.target.as_str()].join(":")
+    }
+}
+
+/// Messages sent by a worker while executing a target, as JSON lines.
+/// The [`RemoteExecutionMessage::Done`] message is always the last one.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RemoteExecutionMessage {
+    Output { stream: OutputStream, data: String },
+    Log { level: LogLevel, message: String },
+    Done { error: Option<RemoteError> },
+}
+
+/// An execution error transferred from a worker to the coordinator.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteError {
+    /// Error messages, from the top-level error down to its root cause.
+    messages: Vec<String>,
+    /// Exit code of the failed process, if the error was caused by a process failure.
+    code: Option<i32>,
+}
+
+impl RemoteError {
+    pub fn new(err: &anyhow::Error) -> Self {
+        Self {
+            messages: err.chain().map(ToString::to_string).collect(),
+            code: process_failure(err).and_then(|failure| failure.code),
+        }
+    }
+
+    fn into_error(self) -> anyhow::Error {
+        let mut messages = self.messages.into_iter().rev();
+        let root = messages.next().unwrap_or_default();
+        let err = match self.code {
+            Some(code) => anyhow::Error::new(ProcessFailure {
+                message: root,
+                code: Some(code),
+            }),
+            None => anyhow!(root),
+        };
+        messages.fold(err, |err, message| err.context(message))
+    }
+}
+
+/// Sends messages of a remote execution to the coordinator.
+#[derive(Clone)]
+pub struct RemoteExecutionSender {
+    connection: Arc<Mutex<TcpStream>>,
+}
+
+impl RemoteExecutionSender {
+    pub fn new(connection: TcpStream) -> Self {
+        Self {
+            connection: Arc::new(Mutex::new(connection)),
+        }
+    }
+
+    pub fn send(&self, message: &RemoteExecutionMessage) -> Result<()> {
+        let mut line = serde_json::to_vec(message)?;
+        line.push(b'\n');
+        self.connection
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on remote execution connection)."))?
+            .write_all(&line)?;
+        Ok(())
+    }
+
+    /// Sink sending the output of processes to the coordinator.
+    pub fn output(&self) -> OutputSink {
+        let sender = self.clone();
+        Arc::new(move |stream, bytes| {
+            let _ = sender.send(&RemoteExecutionMessage::Output {
+                stream,
+                data: String::from_utf8_lossy(bytes).into_owned(),
+            });
+        })
+    }
+
+    /// Logger sending its entries to the coordinator.
+    pub fn logger(&self) -> Logger {
+        Logger::new(self.clone())
+    }
+}
+
+impl LoggingStrategy for RemoteExecutionSender {
+    fn log(&self, message: &str, level: LogLevel) {
+        let _ = self.send(&RemoteExecutionMessage::Log {
+            level,
+            message: message.to_owned(),
+        });
+    }
+}
+
+/// Worker addresses available for remote executions.
+/// Each address executes one target at a time. The same address can be provided multiple times,
+/// in order to execute several targets at once on the same worker.
+pub struct WorkerPool {
+    available: Mutex<Vec<String>>,
+    released: Condvar,
+    token: String,
+}
+
+impl WorkerPool {
+    pub fn new<S: AsRef<str>, I: IntoIterator<Item = S>>(addresses: I, token: &str) -> Self {
+        let mut available = addresses
+            .into_iter()
+            .map(|address| address.as_ref().to_owned())
+            .collect::<Vec<_>>();
+        // workers are acquired from the end of the list.
+        available.reverse();
+        Self {
+            available: Mutex::new(available),
+            released: Condvar::new(),
+            token: token.to_owned(),
+        }
+    }
+
+    /// Execute a target on the next available worker, waiting for one if all are busy.
+    /// The output and log entries of the execution are written to the process group and to the logger.
+    /// The execution is interrupted when processes of the group are killed.
+    pub fn execute(
+        &self,
+        request: &RemoteExecutionRequest,
+        logger: &Logger,
+        process_group: &ProcessGroup,
+    ) -> Result<()> {
+        let address = self.acquire()?;
+        logger.debug(format!(
+            "executing target {} on worker {address}",
+            request.get_double()
+        ));
+        let handshake = RemoteHandshake {
+            token: self.token.clone(),
+        };
+        let result = execute_on_worker(&address, &handshake, request, logger, process_group);
+        self.release(address)?;
+        result
+    }
+
+    fn acquire(&self) -> Result<String> {
+        let mut available = self.lock()?;
+        loop {
+            if let Some(address) = available.pop() {
+                return Ok(address);
+            }
+            available = self
+                .released
+                .wait(available)
+                .map_err(|_| anyhow!("poison error (Mutex on worker pool)."))?;
+        }
+    }
+
+    fn release(&self, address: String) -> Result<()> {
+        self.lock()?.push(address);
+        self.released.notify_one();
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Vec<String>>> {
+        self.available
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on worker pool)."))
+    }
+}
+
+fn execute_on_worker(
+    address: &str,
+    handshake: &RemoteHandshake,
+    request: &RemoteExecutionRequest,
+    logger: &Logger,
+    process_group: &ProcessGroup,
+) -> Result<()> {
+    let mut connection = TcpStream::connect(address)
+        .with_context(|| format!("could not connect to worker {address}"))?;
+
+    let mut line = serde_json::to_vec(handshake)?;
+    line.push(b'\n');
+    line.extend(serde_json::to_vec(request)?);
+    line.push(b'\n');
+    connection
+        .write_all(&line)
+        .with_context(|| format!("could not send execution request to worker {address}"))?;
+
+    connection.set_read_timeout(Some(INTERRUPT_POLL_INTERVAL))?;
+
+    let mut reader = BufReader::new(connection.try_clone()?);
+    let mut line = vec![];
+
+    loop {
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => bail!("connection to worker {address} was closed unexpectedly"),
+            Ok(_) => {}
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                if process_group.is_killed()? {
+                    // the worker kills the target processes when the connection is closed.
+                    connection.shutdown(Shutdown::Both)?;
+                    bail!("execution on worker {address} was interrupted");
+                }
+                continue;
+            }
+            Err(err) => {
+                return Err(err).with_context(|| format!("could not read from worker {address}"))
+            }
+        }
+
+        // a partial line might have been read before a timeout.
+        if line.last() != Some(&b'\n') {
+            continue;
+        }
+
+        let message = serde_json::from_slice::<RemoteExecutionMessage>(&line)
+            .with_context(|| format!("invalid message received from worker {address}"))?;
+        line.clear();
+
+        match message {
+            RemoteExecutionMessage::Output { stream, data } => {
+                process_group.write_output(stream, data.as_bytes())?
+            }
+            RemoteExecutionMessage::Log { level, message } => logger.log(message, level),
+            RemoteExecutionMessage::Done { error: None } => return Ok(()),
+            RemoteExecutionMessage::Done { error: Some(error) } => {
+                return Err(error.into_error())
+                    .with_context(|| format!("execution failed on worker {address}"))
+            }
+        }
+    }
+}
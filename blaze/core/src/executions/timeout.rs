@@ -0,0 +1,82 @@
+use std::{
+    fmt::Display,
+    sync::mpsc::{channel, RecvTimeoutError},
+    thread::scope,
+    time::Duration,
+};
+
+use anyhow::Context;
+use blaze_common::{error::Result, logger::Logger};
+
+use crate::system::{
+    process::ProcessGroup,
+    thread::{join, thread},
+};
+
+/// Error returned when a target execution exceeds its timeout.
+#[derive(Debug)]
+pub struct TimedOut {
+    pub timeout: Duration,
+}
+
+impl Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out after {:?}", self.timeout)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Get the timeout that caused the error, if any.
+pub fn timed_out(err: &anyhow::Error) -> Option<&TimedOut> {
+    err.downcast_ref::<TimedOut>()
+}
+
+/// Run the provided function, killing all processes in the group if it does not return before the timeout.
+/// When the timeout is exceeded, a [`TimedOut`] error is returned.
+pub fn with_timeout<T, F>(
+    timeout: Option<Duration>,
+    group: &ProcessGroup,
+    logger: &Logger,
+    f: F,
+) -> Result<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return f(),
+    };
+
+    let (done_send, done_recv) = channel::<()>();
+
+    let (result, is_timed_out) = scope(|scope| {
+        let watchdog = thread!(scope, move || -> Result<bool> {
+            if let Err(RecvTimeoutError::Timeout) = done_recv.recv_timeout(timeout) {
+                logger.error(format!(
+                    "execution has timed out after {timeout:?}, killing all processes"
+                ));
+                group
+                    .kill_all()
+                    .context("could not kill processes after timeout")?;
+                return Ok(true);
+            }
+            Ok(false)
+        });
+
+        let result = f();
+        let _ = done_send.send(());
+
+        Ok::<_, anyhow::Error>((result, join!(watchdog)?))
+    })?;
+
+    // the group might have been killed for another reason, which must not be reported as a timeout.
+    if !is_timed_out {
+        return result;
+    }
+
+    Err(match result {
+        Ok(_) => TimedOut { timeout }.into(),
+        Err(err) => err.context(TimedOut { timeout }),
+    })
+}
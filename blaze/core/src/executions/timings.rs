@@ -0,0 +1,53 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context;
+use blaze_common::error::Result;
+
+use crate::workspace::cache_store::CacheStore;
+
+const EXECUTION_TIMINGS_CACHE_KEY: &str = "execution-timings";
+
+/// Historical execution times of targets, indexed by execution double.
+#[derive(Debug, Default)]
+pub struct ExecutionTimings {
+    durations: HashMap<String, Duration>,
+}
+
+impl ExecutionTimings {
+    /// Load execution times stored from previous runs.
+    pub fn load(cache: &CacheStore) -> Result<Self> {
+        Ok(Self {
+            durations: cache
+                .restore::<HashMap<String, u64>>(EXECUTION_TIMINGS_CACHE_KEY)
+                .context("could not restore execution timings")?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(double, millis)| (double, Duration::from_millis(millis)))
+                .collect(),
+        })
+    }
+
+    /// Store execution times so that they can be used by next runs.
+    pub fn save(&self, cache: &CacheStore) -> Result<()> {
+        cache
+            .cache(
+                EXECUTION_TIMINGS_CACHE_KEY,
+                &self
+                    .durations
+                    .iter()
+                    .map(|(double, duration)| (double.as_str(), duration.as_millis() as u64))
+                    .collect::<HashMap<_, _>>(),
+            )
+            .context("could not store execution timings")
+    }
+
+    /// Get the last known execution time of a target.
+    pub fn get(&self, double: &str) -> Option<Duration> {
+        self.durations.get(double).copied()
+    }
+
+    /// Record the execution time of a target, replacing any previous value.
+    pub fn record(&mut self, double: &str, duration: Duration) {
+        let _ = self.durations.insert(double.to_owned(), duration);
+    }
+}
@@ -1,10 +1,9 @@
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 
 use anyhow::bail;
 use blaze_common::{
-    cache::{TimeUnit, TtlOptions},
+    cache::TtlOptions,
     error::Result,
-    logger::Logger,
     time::system_time_as_timestamps,
     value::{to_value, Value},
 };
@@ -13,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use crate::system::time::now;
 
 use super::{
-    check::{CacheInvalidationCheck, ExecutionCacheState},
+    check::{CacheInvalidationCheck, ExecutionCacheState, InvalidationReason},
     execution::TargetExecution,
 };
 
@@ -25,12 +24,11 @@ struct TtlState {
 
 pub struct TtlCheck<'a> {
     options: &'a TtlOptions,
-    logger: &'a Logger,
 }
 
 impl<'a> TtlCheck<'a> {
-    pub fn new(options: &'a TtlOptions, logger: &'a Logger) -> Self {
-        Self { options, logger }
+    pub fn new(options: &'a TtlOptions) -> Self {
+        Self { options }
     }
 }
 
@@ -46,9 +44,9 @@ impl CacheInvalidationCheck for TtlCheck<'_> {
 
     fn validate(
         &mut self,
-        execution: &TargetExecution,
+        _: &TargetExecution,
         state: &ExecutionCacheState,
-    ) -> Result<bool> {
+    ) -> Result<Option<InvalidationReason>> {
         let now = now();
         let maybe_last_state = state
             .metadata
@@ -58,41 +56,27 @@ impl CacheInvalidationCheck for TtlCheck<'_> {
 
         let last_state = match maybe_last_state {
             Some(state) => state,
-            None => return Ok(false),
+            None => {
+                return Ok(Some(InvalidationReason::MissingState {
+                    check: "TTL".into(),
+                }))
+            }
         };
 
-        let amount_u64: u64 = self.options.amount().try_into()?;
-
-        if amount_u64 == 0 {
+        if self.options.amount() == 0 {
             bail!("ttl cannot be zero")
         }
 
-        let duration = match self.options.unit() {
-            TimeUnit::Milliseconds => Duration::from_millis(amount_u64),
-            TimeUnit::Seconds => Duration::from_secs(amount_u64),
-            TimeUnit::Minutes => Duration::from_secs(amount_u64 * 60),
-            TimeUnit::Hours => Duration::from_secs(amount_u64 * 60 * 60),
-            TimeUnit::Days => Duration::from_secs(amount_u64 * 60 * 60 * 24),
-        };
+        let duration = self.options.unit().duration(self.options.amount());
 
-        let is_expired = last_state.at + duration <= now;
+        let expiration = last_state.at + duration;
 
-        if is_expired {
-            self.logger.debug(format!(
-                "target {execution} TTL has expired (elapsed={})",
-                {
-                    let elapsed = now.duration_since(last_state.at)?;
-                    match self.options.unit() {
-                        TimeUnit::Milliseconds => format!("{}ms", elapsed.as_millis()),
-                        TimeUnit::Seconds => format!("{}s", elapsed.as_secs()),
-                        TimeUnit::Minutes => format!("{}m", elapsed.as_secs() / 60),
-                        TimeUnit::Hours => format!("{}h", elapsed.as_secs() / 60 / 60),
-                        TimeUnit::Days => format!("{}d", elapsed.as_secs() / 60 / 60 / 24),
-                    }
-                }
-            ));
+        if expiration > now {
+            return Ok(None);
         }
 
-        Ok(!is_expired)
+        Ok(Some(InvalidationReason::TtlExpired {
+            expired_for: now.duration_since(expiration)?.as_secs_f64(),
+        }))
     }
 }
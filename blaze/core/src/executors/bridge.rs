@@ -64,6 +64,7 @@ pub fn bridge_executor<T: Serialize>(
         .context("error while creating executor logs pipe")?;
 
         let project_root_directory = context.project.root().to_path_buf();
+        let process_group = context.process_group.clone();
 
         let process_env = get_executor_env(&context)?;
 
@@ -91,6 +92,7 @@ pub fn bridge_executor<T: Serialize>(
                 cwd: Some(project_root_directory),
                 display_output: true,
                 environment: process_env,
+                group: Some(process_group),
             },
         )
         .context("error while creating executor process")?;
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use blaze_common::{error::Result, util::path_to_string};
+use blaze_common::{error::Result, util::path_to_string, workspace::Workspace};
 
 use super::ExecutorContext;
 
@@ -13,20 +13,28 @@ const PROJECT_ROOT: &str = "BLAZE_PROJECT_ROOT";
 const TARGET: &str = "BLAZE_TARGET";
 
 pub fn get_executor_env(ctx: &ExecutorContext) -> Result<HashMap<String, String>> {
+    let mut env = get_workspace_env(ctx.workspace)?;
+    env.extend([
+        (PROJECT_NAME.into(), ctx.project.name().to_owned()),
+        (PROJECT_ROOT.into(), path_to_string(ctx.project.root())?),
+        (TARGET.into(), ctx.target.to_owned()),
+    ]);
+    Ok(env)
+}
+
+/// Environment variables describing the workspace, for processes that are not related to a target.
+pub fn get_workspace_env(workspace: &Workspace) -> Result<HashMap<String, String>> {
     Ok([
-        (WORKSPACE_NAME.into(), ctx.workspace.name().to_owned()),
-        (WORKSPACE_ROOT.into(), path_to_string(ctx.workspace.root())?),
+        (WORKSPACE_NAME.into(), workspace.name().to_owned()),
+        (WORKSPACE_ROOT.into(), path_to_string(workspace.root())?),
         (
             WORKSPACE_CONFIGURATION_FILE_PATH.into(),
-            path_to_string(ctx.workspace.configuration_file_path())?,
+            path_to_string(workspace.configuration_file_path())?,
         ),
         (
             WORKSPACE_CONFIGURATION_FILE_FORMAT.into(),
-            ctx.workspace.configuration_file_format().to_string(),
+            workspace.configuration_file_format().to_string(),
         ),
-        (PROJECT_NAME.into(), ctx.project.name().to_owned()),
-        (PROJECT_ROOT.into(), path_to_string(ctx.project.root())?),
-        (TARGET.into(), ctx.target.to_owned()),
     ]
     .into())
 }
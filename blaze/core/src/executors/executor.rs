@@ -5,6 +5,8 @@ use blaze_common::{
 };
 use serde::Serialize;
 
+use crate::system::process::ProcessGroup;
+
 #[derive(Serialize)]
 pub struct ExecutorContext<'a> {
     pub workspace: &'a Workspace,
@@ -12,6 +14,9 @@ pub struct ExecutorContext<'a> {
     pub target: &'a str,
     #[serde(skip)]
     pub logger: &'a Logger,
+    /// All processes spawned by the executor must be added to this group, so that they can be killed when the target times out.
+    #[serde(skip)]
+    pub process_group: &'a ProcessGroup,
 }
 
 pub type DynExecutor = Box<dyn Executor + Send + Sync + UnwindSafe + RefUnwindSafe>;
@@ -8,5 +8,7 @@ mod bridge;
 mod node;
 mod rust;
 
+pub use self::std::run_commands;
+pub use env::get_workspace_env;
 pub use executor::*;
 pub use resolve::*;
@@ -1,4 +1,4 @@
 mod bridge;
 mod package;
 pub mod prelude;
-pub use package::is_node_executor;
+pub use package::{is_node_executor, npm};
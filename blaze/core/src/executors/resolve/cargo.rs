@@ -0,0 +1,323 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    error::Result,
+    executor::{CargoOptions, Compression, ExecutorKind},
+    logger::Logger,
+    value::{to_value, Value},
+    workspace::Workspace,
+};
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    header::AUTHORIZATION,
+};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::system::{archive::unpack_tarball, http::USER_AGENT, random::random_string};
+
+use super::{
+    loader::LoadMetadata,
+    resolver::{ExecutorResolver, ExecutorSource},
+};
+
+const CRATES_LOCATION: &str = ".blaze/cargo";
+const CRATES_IO_INDEX: &str = "https://index.crates.io/";
+const REGISTRY_CONFIG_FILENAME: &str = "config.json";
+
+/// Test registries are served over plain HTTP.
+#[cfg(not(feature = "testing"))]
+const REGISTRY_SCHEME: &str = "https";
+#[cfg(feature = "testing")]
+const REGISTRY_SCHEME: &str = "http";
+
+const DOWNLOAD_URL_MARKERS: [&str; 5] = [
+    "{crate}",
+    "{version}",
+    "{prefix}",
+    "{lowerprefix}",
+    "{sha256-checksum}",
+];
+
+#[derive(Serialize, Deserialize)]
+struct State {
+    version: String,
+    crate_path: PathBuf,
+}
+
+/// Registry configuration, located at the root of the index (`config.json`).
+#[derive(Deserialize)]
+struct RegistryConfig {
+    dl: String,
+    #[serde(default, rename = "auth-required")]
+    auth_required: bool,
+}
+
+/// A single line of a crate index file.
+#[derive(Deserialize)]
+struct IndexEntry {
+    vers: String,
+    cksum: String,
+    #[serde(default)]
+    yanked: bool,
+}
+
+struct CrateRelease {
+    name: String,
+    version: Version,
+    checksum: String,
+}
+
+pub struct CargoResolver<'a> {
+    options: CargoOptions,
+    logger: &'a Logger,
+    crates_root: PathBuf,
+}
+
+#[derive(Clone, Copy)]
+pub struct CargoResolverContext<'a> {
+    pub workspace: &'a Workspace,
+    pub logger: &'a Logger,
+}
+
+impl<'a> CargoResolver<'a> {
+    pub fn new(options: CargoOptions, context: CargoResolverContext<'a>) -> Self {
+        Self {
+            options,
+            logger: context.logger,
+            crates_root: context.workspace.root().join(CRATES_LOCATION),
+        }
+    }
+
+    fn client(&self) -> Result<Client> {
+        Client::builder()
+            .user_agent(USER_AGENT)
+            .danger_accept_invalid_certs(self.options.insecure())
+            .build()
+            .context("could not create HTTP client")
+    }
+
+    /// Build a GET request. The registry token is only sent when `authenticated` is `true`.
+    fn get(&self, client: &Client, url: &str, authenticated: bool) -> RequestBuilder {
+        let request = client.get(url);
+        match self.options.token() {
+            Some(token) if authenticated => request.header(AUTHORIZATION, token),
+            _ => request,
+        }
+    }
+
+    /// Get the sparse index root URL from the executor URL.
+    fn index_url(url: &Url) -> Result<Url> {
+        match url.host_str() {
+            Some(host) => {
+                let mut index_url = Url::parse(&format!("{REGISTRY_SCHEME}://{host}/"))?;
+                index_url
+                    .set_port(url.port())
+                    .map_err(|_| anyhow!("invalid registry port in {url}"))?;
+                Ok(index_url)
+            }
+            None => Ok(Url::parse(CRATES_IO_INDEX)?),
+        }
+    }
+
+    fn crate_name(url: &Url) -> Result<String> {
+        let name = url.path().trim_start_matches('/');
+        if name.is_empty() {
+            bail!("crate name is missing in {url}");
+        }
+        Ok(name.to_owned())
+    }
+
+    /// Index files are stored in directories named after the first characters of the crate name.
+    fn index_prefix(name: &str) -> String {
+        match name.len() {
+            1 => "1".into(),
+            2 => "2".into(),
+            3 => format!("3/{}", &name[..1]),
+            _ => format!("{}/{}", &name[..2], &name[2..4]),
+        }
+    }
+
+    /// Find the greatest non yanked release that matches the version requirement.
+    fn find_release(&self, client: &Client, url: &Url) -> Result<CrateRelease> {
+        let name = Self::crate_name(url)?;
+        let requirement = VersionReq::parse(self.options.version().unwrap_or("*"))
+            .with_context(|| format!("invalid version requirement for {url}"))?;
+
+        let index_file_url = Self::index_url(url)?.join(&format!(
+            "{}/{}",
+            Self::index_prefix(&name.to_lowercase()),
+            name.to_lowercase()
+        ))?;
+
+        let index_file = self
+            .get(client, index_file_url.as_str(), true)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .with_context(|| format!("could not fetch index file at {index_file_url}"))?;
+
+        let mut candidates = vec![];
+
+        for line in index_file.lines().filter(|line| !line.trim().is_empty()) {
+            let entry = serde_json::from_str::<IndexEntry>(line)
+                .with_context(|| format!("invalid index entry in {index_file_url}"))?;
+            if entry.yanked {
+                continue;
+            }
+            let version = Version::parse(&entry.vers)
+                .with_context(|| format!("invalid version in {index_file_url}"))?;
+            if requirement.matches(&version) {
+                candidates.push((version, entry.cksum));
+            }
+        }
+
+        let (version, checksum) = candidates
+            .into_iter()
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .ok_or_else(|| {
+                anyhow!("no version of crate {name} matches requirement \"{requirement}\"")
+            })?;
+
+        Ok(CrateRelease {
+            name,
+            version,
+            checksum,
+        })
+    }
+
+    /// Get the crate download URL, and whether the registry token must be sent to it.
+    /// The token is sent to the index host, or to any host if the registry requires authentication for all requests.
+    fn download_url(
+        &self,
+        client: &Client,
+        url: &Url,
+        release: &CrateRelease,
+    ) -> Result<(String, bool)> {
+        let index_url = Self::index_url(url)?;
+        let config_url = index_url.join(REGISTRY_CONFIG_FILENAME)?;
+        let config = self
+            .get(client, config_url.as_str(), true)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json::<RegistryConfig>())
+            .with_context(|| format!("could not fetch registry configuration at {config_url}"))?;
+
+        let download_url = if DOWNLOAD_URL_MARKERS
+            .iter()
+            .any(|marker| config.dl.contains(marker))
+        {
+            let prefix = Self::index_prefix(&release.name);
+            config
+                .dl
+                .replace("{crate}", &release.name)
+                .replace("{version}", &release.version.to_string())
+                .replace("{prefix}", &prefix)
+                .replace("{lowerprefix}", &prefix.to_lowercase())
+                .replace("{sha256-checksum}", &release.checksum)
+        } else {
+            format!(
+                "{}/{}/{}/download",
+                config.dl.trim_end_matches('/'),
+                release.name,
+                release.version
+            )
+        };
+
+        let authenticated = config.auth_required
+            || Url::parse(&download_url)
+                .with_context(|| format!("invalid crate download URL {download_url}"))?
+                .origin()
+                == index_url.origin();
+
+        Ok((download_url, authenticated))
+    }
+
+    /// Download, verify and unpack a crate release.
+    fn fetch(&self, client: &Client, url: &Url, release: CrateRelease) -> Result<State> {
+        let (download_url, authenticated) = self.download_url(client, url, &release)?;
+
+        let archive = self
+            .get(client, &download_url, authenticated)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.bytes())
+            .with_context(|| format!("could not download crate at {download_url}"))?;
+
+        let checksum = format!("{:x}", Sha256::digest(&archive));
+
+        if checksum != release.checksum {
+            bail!(
+                "checksum mismatch for crate {} {} (expected {}, got {checksum})",
+                release.name,
+                release.version,
+                release.checksum
+            );
+        }
+
+        self.logger.debug(format!(
+            "downloaded {url} (version {}) from {download_url}",
+            release.version
+        ));
+
+        let crate_root = self.crates_root.join(random_string(12));
+
+        std::fs::create_dir_all(&crate_root)?;
+
+        unpack_tarball(archive.as_ref(), Some(Compression::Gzip), &crate_root)
+            .with_context(|| format!("could not unpack crate downloaded from {download_url}"))?;
+
+        Ok(State {
+            crate_path: crate_root.join(format!("{}-{}", release.name, release.version)),
+            version: release.version.to_string(),
+        })
+    }
+
+    fn source(state: State) -> Result<ExecutorSource> {
+        Ok(ExecutorSource {
+            load_metadata: LoadMetadata {
+                src: state.crate_path.clone(),
+                kind: ExecutorKind::Rust,
+            },
+            state: to_value(state)?,
+        })
+    }
+}
+
+impl ExecutorResolver for CargoResolver<'_> {
+    fn resolve(&self, url: &Url) -> Result<ExecutorSource> {
+        let client = self.client()?;
+        let release = self.find_release(&client, url)?;
+        Self::source(self.fetch(&client, url, release)?)
+    }
+
+    fn update(&self, url: &Url, state: &Value) -> Result<Option<ExecutorSource>> {
+        let state = State::deserialize(state)?;
+        let client = self.client()?;
+        let release = self.find_release(&client, url)?;
+
+        if release.version.to_string() == state.version && state.crate_path.try_exists()? {
+            self.logger
+                .debug(format!("{url} is up to date ({})", release.version));
+            return Ok(None);
+        }
+
+        let new_state = self.fetch(&client, url, release)?;
+
+        if let Some(previous_root) = state.crate_path.parent() {
+            if previous_root.try_exists()? {
+                std::fs::remove_dir_all(previous_root)?;
+            }
+        }
+
+        self.logger.debug(format!(
+            "{url} was updated from {} to {}",
+            state.version, new_state.version
+        ));
+
+        Ok(Some(Self::source(new_state)?))
+    }
+}
@@ -15,7 +15,10 @@ use blaze_common::{
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::system::file_changes::{MatchedFiles, MatchedFilesState};
+use crate::system::{
+    file_changes::{MatchedFiles, MatchedFilesState},
+    hash_cache::FileHashCache,
+};
 
 use super::{
     kinds::infer_local_executor_type, loader::LoadMetadata, resolver::ExecutorSource,
@@ -71,7 +74,10 @@ impl ExecutorResolver for FileSystemResolver {
         }
         Ok(ExecutorSource {
             state: to_value(State {
-                files: MatchedFilesState::from_files(self.get_matched_files(&root)?)?,
+                files: MatchedFilesState::from_files(
+                    self.get_matched_files(&root)?,
+                    &*FileHashCache::for_workspace(&self.workspace_root)?,
+                )?,
             })?,
             load_metadata: LoadMetadata {
                 kind: if let Some(kind) = self.options.kind() {
@@ -90,7 +96,10 @@ impl ExecutorResolver for FileSystemResolver {
         let state = State::deserialize(state)?;
 
         let matched_files = self.get_matched_files(&root)?;
-        let merged_state = state.files.merge(matched_files)?;
+        let merged_state = state.files.merge(
+            matched_files,
+            &*FileHashCache::for_workspace(&self.workspace_root)?,
+        )?;
 
         let new_state = State {
             files: merged_state.state,
@@ -56,7 +56,7 @@ impl<'a> GitHeadlessResolver<'a> {
         }
     }
 
-    fn default_remote_callbacks(&self) -> RemoteCallbacks {
+    fn default_remote_callbacks(&self) -> RemoteCallbacks<'_> {
         let remote_callbacks = RemoteCallbacks::new();
         remote_callbacks
     }
@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use blaze_common::{
+    error::{Error, Result},
+    executor::{HttpAuthentication, HttpTransport, TarballOptions},
+    logger::Logger,
+    value::{to_value, Value},
+    workspace::Workspace,
+};
+use reqwest::{
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Method, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::system::{archive::unpack_tarball, http::HttpSession, random::random_string};
+
+use super::{
+    kinds::infer_local_executor_type,
+    loader::LoadMetadata,
+    resolver::{ExecutorResolver, ExecutorSource},
+};
+
+const TARBALLS_LOCATION: &str = ".blaze/tarballs";
+
+#[derive(Serialize, Deserialize)]
+struct State {
+    download_root: PathBuf,
+    executor_root: PathBuf,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+enum Download {
+    NotModified,
+    Modified {
+        content: Vec<u8>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
+
+pub struct TarballOverHttpResolver<'a> {
+    transport: HttpTransport,
+    tarball_options: TarballOptions,
+    authentication: Option<HttpAuthentication>,
+    logger: &'a Logger,
+    tarballs_root: PathBuf,
+}
+
+#[derive(Clone, Copy)]
+pub struct TarballResolverContext<'a> {
+    pub workspace: &'a Workspace,
+    pub logger: &'a Logger,
+}
+
+impl<'a> TarballOverHttpResolver<'a> {
+    pub fn new(
+        transport: HttpTransport,
+        tarball_options: TarballOptions,
+        authentication: Option<HttpAuthentication>,
+        context: TarballResolverContext<'a>,
+    ) -> Self {
+        Self {
+            transport,
+            tarball_options,
+            authentication,
+            logger: context.logger,
+            tarballs_root: context.workspace.root().join(TARBALLS_LOCATION),
+        }
+    }
+
+    /// Download the tarball, unless it was not modified since the previous download.
+    fn download(&self, url: &Url, previous: Option<&State>) -> Result<Download> {
+        let session = HttpSession::try_new(self.transport.clone(), self.authentication.clone())?;
+
+        let response = session.send(Method::GET, url, |mut request| {
+            if let Some(previous) = previous {
+                if let Some(etag) = &previous.etag {
+                    request = request.header(IF_NONE_MATCH, etag);
+                }
+                if let Some(last_modified) = &previous.last_modified {
+                    request = request.header(IF_MODIFIED_SINCE, last_modified);
+                }
+            }
+            request
+        })?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Download::NotModified);
+        }
+
+        let response = response
+            .error_for_status()
+            .with_context(|| format!("could not download tarball at {url}"))?;
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+
+        Ok(Download::Modified {
+            content: response
+                .bytes()
+                .with_context(|| format!("could not read tarball content from {url}"))?
+                .to_vec(),
+            etag,
+            last_modified,
+        })
+    }
+
+    fn unpack(
+        &self,
+        url: &Url,
+        content: &[u8],
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> Result<State> {
+        let download_root = self.tarballs_root.join(random_string(12));
+
+        std::fs::create_dir_all(&download_root)?;
+
+        unpack_tarball(content, self.tarball_options.compression(), &download_root)
+            .with_context(|| format!("could not unpack tarball downloaded from {url}"))?;
+
+        self.logger.debug(format!(
+            "unpacked {url} to {} (etag={etag:?}, last-modified={last_modified:?})",
+            download_root.display()
+        ));
+
+        Ok(State {
+            executor_root: executor_root(&download_root)?,
+            download_root,
+            etag,
+            last_modified,
+        })
+    }
+
+    fn source(&self, state: State) -> Result<ExecutorSource> {
+        Ok(ExecutorSource {
+            load_metadata: LoadMetadata {
+                kind: self
+                    .tarball_options
+                    .kind()
+                    .map(Ok::<_, Error>)
+                    .unwrap_or_else(|| infer_local_executor_type(&state.executor_root))?,
+                src: state.executor_root.clone(),
+            },
+            state: to_value(state)?,
+        })
+    }
+}
+
+impl ExecutorResolver for TarballOverHttpResolver<'_> {
+    fn resolve(&self, url: &Url) -> Result<ExecutorSource> {
+        match self.download(url, None)? {
+            Download::Modified {
+                content,
+                etag,
+                last_modified,
+            } => self.source(self.unpack(url, &content, etag, last_modified)?),
+            Download::NotModified => bail!("unexpected 304 response status from {url}"),
+        }
+    }
+
+    fn update(&self, url: &Url, state: &Value) -> Result<Option<ExecutorSource>> {
+        let state = State::deserialize(state)?;
+
+        let previous = if state.executor_root.try_exists()? {
+            Some(&state)
+        } else {
+            None
+        };
+
+        match self.download(url, previous)? {
+            Download::NotModified => {
+                self.logger.debug(format!("{url} was not modified"));
+                Ok(None)
+            }
+            Download::Modified {
+                content,
+                etag,
+                last_modified,
+            } => {
+                let new_state = self.unpack(url, &content, etag, last_modified)?;
+
+                if state.download_root.try_exists()? {
+                    std::fs::remove_dir_all(&state.download_root)?;
+                }
+
+                self.logger
+                    .debug(format!("executor files were updated for {url}"));
+
+                Ok(Some(self.source(new_state)?))
+            }
+        }
+    }
+}
+
+/// When the tarball content is wrapped in a single top-level directory, this directory is used as the executor root.
+fn executor_root(download_root: &Path) -> Result<PathBuf> {
+    let entries = std::fs::read_dir(download_root)?.collect::<std::io::Result<Vec<_>>>()?;
+
+    match &entries[..] {
+        [entry] if entry.file_type()?.is_dir() => Ok(entry.path()),
+        _ => Ok(download_root.to_owned()),
+    }
+}
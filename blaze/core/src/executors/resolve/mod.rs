@@ -1,7 +1,9 @@
+pub mod cargo;
 pub mod file_system;
 pub mod git;
 pub mod git_common;
 pub mod http_git;
+pub mod http_tarball;
 pub mod kinds;
 pub mod loader;
 pub mod npm;
@@ -10,8 +12,9 @@ pub mod ssh_git;
 pub mod standard;
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     hash::{Hash, Hasher},
+    path::Path,
 };
 
 use anyhow::Context;
@@ -23,14 +26,16 @@ use blaze_common::{
     workspace::Workspace,
 };
 use possibly::possibly;
-use rand::{thread_rng, RngCore};
 use resolver::ExecutorSource;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::{
     executors::DynExecutor,
-    system::{hash::hasher, locks::ProcessLock, parallel_executor::ParallelRunner},
+    system::{
+        glob::glob, hash::hasher, hash_cache::FileHashCache, locks::ProcessLock,
+        parallel_executor::ParallelRunner,
+    },
     workspace::cache_store::CacheStore,
 };
 
@@ -54,7 +59,7 @@ pub struct CachedMetadata {
     pub kind: ExecutorKind,
     pub resolution_state: Value,
     pub executor_metadata: Value,
-    pub nonce: u64,
+    pub digest: u64,
 }
 
 pub struct ResolvedExecutors {
@@ -81,7 +86,7 @@ impl ExecutorResolution {
     }
 
     pub fn resolution_cache(&self) -> Option<(ExecutorCacheState, u64)> {
-        possibly!(self, Self::Custom(CustomExecutorResolution { state, nonce, .. }) => (*state, *nonce))
+        possibly!(self, Self::Custom(CustomExecutorResolution { state, digest, .. }) => (*state, *digest))
     }
 }
 
@@ -186,7 +191,8 @@ pub enum ExecutorCacheState {
 pub struct CustomExecutorResolution {
     state: ExecutorCacheState,
     executor: DynExecutor,
-    nonce: u64,
+    /// Identifies the executor version, see [`executor_digest`].
+    digest: u64,
 }
 
 fn resolve_custom_executor(
@@ -197,7 +203,7 @@ fn resolve_custom_executor(
 ) -> Result<CustomExecutorResolution> {
     let resolver: Box<dyn ExecutorResolver> = resolver_for_location(location.clone(), context);
 
-    let state_key = format!("executors/{package_id}");
+    let state_key = format!("executors/{package_id}/resolution");
 
     let maybe_cached_metadata = context
         .cache
@@ -225,24 +231,24 @@ fn resolve_custom_executor(
                     state,
                     load_metadata,
                 }) => {
+                    let digest = executor_digest(package_id, &load_metadata.src, context)?;
                     let reloaded_executor = loader_for_executor_kind(load_metadata.kind)
                         .load_from_src(&load_metadata.src, load_context)?;
                     context
                         .logger
                         .debug(format!("{url} was reloaded from source"));
                     let reloaded_executor_metadata = reloaded_executor.metadata()?;
-                    let nonce = thread_rng().next_u64();
                     (
                         CustomExecutorResolution {
                             executor: reloaded_executor.to_dyn(),
                             state: ExecutorCacheState::Updated,
-                            nonce,
+                            digest,
                         },
                         CachedMetadata {
                             kind: load_metadata.kind,
                             executor_metadata: reloaded_executor_metadata,
                             resolution_state: state,
-                            nonce,
+                            digest,
                         },
                     )
                 }
@@ -256,7 +262,7 @@ fn resolve_custom_executor(
                         CustomExecutorResolution {
                             executor: cached_executor.to_dyn(),
                             state: ExecutorCacheState::Cached,
-                            nonce: cached_metadata.nonce,
+                            digest: cached_metadata.digest,
                         },
                         cached_metadata,
                     )
@@ -270,6 +276,8 @@ fn resolve_custom_executor(
 
             context.logger.debug(format!("{url} was resolved"));
 
+            let digest = executor_digest(package_id, &resolution.load_metadata.src, context)?;
+
             let executor = loader_for_executor_kind(resolution.load_metadata.kind)
                 .load_from_src(&resolution.load_metadata.src, load_context)?;
 
@@ -278,19 +286,18 @@ fn resolve_custom_executor(
                 .debug(format!("{url} was loaded from source"));
 
             let executor_metadata = executor.metadata()?;
-            let nonce: u64 = thread_rng().next_u64();
 
             (
                 CustomExecutorResolution {
                     executor: executor.to_dyn(),
                     state: ExecutorCacheState::New,
-                    nonce,
+                    digest,
                 },
                 CachedMetadata {
                     kind: resolution.load_metadata.kind,
                     executor_metadata,
                     resolution_state: resolution.state,
-                    nonce,
+                    digest,
                 },
             )
         }
@@ -305,6 +312,30 @@ fn resolve_custom_executor(
     Ok(executor)
 }
 
+/// Compute a digest of a custom executor from its package ID and the content of its source files (before they are built).
+/// Unlike a random value, it is the same on every machine which resolves the same executor sources, so that cache entries can be shared.
+fn executor_digest(
+    package_id: u64,
+    src: &Path,
+    context: CustomResolutionContext<'_>,
+) -> Result<u64> {
+    let file_hashes = FileHashCache::for_workspace(context.workspace.root())?;
+    let mut hasher = hasher();
+    package_id.hash(&mut hasher);
+    let paths = glob(src, "**", ["node_modules/**", "target/**", ".git/**"])
+        .with_context(|| format!("could not list executor source files at {}", src.display()))?
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+    for path in paths {
+        path.strip_prefix(src).unwrap_or(&path).hash(&mut hasher);
+        file_hashes
+            .hash_file(&path)
+            .with_context(|| format!("could not compute hash of {}", path.display()))?
+            .hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
 pub fn get_executor_package_id(reference: &ExecutorReference) -> u64 {
     let mut hasher = hasher();
     match reference {
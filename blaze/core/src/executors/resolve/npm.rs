@@ -1,40 +1,290 @@
-use std::path::PathBuf;
+use std::{
+    collections::HashMap,
+    io::Read,
+    path::{Path, PathBuf},
+};
 
-use blaze_common::{error::Result, executor::NpmOptions, logger::Logger, value::Value, workspace::Workspace};
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    error::Result,
+    executor::{Compression, ExecutorKind, NpmOptions},
+    logger::Logger,
+    value::{to_value, Value},
+    workspace::Workspace,
+};
+use serde::{Deserialize, Serialize};
 use url::Url;
 
-use super::resolver::{ExecutorResolver, ExecutorSource};
+use crate::{
+    executors::node::npm,
+    system::{archive::unpack_tarball, process::ProcessOptions, random::random_string},
+};
+
+use super::{
+    loader::LoadMetadata,
+    resolver::{ExecutorResolver, ExecutorSource},
+};
 
 const PACKAGE_LOCATION: &str = ".blaze/npm";
+const DEFAULT_REGISTRY_HOST: &str = "registry.npmjs.org";
+const LATEST_VERSION_TAG: &str = "latest";
+
+/// Environment variable through which the registry token is passed to npm, so that it never appears in process arguments.
+const TOKEN_ENVIRONMENT_VARIABLE: &str = "BLAZE_NPM_TOKEN";
+const USER_CONFIG_ENVIRONMENT_VARIABLE: &str = "NPM_CONFIG_USERCONFIG";
+
+/// Root folder of the package, inside of the tarball that is returned by `npm pack`.
+const TARBALL_PACKAGE_ROOT: &str = "package";
+
+#[derive(Serialize, Deserialize)]
+struct State {
+    version: String,
+    package_path: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct PackedTarball {
+    filename: String,
+    version: String,
+}
 
-struct NpmResolver<'a> {
+pub struct NpmResolver<'a> {
     options: NpmOptions,
     logger: &'a Logger,
-    packages_root: PathBuf
+    packages_root: PathBuf,
 }
 
 #[derive(Clone, Copy)]
 pub struct NpmResolverContext<'a> {
-    workspace: &'a Workspace,
-    logger: &'a Logger
+    pub workspace: &'a Workspace,
+    pub logger: &'a Logger,
 }
 
-impl <'a> NpmResolver<'a> {
+impl<'a> NpmResolver<'a> {
     pub fn new(options: NpmOptions, context: NpmResolverContext<'a>) -> Self {
         Self {
             options,
             logger: context.logger,
-            packages_root: context.workspace.root().join(PACKAGE_LOCATION)
+            packages_root: context.workspace.root().join(PACKAGE_LOCATION),
         }
     }
+
+    /// Get the package specifier (`<name>@<version>`) from the executor URL.
+    fn package_spec(&self, url: &Url) -> Result<String> {
+        let name = url.path().trim_start_matches('/');
+        if name.is_empty() {
+            bail!("npm package name is missing in {url}");
+        }
+        Ok(format!(
+            "{name}@{}",
+            self.options.version().unwrap_or(LATEST_VERSION_TAG)
+        ))
+    }
+
+    /// Get the registry host (`<host>[:<port>]`) from the executor URL.
+    fn registry_host(url: &Url) -> Option<String> {
+        url.host_str().map(|host| match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_owned(),
+        })
+    }
+
+    /// Get the registry related options that must be passed to each npm command.
+    /// The registry token is not part of them, see [`Self::npm_json`].
+    fn registry_args(&self, url: &Url) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(host) = Self::registry_host(url) {
+            args.push(format!("--registry=https://{host}/"));
+        }
+
+        if self.options.insecure() {
+            args.push("--strict-ssl=false".into());
+        }
+
+        args
+    }
+
+    /// Run an npm command with the registry options and get its parsed JSON output.
+    ///
+    /// When a registry token is provided, it is passed through an environment variable which is referenced
+    /// by a temporary user configuration file, so that it is not visible in the process arguments.
+    fn npm_json<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &Url,
+        mut args: Vec<String>,
+        cwd: &Path,
+    ) -> Result<T> {
+        let command = args.first().cloned().unwrap_or_default();
+        args.extend(self.registry_args(url));
+
+        let mut environment = HashMap::new();
+
+        let user_config = match self.options.token() {
+            Some(token) => {
+                let registry_host =
+                    Self::registry_host(url).unwrap_or_else(|| DEFAULT_REGISTRY_HOST.to_owned());
+                let path = self
+                    .packages_root
+                    .join(format!("{}.npmrc", random_string(12)));
+                std::fs::write(
+                    &path,
+                    format!("//{registry_host}/:_authToken=${{{TOKEN_ENVIRONMENT_VARIABLE}}}\n"),
+                )
+                .with_context(|| format!("could not write {}", path.display()))?;
+                let _ = environment.insert(
+                    USER_CONFIG_ENVIRONMENT_VARIABLE.to_owned(),
+                    path.display().to_string(),
+                );
+                let _ = environment.insert(TOKEN_ENVIRONMENT_VARIABLE.to_owned(), token.to_owned());
+                Some(path)
+            }
+            None => None,
+        };
+
+        let result = Self::run_npm(&command, args, cwd, environment);
+
+        if let Some(path) = user_config {
+            let _ = std::fs::remove_file(path);
+        }
+
+        result
+    }
+
+    fn run_npm<T: for<'de> Deserialize<'de>>(
+        command: &str,
+        args: Vec<String>,
+        cwd: &Path,
+        environment: HashMap<String, String>,
+    ) -> Result<T> {
+        let process = npm(
+            &args,
+            ProcessOptions {
+                cwd: Some(cwd.to_owned()),
+                environment,
+                ..Default::default()
+            },
+        )
+        .with_context(|| format!("could not start npm {command} process"))?;
+
+        let mut output = String::new();
+        process.stdout()?.read_to_string(&mut output)?;
+
+        let status = process.wait()?;
+        if !status.success {
+            bail!("npm {command} failed (exitcode={:?})", status.code);
+        }
+
+        serde_json::from_str(&output)
+            .with_context(|| format!("could not parse npm {command} output"))
+    }
+
+    /// Get the version that would be fetched for the current package specifier.
+    fn remote_version(&self, url: &Url) -> Result<String> {
+        let args = vec![
+            "view".into(),
+            self.package_spec(url)?,
+            "version".into(),
+            "--json".into(),
+        ];
+
+        std::fs::create_dir_all(&self.packages_root)?;
+
+        // when the version specifier is a range, npm returns all the matching versions in ascending order.
+        match self.npm_json::<Value>(url, args, &self.packages_root)? {
+            Value::String(version) => Ok(version),
+            Value::Array(versions) => versions
+                .last()
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+                .ok_or_else(|| anyhow!("no matching version for {url}")),
+            _ => bail!("unexpected npm output while resolving version for {url}"),
+        }
+    }
+
+    /// Download and unpack the package.
+    fn fetch(&self, url: &Url) -> Result<State> {
+        let package_root = self.packages_root.join(random_string(12));
+
+        std::fs::create_dir_all(&package_root)?;
+
+        let args = vec![
+            "pack".into(),
+            self.package_spec(url)?,
+            "--json".into(),
+            "--pack-destination".into(),
+            package_root.display().to_string(),
+        ];
+
+        let tarballs = self.npm_json::<Vec<PackedTarball>>(url, args, &package_root)?;
+        let tarball = tarballs
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("npm did not pack any tarball for {url}"))?;
+
+        let tarball_path = package_root.join(&tarball.filename);
+
+        self.logger.debug(format!(
+            "downloaded {url} (version {}) to {}",
+            tarball.version,
+            tarball_path.display()
+        ));
+
+        unpack_tarball(
+            std::fs::File::open(&tarball_path)
+                .with_context(|| format!("could not open {}", tarball_path.display()))?,
+            Some(Compression::Gzip),
+            &package_root,
+        )
+        .with_context(|| format!("could not unpack {}", tarball_path.display()))?;
+
+        std::fs::remove_file(&tarball_path)?;
+
+        Ok(State {
+            version: tarball.version,
+            package_path: package_root.join(TARBALL_PACKAGE_ROOT),
+        })
+    }
+
+    fn source(state: State) -> Result<ExecutorSource> {
+        Ok(ExecutorSource {
+            load_metadata: LoadMetadata {
+                src: state.package_path.clone(),
+                kind: ExecutorKind::Node,
+            },
+            state: to_value(state)?,
+        })
+    }
 }
 
 impl ExecutorResolver for NpmResolver<'_> {
-    fn resolve(&self, _url: &Url) -> Result<ExecutorSource> {
-        todo!()
+    fn resolve(&self, url: &Url) -> Result<ExecutorSource> {
+        Self::source(self.fetch(url)?)
     }
 
-    fn update(&self, _url: &Url, _state: &Value) -> Result<Option<ExecutorSource>> {
-        todo!()
+    fn update(&self, url: &Url, state: &Value) -> Result<Option<ExecutorSource>> {
+        let state = State::deserialize(state)?;
+        let remote_version = self.remote_version(url)?;
+
+        if remote_version == state.version && state.package_path.try_exists()? {
+            self.logger
+                .debug(format!("{url} is up to date ({remote_version})"));
+            return Ok(None);
+        }
+
+        let new_state = self.fetch(url)?;
+
+        if let Some(previous_root) = state.package_path.parent() {
+            if previous_root.try_exists()? {
+                std::fs::remove_dir_all(previous_root)?;
+            }
+        }
+
+        self.logger.debug(format!(
+            "{url} was updated from {} to {}",
+            state.version, new_state.version
+        ));
+
+        Ok(Some(Self::source(new_state)?))
     }
 }
@@ -2,8 +2,15 @@ use blaze_common::{error::Result, executor::Location, value::Value};
 use url::Url;
 
 use super::{
-    file_system::FileSystemResolver, git::GitResolver, git_common::GitResolverContext,
-    http_git::GitOverHttpResolver, loader::LoadMetadata, ssh_git::GitOverSshResolver,
+    cargo::{CargoResolver, CargoResolverContext},
+    file_system::FileSystemResolver,
+    git::GitResolver,
+    git_common::GitResolverContext,
+    http_git::GitOverHttpResolver,
+    http_tarball::{TarballOverHttpResolver, TarballResolverContext},
+    loader::LoadMetadata,
+    npm::{NpmResolver, NpmResolverContext},
+    ssh_git::GitOverSshResolver,
     CustomResolutionContext,
 };
 
@@ -52,6 +59,32 @@ pub fn resolver_for_location<'a>(
             authentication,
             git_context(),
         )),
-        _ => todo!(),
+        Location::Npm { options } => Box::new(NpmResolver::new(
+            options,
+            NpmResolverContext {
+                workspace: context.workspace,
+                logger: context.logger,
+            },
+        )),
+        Location::Cargo { options } => Box::new(CargoResolver::new(
+            options,
+            CargoResolverContext {
+                workspace: context.workspace,
+                logger: context.logger,
+            },
+        )),
+        Location::TarballOverHttp {
+            transport,
+            tarball_options,
+            authentication,
+        } => Box::new(TarballOverHttpResolver::new(
+            transport,
+            tarball_options,
+            authentication,
+            TarballResolverContext {
+                workspace: context.workspace,
+                logger: context.logger,
+            },
+        )),
     }
 }
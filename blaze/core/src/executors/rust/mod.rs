@@ -96,6 +96,7 @@ impl CustomExecutorLoader for RustExecutorLoader {
                     path_to_string(root.join("target"))?,
                 )]
                 .into(),
+                ..Default::default()
             },
         )?
         .wait()?
@@ -139,6 +140,7 @@ impl CustomExecutorLoader for RustExecutorLoader {
                     path_to_string(root.join("target"))?,
                 )]
                 .into(),
+                ..Default::default()
             },
         )?
         .wait()?
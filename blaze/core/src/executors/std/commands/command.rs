@@ -10,12 +10,12 @@ use std::{
 };
 
 use anyhow::{bail, Context};
-use blaze_common::{error::Result, unit_enum_deserialize, unit_enum_from_str};
+use blaze_common::{error::Result, target::TimeSpan, unit_enum_deserialize, unit_enum_from_str};
 use serde::Deserialize;
 use strum_macros::{Display, EnumIter};
 
 use crate::system::{
-    process::{Process, ProcessOptions, ProcessStatus},
+    process::{Process, ProcessGroup, ProcessOptions, ProcessStatus},
     shell::ShellFormatter,
     thread::{join, thread},
 };
@@ -48,6 +48,7 @@ pub struct Command {
     pub cwd: Option<PathBuf>,
     pub environment: HashMap<String, String>,
     pub quiet: bool,
+    pub timeout: Option<Duration>,
 }
 
 impl FromStr for Command {
@@ -61,6 +62,7 @@ impl FromStr for Command {
             environment: Default::default(),
             on_failure: OnFailure::default(),
             quiet: false,
+            timeout: None,
         })
     }
 }
@@ -85,6 +87,7 @@ impl<'de> Deserialize<'de> for Command {
             environment: HashMap<String, String>,
             #[serde(default)]
             quiet: bool,
+            timeout: Option<TimeSpan>,
         }
 
         #[derive(Deserialize)]
@@ -106,6 +109,7 @@ impl<'de> Deserialize<'de> for Command {
                     environment: command.environment,
                     on_failure: command.on_failure,
                     quiet: command.quiet,
+                    timeout: command.timeout.as_ref().map(TimeSpan::duration),
                 },
             },
         )
@@ -127,11 +131,13 @@ impl Command {
     pub fn run<T: FnOnce(Result<ProcessStatus>) -> Result<()> + Send + 'static>(
         self,
         use_shell: Option<&UseShell>,
+        process_group: &ProcessGroup,
         termination: T,
     ) -> Result<RunningCommand> {
         let (process_send, process_recv) = channel::<Result<Arc<Process>>>();
 
         let self_clone = self.to_owned();
+        let process_group = process_group.clone();
 
         let (program, arguments) = match (use_shell, self.argv.clone()) {
             (None, Argv::Vec(program, arguments)) => (program, arguments),
@@ -153,13 +159,14 @@ impl Command {
         };
 
         let process_thread = thread!(move || {
-            let process = match Process::run_with_options(
+            let mut process = match Process::run_with_options(
                 &program,
                 &arguments,
                 ProcessOptions {
                     cwd: self_clone.cwd.to_owned(),
                     display_output: !self_clone.quiet,
                     environment: self_clone.environment.to_owned(),
+                    group: Some(process_group.clone()),
                 },
             )
             .with_context(|| format!("error while creating process for command \"{self_clone}\"."))
@@ -171,6 +178,14 @@ impl Command {
                 }
             };
 
+            if let Err(err) = process
+                .write_group_input(&process_group)
+                .with_context(|| format!("could not write input of command \"{self_clone}\"."))
+            {
+                process_send.send(Err(err))?;
+                return Ok(());
+            }
+
             let pid = process.pid();
 
             let arc_process_0 = Arc::new(process);
@@ -210,6 +225,11 @@ pub struct RunningCommand {
 }
 
 impl RunningCommand {
+    /// Kill the command process without waiting for its termination.
+    pub fn kill_process(&self) -> Result<()> {
+        self.process.kill()
+    }
+
     pub fn kill(self) -> Result<()> {
         self.process.kill()?;
         self.join()?;
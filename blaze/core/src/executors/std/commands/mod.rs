@@ -1,10 +1,12 @@
+use std::{collections::HashMap, path::Path};
+
 use anyhow::Context;
-use blaze_common::{error::Result, value::Value};
+use blaze_common::{error::Result, logger::Logger, value::Value};
 use serde::Deserialize;
 
 use crate::{
     executors::{env::get_executor_env, Executor, ExecutorContext},
-    system::process::ProcessStatus,
+    system::process::{ProcessGroup, ProcessStatus},
 };
 
 use self::{
@@ -29,45 +31,68 @@ pub struct CommandsExecutor;
 
 impl Executor for CommandsExecutor {
     fn execute(&self, ctx: ExecutorContext, raw_options: Value) -> Result<()> {
-        let options = CommandsExecutorOptions::deserialize(&raw_options).with_context(|| {
-            format!("error while converting commands executor options from {raw_options}")
-        })?;
+        run_commands(
+            raw_options,
+            ctx.project.root(),
+            get_executor_env(&ctx)?,
+            ctx.logger,
+            ctx.process_group,
+        )
+    }
+}
 
-        let mut runner = CommandsRunner::new(CommandsRunnerOptions {
-            use_shell: options.use_shell,
-            default_cwd: ctx.project.root().to_owned(),
-            default_environment: get_executor_env(&ctx)?,
-        });
+/// Run commands described by `std:commands` executor options, outside of any target.
+pub fn run_commands(
+    raw_options: Value,
+    cwd: &Path,
+    environment: HashMap<String, String>,
+    logger: &Logger,
+    process_group: &ProcessGroup,
+) -> Result<()> {
+    let options = CommandsExecutorOptions::deserialize(&raw_options).with_context(|| {
+        format!("error while converting commands executor options from {raw_options}")
+    })?;
 
-        runner.on_command_started(|command| {
-            ctx.logger.info(format!("+ {command}"));
-        });
+    let mut runner = CommandsRunner::new(CommandsRunnerOptions {
+        use_shell: options.use_shell,
+        default_cwd: cwd.to_owned(),
+        default_environment: environment,
+        process_group: process_group.clone(),
+    });
 
-        runner.on_command_terminated(|command, status| match *status {
-            ProcessStatus { success: true, .. } => {
-                ctx.logger
-                    .debug(format!("command \"{command}\" was successful"));
-            }
-            ProcessStatus {
-                code: Some(code),
-                success: false,
-            } => {
-                ctx.logger
-                    .error(format!("\"{command}\" has failed with status code {code}"));
-            }
-            ProcessStatus {
-                code: None,
-                success: false,
-            } => {
-                ctx.logger
-                    .error(format!("\"{command}\" exited without any status code"));
-            }
-        });
+    runner.on_command_started(|command| {
+        logger.info(format!("+ {command}"));
+    });
 
-        runner
-            .run_all(&options.commands)
-            .context("error while running commands")?;
+    runner.on_command_timed_out(|command| {
+        if let Some(timeout) = command.timeout {
+            logger.error(format!(
+                "\"{command}\" has timed out after {timeout:?}, killing process"
+            ));
+        }
+    });
 
-        Ok(())
-    }
+    runner.on_command_terminated(|command, status| match *status {
+        ProcessStatus { success: true, .. } => {
+            logger.debug(format!("command \"{command}\" was successful"));
+        }
+        ProcessStatus {
+            code: Some(code),
+            success: false,
+        } => {
+            logger.error(format!("\"{command}\" has failed with status code {code}"));
+        }
+        ProcessStatus {
+            code: None,
+            success: false,
+        } => {
+            logger.error(format!("\"{command}\" exited without any status code"));
+        }
+    });
+
+    runner
+        .run_all(&options.commands)
+        .context("error while running commands")?;
+
+    Ok(())
 }
@@ -1,13 +1,17 @@
 use std::{
-    collections::{BTreeMap, HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     path::PathBuf,
-    sync::mpsc::{channel, Receiver},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    time::Instant,
 };
 
 use anyhow::{bail, Context};
 use blaze_common::error::Result;
 
-use crate::{executors::std::options::UseShell, system::process::ProcessStatus};
+use crate::{
+    executors::std::options::UseShell,
+    system::process::{ProcessFailure, ProcessGroup, ProcessStatus},
+};
 
 use super::command::{Command, OnFailure, RunningCommand};
 
@@ -20,15 +24,18 @@ pub struct CommandsRunnerOptions {
     pub use_shell: Option<UseShell>,
     pub default_cwd: PathBuf,
     pub default_environment: HashMap<String, String>,
+    pub process_group: ProcessGroup,
 }
 
 type CommandStartedHandler<'a> = Box<dyn Fn(&Command) + 'a>;
 type CommandTerminatedHandler<'a> = Box<dyn Fn(&Command, &ProcessStatus) + 'a>;
+type CommandTimedOutHandler<'a> = Box<dyn Fn(&Command) + 'a>;
 
 pub struct CommandsRunner<'a> {
     options: CommandsRunnerOptions,
     command_started_handler: CommandStartedHandler<'a>,
     command_terminated_handler: CommandTerminatedHandler<'a>,
+    command_timed_out_handler: CommandTimedOutHandler<'a>,
 }
 
 impl<'a> CommandsRunner<'a> {
@@ -37,6 +44,7 @@ impl<'a> CommandsRunner<'a> {
             options,
             command_started_handler: Box::new(|_| {}),
             command_terminated_handler: Box::new(|_, _| {}),
+            command_timed_out_handler: Box::new(|_| {}),
         }
     }
 
@@ -48,9 +56,15 @@ impl<'a> CommandsRunner<'a> {
         self.command_terminated_handler = Box::new(terminated);
     }
 
+    pub fn on_command_timed_out<F: Fn(&Command) + 'a>(&mut self, timed_out: F) {
+        self.command_timed_out_handler = Box::new(timed_out);
+    }
+
     pub fn run_all(&self, commands: &[Command]) -> Result<()> {
         let mut pending = VecDeque::from_iter(commands);
         let mut running: BTreeMap<usize, RunningCommand> = BTreeMap::new();
+        let mut deadlines = HashMap::<usize, Instant>::new();
+        let mut timed_out = HashSet::<usize>::new();
         let (termination_send, termination_recv) = channel::<Result<Termination>>();
 
         let on_terminate_for = |i: usize| {
@@ -72,9 +86,13 @@ impl<'a> CommandsRunner<'a> {
                     command_index,
                     next.to_owned().run(
                         self.options.use_shell.as_ref(),
+                        &self.options.process_group,
                         on_terminate_for(command_index),
                     )?,
                 );
+                if let Some(timeout) = next.timeout {
+                    deadlines.insert(command_index, Instant::now() + timeout);
+                }
                 (self.command_started_handler)(&next);
             }
 
@@ -82,9 +100,42 @@ impl<'a> CommandsRunner<'a> {
                 break;
             }
 
-            let termination = termination_recv
-                .recv()
-                .context("error while waiting for command termination signal")??;
+            let termination = loop {
+                let next_deadline = deadlines
+                    .iter()
+                    .min_by_key(|(_, deadline)| **deadline)
+                    .map(|(index, deadline)| (*index, *deadline));
+
+                let Some((index, deadline)) = next_deadline else {
+                    break termination_recv
+                        .recv()
+                        .context("error while waiting for command termination signal")?;
+                };
+
+                match termination_recv
+                    .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                {
+                    Ok(termination) => break termination,
+                    Err(RecvTimeoutError::Timeout) => {
+                        let _ = deadlines.remove(&index);
+                        let timed_out_command = &running[&index];
+                        (self.command_timed_out_handler)(&timed_out_command.command);
+                        timed_out_command.kill_process().with_context(|| {
+                            format!(
+                                "error while killing timed out command {}",
+                                timed_out_command.command
+                            )
+                        })?;
+                        timed_out.insert(index);
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        bail!("command termination channel was closed unexpectedly")
+                    }
+                }
+            }?;
+
+            let _ = deadlines.remove(&termination.command);
+            let is_timed_out = timed_out.remove(&termination.command);
 
             let terminating = running.remove(&termination.command).unwrap();
             let terminated_cmd = terminating.command.to_owned();
@@ -93,21 +144,33 @@ impl<'a> CommandsRunner<'a> {
                 .with_context(|| format!("error while joining command {terminated_cmd} thread"))?;
             (self.command_terminated_handler)(&terminated_cmd, &termination.status);
 
-            if !termination.status.success {
+            if is_timed_out || !termination.status.success {
+                let failure = describe_failure(&terminated_cmd, is_timed_out);
                 match terminated_cmd.on_failure {
                     OnFailure::Restart => {
+                        if let Some(timeout) = terminated_cmd.timeout {
+                            deadlines.insert(termination.command, Instant::now() + timeout);
+                        }
                         let _ = running.insert(
                             termination.command,
                             terminated_cmd.run(
                                 self.options.use_shell.as_ref(),
+                                &self.options.process_group,
                                 on_terminate_for(termination.command),
                             )?,
                         );
                     }
                     OnFailure::Ignore => (),
-                    OnFailure::ForceExit => return Self::kill_all(terminated_cmd, running),
+                    OnFailure::ForceExit => {
+                        return Self::kill_all(failure, termination.status.code, running)
+                    }
                     OnFailure::Exit => {
-                        return Self::command_failure(terminated_cmd, running, termination_recv)
+                        return Self::command_failure(
+                            failure,
+                            termination.status.code,
+                            running,
+                            termination_recv,
+                        )
                     }
                 }
             }
@@ -116,7 +179,11 @@ impl<'a> CommandsRunner<'a> {
         Ok(())
     }
 
-    fn kill_all(cause: Command, running: BTreeMap<usize, RunningCommand>) -> Result<()> {
+    fn kill_all(
+        failure: String,
+        code: Option<i32>,
+        running: BTreeMap<usize, RunningCommand>,
+    ) -> Result<()> {
         let kills = running
             .into_values()
             .map(RunningCommand::kill)
@@ -138,17 +205,21 @@ impl<'a> CommandsRunner<'a> {
             Some(details.join(", "))
         };
 
-        bail!(
-            "command {} failed {}",
-            cause,
-            error_details
-                .map(|details| format!("({details})"))
-                .unwrap_or(String::default())
-        )
+        bail!(ProcessFailure {
+            message: format!(
+                "{} {}",
+                failure,
+                error_details
+                    .map(|details| format!("({details})"))
+                    .unwrap_or(String::default())
+            ),
+            code
+        })
     }
 
     fn command_failure(
-        cause: Command,
+        failure: String,
+        code: Option<i32>,
         mut running: BTreeMap<usize, RunningCommand>,
         termination_recv: Receiver<Result<Termination>>,
     ) -> Result<()> {
@@ -162,7 +233,7 @@ impl<'a> CommandsRunner<'a> {
             })
             .collect::<Result<Vec<ProcessStatus>>>()?;
         let failed = statuses.iter().filter(|status| !status.success).count();
-        let mut error_msg = format!("command \"{cause}\" failed");
+        let mut error_msg = failure;
         if detached > 0 {
             error_msg.push(' ');
             error_msg.push_str(&if failed > 0 {
@@ -171,7 +242,10 @@ impl<'a> CommandsRunner<'a> {
                 "(all detached processes exited successfully)".into()
             })
         }
-        bail!(error_msg)
+        bail!(ProcessFailure {
+            message: error_msg,
+            code
+        })
     }
 
     fn configure_cmd(&self, command: &mut Command) -> Result<()> {
@@ -190,3 +264,12 @@ impl<'a> CommandsRunner<'a> {
         Ok(())
     }
 }
+
+fn describe_failure(command: &Command, is_timed_out: bool) -> String {
+    match command.timeout {
+        Some(timeout) if is_timed_out => {
+            format!("command \"{command}\" timed out after {timeout:?}")
+        }
+        _ => format!("command \"{command}\" failed"),
+    }
+}
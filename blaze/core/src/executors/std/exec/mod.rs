@@ -3,7 +3,7 @@ use std::{collections::HashMap, path::PathBuf};
 use crate::{
     executors::{env::get_executor_env, Executor, ExecutorContext},
     system::{
-        process::{Process, ProcessOptions},
+        process::{Process, ProcessFailure, ProcessOptions},
         shell::ShellFormatter,
     },
 };
@@ -86,18 +86,25 @@ impl Executor for ExecExecutor {
             (program, options.arguments)
         };
 
-        let result = Process::run_with_options(
+        let mut process = Process::run_with_options(
             &program,
             arguments,
             ProcessOptions {
                 cwd: Some(cwd),
                 display_output: !options.quiet,
                 environment: options.environment,
+                group: Some(context.process_group.clone()),
             },
         )
-        .with_context(|| format!("could not create process for \"{}\"", program.display()))?
-        .wait()
-        .context("could not wait for process termination")?;
+        .with_context(|| format!("could not create process for \"{}\"", program.display()))?;
+
+        process
+            .write_group_input(context.process_group)
+            .context("could not write process input")?;
+
+        let result = process
+            .wait()
+            .context("could not wait for process termination")?;
 
         if !result.success {
             let error = format!(
@@ -108,7 +115,10 @@ impl Executor for ExecExecutor {
                     .map(|c| format!("with status code {c}"))
                     .unwrap_or_else(|| "without any status code".into())
             );
-            bail!(error)
+            bail!(ProcessFailure {
+                message: error,
+                code: result.code
+            })
         }
 
         context
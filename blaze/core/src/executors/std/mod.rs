@@ -2,5 +2,5 @@ mod commands;
 mod exec;
 mod options;
 
-pub use commands::CommandsExecutor;
+pub use commands::{run_commands, CommandsExecutor};
 pub use exec::ExecExecutor;
@@ -6,9 +6,15 @@ mod usecases;
 mod workspace;
 
 pub use blaze_common as common;
-pub use executions::graph::ExecutedGraph;
+pub use executions::{
+    check::{
+        ChangedFile, ChangedVariable, ExecutionCacheState, FileChangeKind, InvalidationReason,
+        VariableChangeKind,
+    },
+    graph::ExecutedGraph,
+};
 pub use usecases::*;
-pub use workspace::selection::SelectorSource;
+pub use workspace::{cache_store::CacheEntry, selection::SelectorSource};
 
 #[cfg(feature = "testing")]
 pub use system::time;
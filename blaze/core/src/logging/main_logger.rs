@@ -14,7 +14,7 @@ pub fn get_contextual_logger(level: LogLevel, context: &str) -> Logger {
     Logger::new(strategy)
 }
 
-fn fmt_log_level(level: LogLevel) -> ColoredString {
+pub(super) fn fmt_log_level(level: LogLevel) -> ColoredString {
     let raw = level.to_string().to_uppercase();
 
     match level {
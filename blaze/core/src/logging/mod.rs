@@ -1,5 +1,7 @@
 mod colors;
 mod main_logger;
+mod run_display;
 
 pub use colors::*;
 pub use main_logger::*;
+pub use run_display::*;
@@ -0,0 +1,372 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Write as _,
+    io::{IsTerminal, Write},
+    sync::{Arc, Mutex, MutexGuard},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use blaze_common::{
+    error::Result,
+    logger::{LogLevel, Logger, LoggingStrategy},
+};
+use colored::{ColoredString, Colorize};
+use terminal_size::{terminal_size, Height, Width};
+
+use crate::system::{
+    process::OutputSink,
+    thread::{join, thread},
+};
+
+use super::{colorize, main_logger::fmt_log_level};
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const DEFAULT_TERMINAL_SIZE: (usize, usize) = (80, 24);
+
+/// How a target execution ended, as displayed by a [`RunDisplay`].
+pub enum TargetOutcome {
+    Executed,
+    Cached,
+    Noop,
+    Failed,
+    Skipped,
+}
+
+struct RunningTarget {
+    start: Instant,
+    tail: VecDeque<String>,
+    partial: Vec<u8>,
+}
+
+#[derive(Default)]
+struct DisplayState {
+    total: usize,
+    done: usize,
+    failed: usize,
+    running: BTreeMap<String, RunningTarget>,
+    /// Lines waiting to be printed above the live area.
+    printed: Vec<String>,
+    /// Number of lines of the live area, which must be erased before drawing again.
+    drawn_lines: usize,
+    frame: usize,
+    finished: bool,
+}
+
+/// Interactive terminal display of a run, showing each running target with its elapsed time and the last lines of its output.
+/// Targets are collapsed to a single status line once done, except failed ones which keep their output visible.
+#[derive(Clone)]
+pub struct RunDisplay {
+    state: Arc<Mutex<DisplayState>>,
+    tail_lines: usize,
+    level: LogLevel,
+}
+
+/// Handle on the thread that refreshes a [`RunDisplay`].
+pub struct RunDisplayRefresh(JoinHandle<Result<()>>);
+
+impl RunDisplay {
+    /// Returns true if the standard output is attached to a terminal, which is required for interactive display.
+    pub fn is_available() -> bool {
+        std::io::stdout().is_terminal()
+    }
+
+    /// Start displaying a run of the provided amount of targets.
+    /// Up to `tail_lines` lines of output are displayed for each running target.
+    pub fn start(total: usize, tail_lines: usize, level: LogLevel) -> (Self, RunDisplayRefresh) {
+        let display = Self {
+            state: Arc::new(Mutex::new(DisplayState {
+                total,
+                ..Default::default()
+            })),
+            tail_lines,
+            level,
+        };
+
+        let refreshed = display.clone();
+        let handle = thread!(move || -> Result<()> {
+            loop {
+                let finished = {
+                    let mut state = refreshed.lock()?;
+                    refreshed.draw(&mut state)?;
+                    state.finished
+                };
+                if finished {
+                    return Ok(());
+                }
+                std::thread::sleep(REFRESH_INTERVAL);
+            }
+        });
+
+        (display, RunDisplayRefresh(handle))
+    }
+
+    /// Stop refreshing the display, and draw it for the last time.
+    pub fn finish(&self, refresh: RunDisplayRefresh) -> Result<()> {
+        self.lock()?.finished = true;
+        join!(refresh.0)
+    }
+
+    /// Logger that prints messages above the live area.
+    pub fn logger(&self) -> Logger {
+        Logger::new(DisplayLoggingStrategy {
+            display: self.clone(),
+            target: None,
+        })
+    }
+
+    /// Logger that adds messages to the output of a target.
+    pub fn target_logger(&self, double: &str) -> Logger {
+        Logger::new(DisplayLoggingStrategy {
+            display: self.clone(),
+            target: Some(double.to_owned()),
+        })
+    }
+
+    /// Sink for the output of processes spawned by a target.
+    pub fn target_output(&self, double: &str) -> OutputSink {
+        let display = self.clone();
+        let double = double.to_owned();
+        Arc::new(move |_, bytes| {
+            if let Ok(mut state) = display.lock() {
+                if let Some(target) = state.running.get_mut(&double) {
+                    target.partial.extend_from_slice(bytes);
+                    while let Some(index) = target.partial.iter().position(|b| *b == b'\n') {
+                        let line = target.partial.drain(..=index).collect::<Vec<_>>();
+                        push_line(
+                            &mut target.tail,
+                            &String::from_utf8_lossy(&line),
+                            display.tail_lines,
+                        );
+                    }
+                }
+            }
+        })
+    }
+
+    pub fn target_started(&self, double: &str) {
+        if let Ok(mut state) = self.lock() {
+            state.running.insert(
+                double.to_owned(),
+                RunningTarget {
+                    start: Instant::now(),
+                    tail: VecDeque::with_capacity(self.tail_lines),
+                    partial: vec![],
+                },
+            );
+        }
+    }
+
+    pub fn target_finished(&self, double: &str, outcome: TargetOutcome) {
+        let Ok(mut state) = self.lock() else {
+            return;
+        };
+        let Some(target) = state.running.remove(double) else {
+            return;
+        };
+
+        state.done += 1;
+
+        let elapsed = target.start.elapsed();
+        let line = match outcome {
+            TargetOutcome::Executed => format!(
+                "{} {double} (executed in {elapsed:.1?})",
+                colorize("✔", ColoredString::bright_green)
+            ),
+            TargetOutcome::Cached => {
+                format!("{} {double} (cached)", colorize("✔", ColoredString::green))
+            }
+            TargetOutcome::Noop => {
+                format!("{} {double} (done)", colorize("✔", ColoredString::green))
+            }
+            TargetOutcome::Skipped => {
+                format!(
+                    "{} {double} (skipped)",
+                    colorize("-", ColoredString::yellow)
+                )
+            }
+            TargetOutcome::Failed => {
+                state.failed += 1;
+                format!(
+                    "{} {double} (failed after {elapsed:.1?})",
+                    colorize("✘", ColoredString::bright_red)
+                )
+            }
+        };
+
+        state.printed.push(line);
+
+        if matches!(outcome, TargetOutcome::Failed) {
+            let tail = target
+                .tail
+                .iter()
+                .map(|line| format!("    │ {line}"))
+                .collect::<Vec<_>>();
+            state.printed.extend(tail);
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, DisplayState>> {
+        self.state
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on run display)."))
+    }
+
+    fn draw(&self, state: &mut DisplayState) -> Result<()> {
+        let (width, height) = terminal_size()
+            .map(|(Width(w), Height(h))| (w as usize, h as usize))
+            .unwrap_or(DEFAULT_TERMINAL_SIZE);
+
+        let mut buffer = String::new();
+
+        if state.drawn_lines > 0 {
+            // move back to the beginning of the live area, and erase it.
+            write!(buffer, "\x1b[{}F\x1b[J", state.drawn_lines)?;
+        }
+
+        for line in state.printed.drain(..) {
+            buffer.push_str(&line);
+            buffer.push('\n');
+        }
+
+        let mut live = vec![];
+
+        if !state.finished {
+            let spinner = SPINNER[state.frame % SPINNER.len()];
+            state.frame += 1;
+
+            live.push(format!(
+                "{} {}/{} target(s) done, {} running, {} failed",
+                colorize(spinner, ColoredString::cyan),
+                state.done,
+                state.total,
+                state.running.len(),
+                state.failed
+            ));
+
+            // the live area must fit in the terminal, otherwise it could not be erased.
+            let max_lines = height.saturating_sub(1).max(1);
+            // tail lines are indented by 6 columns, they are not shown if the terminal is narrower.
+            let tail_width = width.saturating_sub(6);
+            let show_tails =
+                tail_width > 0 && state.running.len() * (self.tail_lines + 1) < max_lines;
+
+            for (double, target) in &state.running {
+                if live.len() >= max_lines {
+                    break;
+                }
+                live.push(format!(
+                    "  {} {} ({:.1?})",
+                    colorize(spinner, ColoredString::cyan),
+                    colorize(double, |double| double.bold()),
+                    target.start.elapsed()
+                ));
+                if show_tails {
+                    live.extend(
+                        target
+                            .tail
+                            .iter()
+                            .map(|line| format!("    │ {}", truncate(line, tail_width))),
+                    );
+                }
+            }
+
+            live.truncate(max_lines);
+        }
+
+        state.drawn_lines = live.len();
+
+        for line in live {
+            buffer.push_str(&line);
+            buffer.push('\n');
+        }
+
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(buffer.as_bytes())?;
+        stdout.flush()?;
+
+        Ok(())
+    }
+}
+
+struct DisplayLoggingStrategy {
+    display: RunDisplay,
+    target: Option<String>,
+}
+
+impl LoggingStrategy for DisplayLoggingStrategy {
+    fn log(&self, message: &str, level: LogLevel) {
+        if level < self.display.level {
+            return;
+        }
+
+        let Ok(mut state) = self.display.lock() else {
+            return;
+        };
+
+        let message = format!("[{}] {}", fmt_log_level(level), message);
+
+        match self
+            .target
+            .as_ref()
+            .and_then(|double| state.running.get_mut(double))
+        {
+            Some(target) => {
+                for line in message.lines() {
+                    push_line(&mut target.tail, line, self.display.tail_lines);
+                }
+            }
+            None => state.printed.extend(message.lines().map(str::to_owned)),
+        }
+    }
+}
+
+/// Add a line to the tail of a target output, keeping only the last lines.
+fn push_line(tail: &mut VecDeque<String>, line: &str, max: usize) {
+    if max == 0 {
+        return;
+    }
+    if tail.len() == max {
+        let _ = tail.pop_front();
+    }
+    tail.push_back(sanitize(line));
+}
+
+/// Remove control characters and escape sequences, which would break the display.
+/// When the line contains carriage returns, only the last written part is kept.
+fn sanitize(line: &str) -> String {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let line = line.rsplit('\r').next().unwrap_or_default();
+
+    let mut sanitized = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                // skip CSI sequences such as colors, up to their final byte.
+                if chars.next() == Some('[') {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\t' => sanitized.push_str("    "),
+            c if c.is_control() => {}
+            c => sanitized.push(c),
+        }
+    }
+
+    sanitized
+}
+
+fn truncate(line: &str, width: usize) -> &str {
+    match line.char_indices().nth(width) {
+        Some((index, _)) => &line[..index],
+        None => line,
+    }
+}
@@ -0,0 +1,22 @@
+use std::{io::Read, path::Path};
+
+use blaze_common::{error::Result, executor::Compression};
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+
+/// Unpack a tar archive into a destination directory, using an optional compression format.
+pub fn unpack_tarball<R: Read>(
+    reader: R,
+    compression: Option<Compression>,
+    destination: &Path,
+) -> Result<()> {
+    let reader: Box<dyn Read> = match compression {
+        None => Box::new(reader),
+        Some(Compression::Gzip) => Box::new(GzDecoder::new(reader)),
+        Some(Compression::Zlib) => Box::new(ZlibDecoder::new(reader)),
+        Some(Compression::Deflate) => Box::new(DeflateDecoder::new(reader)),
+    };
+
+    tar::Archive::new(reader).unpack(destination)?;
+
+    Ok(())
+}
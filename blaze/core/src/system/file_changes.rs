@@ -1,4 +1,5 @@
 use std::{
+    cell::OnceCell,
     collections::{BTreeSet, HashMap, HashSet},
     fs::metadata,
     path::{Path, PathBuf},
@@ -16,7 +17,7 @@ use blaze_common::{
 
 use serde::{Deserialize, Serialize};
 
-use super::{glob::glob, hash::hash_file};
+use super::{glob::glob, hash_cache::FileHashCache, repository::GitFileIds};
 
 #[derive(PartialEq, Eq, Hash)]
 pub enum FileChangeType {
@@ -96,19 +97,21 @@ struct HashFileState {
 }
 
 /// Compares using only the file's checksum.
-struct HashFileModificationCheck;
+struct HashFileModificationCheck<'a> {
+    file_hashes: &'a FileHashCache,
+}
 
-impl FileModificationCheck for HashFileModificationCheck {
+impl FileModificationCheck for HashFileModificationCheck<'_> {
     fn init(&self, path: &Path) -> Result<Value> {
         Ok(to_value(HashFileState {
-            hash: get_hash(path)?,
+            hash: get_hash(self.file_hashes, path)?,
         })?)
     }
 
     fn check(&self, path: &Path, cached: &Value) -> Result<Option<FileModificationState>> {
         let state = deserialize_state::<HashFileState>(path, cached)?;
 
-        Ok(check_hash_change(path, state.hash)?
+        Ok(check_hash_change(self.file_hashes, path, state.hash)?
             .map(|new_hash| {
                 to_value(HashFileState { hash: new_hash }).map(FileModificationState::modified)
             })
@@ -124,12 +127,14 @@ struct MixedFileState {
 }
 
 /// Compares using the "mtime" field from the file system, and lazily checks if the file's checksum is different.
-struct MixedFileModificationCheck;
+struct MixedFileModificationCheck<'a> {
+    file_hashes: &'a FileHashCache,
+}
 
-impl FileModificationCheck for MixedFileModificationCheck {
+impl FileModificationCheck for MixedFileModificationCheck<'_> {
     fn init(&self, path: &Path) -> Result<Value> {
         Ok(to_value(MixedFileState {
-            hash: get_hash(path)?,
+            hash: get_hash(self.file_hashes, path)?,
             mtime: get_mtime(path)?,
         })?)
     }
@@ -142,7 +147,7 @@ impl FileModificationCheck for MixedFileModificationCheck {
             None => return Ok(None),
         };
 
-        let new_hash = match check_hash_change(path, state.hash)? {
+        let new_hash = match check_hash_change(self.file_hashes, path, state.hash)? {
             Some(new_hash) => new_hash,
             None => {
                 return Ok(Some(FileModificationState::not_modified(to_value(
@@ -163,8 +168,75 @@ impl FileModificationCheck for MixedFileModificationCheck {
     }
 }
 
-fn check_hash_change(path: &Path, cached_hash: u64) -> Result<Option<u64>> {
-    let current_hash = get_hash(path)?;
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct GitFileState {
+    /// Git object id, for files in a git repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    /// File checksum, for files outside of a git repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<u64>,
+}
+
+/// Compares using git object ids, so that the content of clean tracked files is never read.
+/// Files outside of a git repository are compared using their checksum.
+struct GitFileModificationCheck<'a> {
+    ids: OnceCell<Option<GitFileIds>>,
+    file_hashes: &'a FileHashCache,
+}
+
+impl GitFileModificationCheck<'_> {
+    fn state(&self, path: &Path) -> Result<GitFileState> {
+        let ids = match self.ids.get() {
+            Some(ids) => ids,
+            None => {
+                let ids =
+                    GitFileIds::discover(path.parent().unwrap_or(path)).with_context(|| {
+                        format!(
+                            "file changes check: error while reading git repository (path={}).",
+                            path.display()
+                        )
+                    })?;
+                self.ids.get_or_init(|| ids)
+            }
+        };
+
+        Ok(
+            match ids.as_ref().map(|ids| ids.get(path)).transpose()?.flatten() {
+                Some(id) => GitFileState {
+                    id: Some(id),
+                    hash: None,
+                },
+                None => GitFileState {
+                    id: None,
+                    hash: Some(get_hash(self.file_hashes, path)?),
+                },
+            },
+        )
+    }
+}
+
+impl FileModificationCheck for GitFileModificationCheck<'_> {
+    fn init(&self, path: &Path) -> Result<Value> {
+        Ok(to_value(self.state(path)?)?)
+    }
+
+    fn check(&self, path: &Path, cached: &Value) -> Result<Option<FileModificationState>> {
+        let state = deserialize_state::<GitFileState>(path, cached)?;
+        let new_state = self.state(path)?;
+        if new_state == state {
+            return Ok(None);
+        }
+        Ok(Some(FileModificationState::modified(to_value(new_state)?)))
+    }
+}
+
+fn check_hash_change(
+    file_hashes: &FileHashCache,
+    path: &Path,
+    cached_hash: u64,
+) -> Result<Option<u64>> {
+    let current_hash = get_hash(file_hashes, path)?;
     if cached_hash != current_hash {
         Ok(Some(current_hash))
     } else {
@@ -202,8 +274,8 @@ fn get_mtime(path: &Path) -> Result<SystemTime> {
     Ok(UNIX_EPOCH + mtime_ms)
 }
 
-fn get_hash(path: &Path) -> Result<u64> {
-    hash_file(path).with_context(|| {
+fn get_hash(file_hashes: &FileHashCache, path: &Path) -> Result<u64> {
+    file_hashes.hash_file(path).with_context(|| {
         anyhow!(
             "file changes check: error while computing hash (path={}).",
             path.display()
@@ -213,11 +285,16 @@ fn get_hash(path: &Path) -> Result<u64> {
 
 fn modification_check_for_matching_behavior(
     behavior: MatchingBehavior,
-) -> Box<dyn FileModificationCheck> {
+    file_hashes: &FileHashCache,
+) -> Box<dyn FileModificationCheck + '_> {
     match behavior {
-        MatchingBehavior::Hash => Box::new(HashFileModificationCheck),
-        MatchingBehavior::Mixed => Box::new(MixedFileModificationCheck),
+        MatchingBehavior::Hash => Box::new(HashFileModificationCheck { file_hashes }),
+        MatchingBehavior::Mixed => Box::new(MixedFileModificationCheck { file_hashes }),
         MatchingBehavior::Timestamps => Box::new(TimestampsFileModificationCheck),
+        MatchingBehavior::Git => Box::new(GitFileModificationCheck {
+            ids: OnceCell::new(),
+            file_hashes,
+        }),
     }
 }
 
@@ -230,13 +307,14 @@ pub struct MergeResult {
 }
 
 impl MatchedFilesState {
-    pub fn from_files(files: MatchedFiles) -> Result<Self> {
+    pub fn from_files(files: MatchedFiles, file_hashes: &FileHashCache) -> Result<Self> {
         Ok(Self(
             files
                 .0
                 .into_iter()
                 .map(|(behavior, paths)| {
-                    let invalidator = modification_check_for_matching_behavior(behavior);
+                    let invalidator =
+                        modification_check_for_matching_behavior(behavior, file_hashes);
                     Ok((
                         behavior,
                         paths
@@ -252,7 +330,11 @@ impl MatchedFilesState {
         ))
     }
 
-    pub fn merge(mut self, next_matched_files: MatchedFiles) -> Result<MergeResult> {
+    pub fn merge(
+        mut self,
+        next_matched_files: MatchedFiles,
+        file_hashes: &FileHashCache,
+    ) -> Result<MergeResult> {
         let missing_files = self
             .0
             .iter()
@@ -281,8 +363,9 @@ impl MatchedFilesState {
         }
 
         for (behavior, next_files) in next_matched_files.0 {
-            let check = modification_check_for_matching_behavior(behavior);
-            let cached_files_state = self.0.get_mut(&behavior).unwrap();
+            let check = modification_check_for_matching_behavior(behavior, file_hashes);
+            // states stored by older versions do not hold every behavior.
+            let cached_files_state = self.0.entry(behavior).or_default();
             for path in next_files {
                 if let Some(existing_state) = cached_files_state.get_mut(&path) {
                     match check.check(&path, existing_state)? {
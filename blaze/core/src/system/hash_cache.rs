@@ -0,0 +1,148 @@
+use std::{
+    collections::HashMap,
+    fs::Metadata,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context};
+use blaze_common::error::Result;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use super::hash::hash_file;
+
+/// Files modified more recently than this might be modified again without any visible metadata change,
+/// so their checksum is not cached.
+const RACY_MODIFICATION_DELAY: Duration = Duration::from_secs(2);
+
+static FILE_HASH_CACHES: Lazy<Mutex<HashMap<PathBuf, Arc<FileHashCache>>>> =
+    Lazy::new(Default::default);
+
+/// File metadata which is expected to change whenever the file content changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+    size: u64,
+    mtime_secs: u64,
+    mtime_nanos: u32,
+    inode: u64,
+}
+
+impl FileStamp {
+    fn new(metadata: &Metadata) -> Result<Self> {
+        let mtime = metadata.modified()?.duration_since(UNIX_EPOCH)?;
+        Ok(Self {
+            size: metadata.len(),
+            mtime_secs: mtime.as_secs(),
+            mtime_nanos: mtime.subsec_nanos(),
+            inode: inode(metadata),
+        })
+    }
+
+    fn is_recent(&self) -> bool {
+        let mtime = UNIX_EPOCH + Duration::new(self.mtime_secs, self.mtime_nanos);
+        SystemTime::now()
+            .duration_since(mtime)
+            .map_or(true, |elapsed| elapsed < RACY_MODIFICATION_DELAY)
+    }
+}
+
+#[cfg(unix)]
+fn inode(metadata: &Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::ino(metadata)
+}
+
+#[cfg(not(unix))]
+fn inode(_: &Metadata) -> u64 {
+    0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFileHash {
+    stamp: FileStamp,
+    hash: u64,
+}
+
+/// Checksums of files, which are reused as long as file metadata does not change.
+/// Each workspace has its own cache, which is shared by all of its target executions within the process.
+#[derive(Default)]
+pub struct FileHashCache {
+    hashes: RwLock<HashMap<PathBuf, CachedFileHash>>,
+    updated: AtomicBool,
+}
+
+impl FileHashCache {
+    /// Get the cache of the workspace located at `root`.
+    pub fn for_workspace(root: &Path) -> Result<Arc<Self>> {
+        Ok(FILE_HASH_CACHES
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on file hash caches)."))?
+            .entry(root.to_owned())
+            .or_default()
+            .clone())
+    }
+
+    /// Get the checksum of a file, which is only computed if the file is unknown or if its metadata has changed.
+    pub fn hash_file(&self, path: &Path) -> Result<u64> {
+        let stamp = FileStamp::new(
+            &std::fs::metadata(path)
+                .with_context(|| format!("could not get metadata of {}", path.display()))?,
+        )?;
+
+        if let Some(cached) = self
+            .hashes
+            .read()
+            .map_err(|_| anyhow!("poison error (RwLock on file hash cache)."))?
+            .get(path)
+        {
+            if cached.stamp == stamp {
+                return Ok(cached.hash);
+            }
+        }
+
+        let hash = hash_file(path)?;
+
+        if !stamp.is_recent() {
+            let _ = self
+                .hashes
+                .write()
+                .map_err(|_| anyhow!("poison error (RwLock on file hash cache)."))?
+                .insert(path.to_owned(), CachedFileHash { stamp, hash });
+            self.updated.store(true, Ordering::Relaxed);
+        }
+
+        Ok(hash)
+    }
+
+    /// Add checksums computed by a previous process. Checksums which are already known are kept.
+    pub fn extend(&self, hashes: HashMap<PathBuf, CachedFileHash>) -> Result<()> {
+        let mut known = self
+            .hashes
+            .write()
+            .map_err(|_| anyhow!("poison error (RwLock on file hash cache)."))?;
+        for (path, hash) in hashes {
+            let _ = known.entry(path).or_insert(hash);
+        }
+        Ok(())
+    }
+
+    /// Get the checksums of existing files located under `root`, if some checksums were computed since the last call.
+    pub fn updated_hashes(&self, root: &Path) -> Result<Option<HashMap<PathBuf, CachedFileHash>>> {
+        if !self.updated.swap(false, Ordering::Relaxed) {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.hashes
+                .read()
+                .map_err(|_| anyhow!("poison error (RwLock on file hash cache)."))?
+                .iter()
+                .filter(|(path, _)| path.starts_with(root) && path.exists())
+                .map(|(path, hash)| (path.clone(), hash.clone()))
+                .collect(),
+        ))
+    }
+}
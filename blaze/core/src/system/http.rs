@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    error::Result,
+    executor::{HttpAuthentication, HttpDigestAuthentication, HttpTransport},
+};
+use md5::Md5;
+use reqwest::{
+    blocking::{Client, RequestBuilder, Response},
+    header::{AUTHORIZATION, WWW_AUTHENTICATE},
+    Method, StatusCode,
+};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use super::random::random_string;
+
+pub const USER_AGENT: &str = concat!("blaze/", env!("CARGO_PKG_VERSION"));
+
+/// An HTTP client that applies transport options and authentication to every request.
+pub struct HttpSession {
+    client: Client,
+    transport: HttpTransport,
+    authentication: Option<HttpAuthentication>,
+}
+
+impl HttpSession {
+    pub fn try_new(
+        transport: HttpTransport,
+        authentication: Option<HttpAuthentication>,
+    ) -> Result<Self> {
+        Ok(Self {
+            client: Client::builder()
+                .user_agent(USER_AGENT)
+                .danger_accept_invalid_certs(transport.insecure())
+                .build()
+                .context("could not create HTTP client")?,
+            transport,
+            authentication,
+        })
+    }
+
+    /// Send a request. The `customize` function can be called more than once, since digest authentication requires an extra round-trip.
+    pub fn send<F>(&self, method: Method, url: &Url, customize: F) -> Result<Response>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let response = customize(self.request(method.clone(), url))
+            .send()
+            .with_context(|| format!("{method} request to {url} failed"))?;
+
+        match &self.authentication {
+            Some(HttpAuthentication::Digest(digest))
+                if response.status() == StatusCode::UNAUTHORIZED =>
+            {
+                let authorization = digest_authorization(digest, &response, &method, url)?;
+                customize(self.request(method.clone(), url))
+                    .header(AUTHORIZATION, authorization)
+                    .send()
+                    .with_context(|| format!("{method} request to {url} failed"))
+            }
+            _ => Ok(response),
+        }
+    }
+
+    fn request(&self, method: Method, url: &Url) -> RequestBuilder {
+        let mut request = self.client.request(method, url.as_str());
+
+        for (name, value) in self.transport.headers() {
+            request = request.header(name, value);
+        }
+
+        match &self.authentication {
+            Some(HttpAuthentication::Basic(basic)) => {
+                request.basic_auth(basic.username(), Some(basic.password()))
+            }
+            Some(HttpAuthentication::Bearer(bearer)) => request.bearer_auth(bearer.token()),
+            Some(HttpAuthentication::Digest(_)) | None => request,
+        }
+    }
+}
+
+/// Build the `Authorization` header value from a digest challenge (RFC 7616).
+fn digest_authorization(
+    authentication: &HttpDigestAuthentication,
+    response: &Response,
+    method: &Method,
+    url: &Url,
+) -> Result<String> {
+    let challenge = response
+        .headers()
+        .get_all(WWW_AUTHENTICATE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .find_map(parse_digest_challenge)
+        .ok_or_else(|| anyhow!("{url} did not send any digest authentication challenge"))?;
+
+    let param = |name: &str| {
+        challenge
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| anyhow!("digest challenge from {url} is missing \"{name}\""))
+    };
+
+    let realm = param("realm")?;
+    let nonce = param("nonce")?;
+    let algorithm = challenge
+        .get("algorithm")
+        .map(String::as_str)
+        .unwrap_or("MD5");
+
+    let (hash, session): (fn(&str) -> String, bool) = match algorithm.to_uppercase().as_str() {
+        "MD5" => (md5_hex, false),
+        "MD5-SESS" => (md5_hex, true),
+        "SHA-256" => (sha256_hex, false),
+        "SHA-256-SESS" => (sha256_hex, true),
+        unsupported => bail!("unsupported digest algorithm \"{unsupported}\" for {url}"),
+    };
+
+    let qop = match challenge.get("qop") {
+        Some(qop) => {
+            if !qop.split(',').any(|value| value.trim() == "auth") {
+                bail!("unsupported digest quality of protection \"{qop}\" for {url}");
+            }
+            Some("auth")
+        }
+        None => None,
+    };
+
+    let uri = match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_owned(),
+    };
+    let cnonce = random_string(16);
+    let nc = "00000001";
+
+    let mut ha1 = hash(&format!(
+        "{}:{realm}:{}",
+        authentication.username(),
+        authentication.password()
+    ));
+    if session {
+        ha1 = hash(&format!("{ha1}:{nonce}:{cnonce}"));
+    }
+    let ha2 = hash(&format!("{method}:{uri}"));
+
+    let digest = match qop {
+        Some(qop) => hash(&format!("{ha1}:{nonce}:{nc}:{cnonce}:{qop}:{ha2}")),
+        None => hash(&format!("{ha1}:{nonce}:{ha2}")),
+    };
+
+    let mut authorization = format!(
+        "Digest username=\"{}\", realm=\"{realm}\", nonce=\"{nonce}\", uri=\"{uri}\", algorithm={algorithm}, response=\"{digest}\"",
+        authentication.username()
+    );
+
+    if let Some(qop) = qop {
+        authorization.push_str(&format!(", qop={qop}, nc={nc}, cnonce=\"{cnonce}\""));
+    }
+
+    if let Some(opaque) = challenge.get("opaque") {
+        authorization.push_str(&format!(", opaque=\"{opaque}\""));
+    }
+
+    Ok(authorization)
+}
+
+/// Parse the parameters of a `WWW-Authenticate: Digest ...` header value.
+fn parse_digest_challenge(header: &str) -> Option<HashMap<String, String>> {
+    let (scheme, params) = header.trim().split_once(' ')?;
+
+    if !scheme.eq_ignore_ascii_case("digest") {
+        return None;
+    }
+
+    let mut challenge = HashMap::new();
+    let mut chars = params.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| *c == ',' || c.is_whitespace()).is_some() {}
+
+        let name = std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect::<String>();
+        if name.is_empty() {
+            break;
+        }
+        chars.next();
+
+        let value = if chars.next_if_eq(&'"').is_some() {
+            let mut value = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => value.extend(chars.next()),
+                    c => value.push(c),
+                }
+            }
+            value
+        } else {
+            std::iter::from_fn(|| chars.next_if(|c| *c != ','))
+                .collect::<String>()
+                .trim()
+                .to_owned()
+        };
+
+        challenge.insert(name.trim().to_lowercase(), value);
+    }
+
+    Some(challenge)
+}
+
+fn md5_hex(input: &str) -> String {
+    format!("{:x}", Md5::digest(input.as_bytes()))
+}
+
+fn sha256_hex(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
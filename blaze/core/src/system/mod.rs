@@ -1,7 +1,10 @@
+pub mod archive;
 pub mod env;
 pub mod file_changes;
 pub mod glob;
 pub mod hash;
+pub mod hash_cache;
+pub mod http;
 pub mod ipc_server;
 pub mod locks;
 pub mod parallel_executor;
@@ -11,3 +14,4 @@ pub mod repository;
 pub mod shell;
 pub mod thread;
 pub mod time;
+pub mod watch;
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     num::NonZeroUsize,
     panic::UnwindSafe,
     sync::mpsc::{channel, Receiver, Sender},
@@ -12,6 +12,9 @@ use super::thread::{join, thread};
 
 const DEFAULT_THREADS_CAPACITY: usize = 16;
 
+/// Amounts of resources required by a job, indexed by resource pool name.
+pub type Resources = BTreeMap<String, NonZeroUsize>;
+
 /// Used to execute functions in parallel in a thread pool and drain results asynchronously.
 pub struct ParallelRunner<'scope, 'env: 'scope, T>
 where
@@ -23,6 +26,8 @@ where
     thread_id_sequence: usize,
     termination_send: Sender<usize>,
     termination_recv: Receiver<usize>,
+    available_resources: HashMap<String, usize>,
+    acquired_resources: HashMap<usize, Resources>,
 }
 
 impl<'scope, 'env: 'scope, T> ParallelRunner<'scope, 'env, T>
@@ -48,9 +53,24 @@ where
             thread_id_sequence: usize::MIN,
             termination_recv,
             termination_send,
+            available_resources: HashMap::new(),
+            acquired_resources: HashMap::new(),
         })
     }
 
+    /// Limit concurrent jobs using named resource pools, each with a maximum capacity.
+    /// Jobs requiring resources from pools that were not declared will never be started.
+    pub fn with_resource_pools<'a, I: IntoIterator<Item = (&'a String, &'a NonZeroUsize)>>(
+        mut self,
+        pools: I,
+    ) -> Self {
+        self.available_resources = pools
+            .into_iter()
+            .map(|(name, capacity)| (name.to_owned(), capacity.get()))
+            .collect();
+        self
+    }
+
     /// Push jobs to the executions threads pool for each available slots in the thread pool.
     /// Available slots are calculated according to the configured parallelism level.
     /// The execution supplier closure will be called for each slot available in the thread pool, or until [`None`] is returned by the closure, meaning that there are no more jobs pending.
@@ -58,16 +78,41 @@ where
     where
         E: FnOnce() -> T + Send + UnwindSafe + 'scope,
         S: FnMut() -> Option<E>,
+    {
+        self.push_available_with_resources(|_| {
+            execution_supplier().map(|execution| (execution, Resources::new()))
+        })
+    }
+
+    /// Same as [`Self::push_available`], but jobs may require resources from the runner pools.
+    /// The execution supplier closure receives a function telling if some resources are currently available, and must only return jobs whose resources are available.
+    /// Resources are acquired when the job is started, and released when its result is drained.
+    pub fn push_available_with_resources<E, S>(&mut self, mut execution_supplier: S)
+    where
+        E: FnOnce() -> T + Send + UnwindSafe + 'scope,
+        S: FnMut(&dyn Fn(&Resources) -> bool) -> Option<(E, Resources)>,
     {
         let available_slots = self
             .max
             .map(|m| m.get() - self.threads.len())
             .unwrap_or(usize::MAX);
         for _ in 0..available_slots {
-            match execution_supplier() {
-                Some(execution_routine) => {
+            let available_resources = &self.available_resources;
+            let is_available = |resources: &Resources| {
+                resources.iter().all(|(name, amount)| {
+                    available_resources
+                        .get(name)
+                        .is_some_and(|available| *available >= amount.get())
+                })
+            };
+            match execution_supplier(&is_available) {
+                Some((execution_routine, resources)) => {
                     let thread_id = self.thread_id_sequence;
                     let termination_send_clone = self.termination_send.clone();
+                    for (name, amount) in &resources {
+                        *self.available_resources.get_mut(name).unwrap() -= amount.get();
+                    }
+                    self.acquired_resources.insert(thread_id, resources);
                     self.threads.insert(
                         self.thread_id_sequence,
                         thread!(self.scope, move || {
@@ -91,7 +136,12 @@ where
     pub fn drain(&mut self) -> Result<Vec<T>> {
         let mut drained = Vec::with_capacity(self.threads.len());
 
-        let mut join = |thread_id| join!(self.threads.remove(&thread_id).unwrap());
+        let mut join = |thread_id| {
+            for (name, amount) in self.acquired_resources.remove(&thread_id).unwrap() {
+                *self.available_resources.get_mut(&name).unwrap() += amount.get();
+            }
+            join!(self.threads.remove(&thread_id).unwrap())
+        };
 
         drained.push(join(self.termination_recv.recv()?)?);
         drained.extend(
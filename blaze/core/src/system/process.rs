@@ -2,15 +2,17 @@ use std::{
     collections::HashMap,
     fmt::Display,
     io::{Read, Write},
+    panic::{RefUnwindSafe, UnwindSafe},
     path::{Path, PathBuf},
     process::{Command, ExitStatus, Stdio},
-    sync::{RwLock, RwLockWriteGuard},
+    sync::{Arc, Mutex, MutexGuard, RwLock, RwLockWriteGuard, Weak},
     thread::JoinHandle,
 };
 
 use anyhow::{anyhow, Context};
 
 use blaze_common::error::Result;
+use serde::{Deserialize, Serialize};
 use shared_child::SharedChild;
 
 use super::thread::{join, thread};
@@ -20,7 +22,7 @@ type ReadThreadHandles = [JoinHandle<Result<()>>; 2];
 
 /// A child process
 pub struct Process {
-    child: SharedChild,
+    child: Arc<SharedChild>,
     read_thread_handles: RwLock<Option<ReadThreadHandles>>,
 }
 
@@ -30,6 +32,26 @@ pub struct ProcessStatus {
     pub success: bool,
 }
 
+/// Error returned when a process has terminated unsuccessfully, keeping track of its exit code.
+#[derive(Debug)]
+pub struct ProcessFailure {
+    pub message: String,
+    pub code: Option<i32>,
+}
+
+impl Display for ProcessFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ProcessFailure {}
+
+/// Get the process failure that caused the error, if any.
+pub fn process_failure(err: &anyhow::Error) -> Option<&ProcessFailure> {
+    err.downcast_ref::<ProcessFailure>()
+}
+
 impl From<ExitStatus> for ProcessStatus {
     fn from(exit_status: ExitStatus) -> Self {
         ProcessStatus {
@@ -54,6 +76,113 @@ pub struct ProcessOptions {
     pub display_output: bool,
     /// Environment variables for the process.
     pub environment: HashMap<String, String>,
+    /// A group of processes to which the process will be added.
+    pub group: Option<ProcessGroup>,
+}
+
+/// A standard stream of a process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Destination for the output of processes, instead of the standard output and error streams.
+pub type OutputSink = Arc<dyn Fn(OutputStream, &[u8]) + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
+/// A set of processes that can be killed all at once, for e.g when an execution times out.
+#[derive(Default, Clone)]
+pub struct ProcessGroup {
+    state: Arc<Mutex<ProcessGroupState>>,
+    output: Option<OutputSink>,
+    input: Option<Arc<[u8]>>,
+}
+
+#[derive(Default)]
+struct ProcessGroupState {
+    killed: bool,
+    children: Vec<Weak<SharedChild>>,
+}
+
+impl ProcessGroup {
+    /// Create a group whose processes output is redirected to the provided sink (when displayed).
+    pub fn with_output(output: OutputSink) -> Self {
+        Self {
+            output: Some(output),
+            ..Default::default()
+        }
+    }
+
+    /// Provide some data to be written to the standard input of processes in this group (see [`Process::write_group_input`]).
+    pub fn with_input(mut self, input: Arc<[u8]>) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    /// Kill all processes in this group, along with their descendants.
+    /// Processes that are added to the group afterwards are killed as soon as they are spawned.
+    pub fn kill_all(&self) -> Result<()> {
+        let mut state = self.lock()?;
+        state.killed = true;
+        for child in state.children.drain(..).filter_map(|child| child.upgrade()) {
+            kill_tree(&child)?;
+        }
+        Ok(())
+    }
+
+    /// Write some output on behalf of a process of this group.
+    pub fn write_output(&self, stream: OutputStream, bytes: &[u8]) -> Result<()> {
+        output_stream(Some(self), stream).write_all(bytes)?;
+        Ok(())
+    }
+
+    /// Returns true if [`ProcessGroup::kill_all`] was called.
+    pub fn is_killed(&self) -> Result<bool> {
+        Ok(self.lock()?.killed)
+    }
+
+    fn add(&self, child: &Arc<SharedChild>) -> Result<()> {
+        let mut state = self.lock()?;
+        if state.killed {
+            return kill_tree(child);
+        }
+        state.children.retain(|child| child.strong_count() > 0);
+        state.children.push(Arc::downgrade(child));
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, ProcessGroupState>> {
+        self.state
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on process group)."))
+    }
+}
+
+/// Writes a process stream to an [`OutputSink`].
+struct SinkWriter(OutputSink, OutputStream);
+
+impl Write for SinkWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        (self.0)(self.1, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Get the stream where a process output must be written, according to its group.
+fn output_stream(group: Option<&ProcessGroup>, stream: OutputStream) -> Box<dyn Write + Send> {
+    match (
+        group.as_ref().and_then(|group| group.output.clone()),
+        stream,
+    ) {
+        (Some(output), _) => Box::new(SinkWriter(output, stream)),
+        (None, OutputStream::Stdout) => Box::new(std::io::stdout()),
+        (None, OutputStream::Stderr) => Box::new(std::io::stderr()),
+    }
 }
 
 impl Process {
@@ -86,7 +215,11 @@ impl Process {
         builder.stdout(Stdio::piped());
         builder.stderr(Stdio::piped());
 
-        let child = SharedChild::spawn(&mut builder)?;
+        let child = Arc::new(SharedChild::spawn(&mut builder)?);
+
+        if let Some(group) = &options.group {
+            group.add(&child)?;
+        }
 
         let process = Process {
             child,
@@ -127,17 +260,15 @@ impl Process {
                     process
                         .child
                         .take_stdout()
-                        .take()
                         .ok_or_else(|| anyhow!("could not take stdout for {process}."))?,
-                    std::io::stdout(),
+                    output_stream(options.group.as_ref(), OutputStream::Stdout),
                 ),
                 pipe(
                     process
                         .child
                         .take_stderr()
-                        .take()
                         .ok_or_else(|| anyhow!("could not take stderr for {process}."))?,
-                    std::io::stderr(),
+                    output_stream(options.group.as_ref(), OutputStream::Stderr),
                 ),
             ])
         }
@@ -154,6 +285,23 @@ impl Process {
             .with_context(|| format!("failed to write to stdin for {self}."))
     }
 
+    /// Write the input of the process group (if any) to standard input and close.
+    /// Data is written in the background and ignored if the process does not read it.
+    pub fn write_group_input(&mut self, group: &ProcessGroup) -> Result<()> {
+        let input = match &group.input {
+            Some(input) => input.clone(),
+            None => return Ok(()),
+        };
+        let mut stdin = self
+            .child
+            .take_stdin()
+            .ok_or_else(|| anyhow!("stdin already closed"))?;
+        let _ = thread!(move || {
+            let _ = stdin.write_all(&input);
+        });
+        Ok(())
+    }
+
     /// Get stdout from child process
     pub fn stdout(&self) -> Result<impl Read> {
         self.child
@@ -179,10 +327,9 @@ impl Process {
         Ok(status)
     }
 
-    /// Force termination of the process.
+    /// Force termination of the process, along with all of its descendants.
     pub fn kill(&self) -> Result<()> {
-        self.child.kill()?;
-        Ok(())
+        kill_tree(&self.child)
     }
 
     /// Get the process id.
@@ -190,9 +337,80 @@ impl Process {
         self.child.id()
     }
 
-    fn thread_handle_write(&self) -> Result<RwLockWriteGuard<Option<ReadThreadHandles>>> {
+    fn thread_handle_write(&self) -> Result<RwLockWriteGuard<'_, Option<ReadThreadHandles>>> {
         self.read_thread_handles
             .write()
             .map_err(|_| anyhow!("poison error (RwLock on process read thread)."))
     }
 }
+
+#[cfg(unix)]
+fn kill_tree(child: &SharedChild) -> Result<()> {
+    // descendants are collected first, since they would be re-parented once their parent is killed.
+    // this is done on a best effort basis, if they cannot be listed or killed then only the process itself is killed.
+    let descendants = list_descendants(child.id()).unwrap_or_default();
+
+    child.kill()?;
+
+    if !descendants.is_empty() {
+        // some descendants might have already exited, so both spawn errors and the command status are ignored.
+        let _ = Command::new("kill")
+            .arg("-KILL")
+            .args(descendants.iter().map(u32::to_string))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn list_descendants(pid: u32) -> Result<Vec<u32>> {
+    let mut descendants = vec![];
+    let mut parents = vec![pid];
+
+    while let Some(parent) = parents.pop() {
+        let output = Command::new("pgrep")
+            .arg("-P")
+            .arg(parent.to_string())
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .with_context(|| format!("could not list child processes of {parent}"))?;
+
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let child = line
+                .trim()
+                .parse::<u32>()
+                .with_context(|| format!("invalid process id {line}"))?;
+            descendants.push(child);
+            parents.push(child);
+        }
+    }
+
+    Ok(descendants)
+}
+
+#[cfg(windows)]
+fn kill_tree(child: &SharedChild) -> Result<()> {
+    let killed_tree = Command::new("taskkill")
+        .args(["/T", "/F", "/PID"])
+        .arg(child.id().to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+
+    if !killed_tree {
+        child.kill()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn kill_tree(child: &SharedChild) -> Result<()> {
+    // descendant processes cannot be listed on this platform.
+    Ok(child.kill()?)
+}
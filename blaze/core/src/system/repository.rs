@@ -1,7 +1,11 @@
 use anyhow::Context;
 use blaze_common::error::Result;
+use std::collections::{HashMap, HashSet};
 use std::io::{Seek, Write};
-use std::{fs::OpenOptions, path::Path};
+use std::{
+    fs::OpenOptions,
+    path::{Path, PathBuf},
+};
 
 const GITIGNORE_FILE: &str = ".gitignore";
 
@@ -13,6 +17,131 @@ pub fn open_or_init_repository(root: &Path) -> Result<git2::Repository> {
     })
 }
 
+/// Get the absolute paths of all files that changed since a base revision, including uncommitted and untracked files.
+/// When possible, the comparison is made against the merge base of the base revision and `HEAD`.
+pub fn changed_files(root: &Path, base: &str) -> Result<HashSet<PathBuf>> {
+    let repository = git2::Repository::discover(root)
+        .with_context(|| format!("could not find a git repository at {}", root.display()))?;
+
+    let workdir = repository
+        .workdir()
+        .map(dunce::canonicalize)
+        .transpose()?
+        .with_context(|| {
+            format!(
+                "git repository at {} has no working directory",
+                root.display()
+            )
+        })?;
+
+    let base_commit = repository
+        .revparse_single(base)
+        .and_then(|object| object.peel_to_commit())
+        .with_context(|| format!("could not resolve git revision \"{base}\""))?;
+
+    let compared_commit = match repository.head().and_then(|head| head.peel_to_commit()) {
+        Ok(head) => match repository.merge_base(base_commit.id(), head.id()) {
+            Ok(merge_base) => repository.find_commit(merge_base)?,
+            Err(_) => base_commit,
+        },
+        Err(_) => base_commit,
+    };
+
+    let mut diff_options = git2::DiffOptions::new();
+    diff_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true);
+
+    let diff = repository
+        .diff_tree_to_workdir_with_index(Some(&compared_commit.tree()?), Some(&mut diff_options))
+        .with_context(|| format!("could not compute git diff against \"{base}\""))?;
+
+    Ok(diff
+        .deltas()
+        .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+        .flatten()
+        .map(|path| workdir.join(path))
+        .collect())
+}
+
+/// Content identifiers of the files of a git repository working directory.
+/// Identifiers of clean tracked files are read from the repository index, so their content is not read.
+pub struct GitFileIds {
+    workdir: PathBuf,
+    index: HashMap<PathBuf, git2::Oid>,
+    dirty: HashSet<PathBuf>,
+}
+
+impl GitFileIds {
+    /// Load identifiers from the repository containing `path`.
+    /// Returns [`None`] if `path` does not belong to a git repository working directory.
+    pub fn discover(path: &Path) -> Result<Option<Self>> {
+        let repository = match git2::Repository::discover(path) {
+            Ok(repository) => repository,
+            Err(err) if err.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let workdir = match repository.workdir() {
+            Some(workdir) => dunce::canonicalize(workdir)?,
+            None => return Ok(None),
+        };
+
+        let index = repository
+            .index()
+            .context("could not read git index")?
+            .iter()
+            .filter_map(|entry| {
+                std::str::from_utf8(&entry.path)
+                    .ok()
+                    .map(|path| (workdir.join(path), entry.id))
+            })
+            .collect();
+
+        let mut status_options = git2::StatusOptions::new();
+        status_options
+            .include_untracked(false)
+            .include_ignored(false)
+            .exclude_submodules(true);
+
+        let dirty_status = git2::Status::WT_MODIFIED
+            | git2::Status::WT_DELETED
+            | git2::Status::WT_TYPECHANGE
+            | git2::Status::WT_RENAMED
+            | git2::Status::CONFLICTED;
+
+        let dirty = repository
+            .statuses(Some(&mut status_options))
+            .context("could not get git status")?
+            .iter()
+            .filter(|entry| entry.status().intersects(dirty_status))
+            .filter_map(|entry| entry.path().map(|path| workdir.join(path)))
+            .collect();
+
+        Ok(Some(Self {
+            workdir,
+            index,
+            dirty,
+        }))
+    }
+
+    /// Get the git object identifier of a file content.
+    /// Files which are untracked or modified are hashed the same way git does, so that identifiers stay stable once they are committed.
+    /// Returns [`None`] if the file is outside of the repository working directory.
+    pub fn get(&self, path: &Path) -> Result<Option<String>> {
+        if !path.starts_with(&self.workdir) {
+            return Ok(None);
+        }
+        let id = match self.index.get(path) {
+            Some(id) if !self.dirty.contains(path) => *id,
+            _ => git2::Oid::hash_file(git2::ObjectType::Blob, path).with_context(|| {
+                format!("could not compute git object id of {}", path.display())
+            })?,
+        };
+        Ok(Some(id.to_string()))
+    }
+}
+
 pub fn add_to_gitignore<S>(root: &Path, rules: &[S]) -> Result<()>
 where
     S: AsRef<str>,
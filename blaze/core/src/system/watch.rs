@@ -0,0 +1,131 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+use blaze_common::{cache::FileChangesMatcher, error::Result};
+
+use super::glob::glob;
+
+#[derive(PartialEq, Eq)]
+struct FileStamp {
+    mtime: Option<SystemTime>,
+    len: u64,
+}
+
+type Snapshot = HashMap<PathBuf, FileStamp>;
+
+/// Detects file changes by polling the file system.
+/// Each key is associated with a set of matchers, and files are compared using their size and last modification time.
+pub struct PollingWatcher<K> {
+    watched: Vec<(K, PathBuf, FileChangesMatcher)>,
+    snapshots: HashMap<K, Snapshot>,
+}
+
+impl<K: Clone + Eq + Hash> PollingWatcher<K> {
+    /// Create a watcher from matchers associated with their keys and default root directories.
+    pub fn try_new<I: IntoIterator<Item = (K, PathBuf, FileChangesMatcher)>>(
+        watched: I,
+    ) -> Result<Self> {
+        let mut watcher = Self {
+            watched: watched.into_iter().collect(),
+            snapshots: HashMap::new(),
+        };
+        watcher.snapshots = watcher.snapshot()?;
+        Ok(watcher)
+    }
+
+    /// Returns true if nothing needs to be watched.
+    pub fn is_empty(&self) -> bool {
+        self.watched.is_empty()
+    }
+
+    /// Take a new snapshot of the watched files and return the keys for which files have changed since the last call.
+    pub fn refresh(&mut self) -> Result<HashSet<K>> {
+        self.refresh_ignoring(&HashSet::new())
+    }
+
+    /// Same as [`Self::refresh`], except that changes of the `ignored` files are not reported.
+    pub fn refresh_ignoring(&mut self, ignored: &HashSet<PathBuf>) -> Result<HashSet<K>> {
+        let snapshots = self.snapshot()?;
+        let empty = Snapshot::new();
+
+        let changed = snapshots
+            .iter()
+            .filter(|(key, snapshot)| {
+                let previous = self.snapshots.get(*key).unwrap_or(&empty);
+                previous
+                    .keys()
+                    .chain(snapshot.keys())
+                    .filter(|path| !ignored.contains(*path))
+                    .any(|path| previous.get(path) != snapshot.get(path))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        self.snapshots = snapshots;
+
+        Ok(changed)
+    }
+
+    /// Block until some files change, then keep collecting changes until none happen during the `debounce` duration.
+    pub fn wait_for_changes(
+        &mut self,
+        poll_interval: Duration,
+        debounce: Duration,
+    ) -> Result<HashSet<K>> {
+        let mut changed = loop {
+            std::thread::sleep(poll_interval);
+            let changed = self.refresh()?;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+
+        loop {
+            std::thread::sleep(debounce);
+            let next_changes = self.refresh()?;
+            if next_changes.is_empty() {
+                return Ok(changed);
+            }
+            changed.extend(next_changes);
+        }
+    }
+
+    fn snapshot(&self) -> Result<HashMap<K, Snapshot>> {
+        let mut snapshots = HashMap::<K, Snapshot>::with_capacity(self.watched.len());
+
+        for (key, default_root, matcher) in &self.watched {
+            let paths = glob(
+                matcher.root().unwrap_or(default_root),
+                matcher.pattern(),
+                matcher.exclude().iter().map(String::as_str),
+            )
+            .context("failure while walking across watched files")?;
+
+            let snapshot = snapshots.entry(key.clone()).or_default();
+
+            for path in paths {
+                let stamp = match std::fs::metadata(&path) {
+                    Ok(metadata) => FileStamp {
+                        mtime: metadata.modified().ok(),
+                        len: metadata.len(),
+                    },
+                    // the file might have been removed while walking
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(err) => {
+                        return Err(err).with_context(|| {
+                            format!("could not read metadata of {}", path.display())
+                        })
+                    }
+                };
+                snapshot.insert(path, stamp);
+            }
+        }
+
+        Ok(snapshots)
+    }
+}
@@ -0,0 +1,163 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::{anyhow, bail};
+use blaze_common::error::Result;
+
+use crate::{
+    executions::{check::ExecutionCacheState, execution::TargetExecution},
+    system::time::now,
+    workspace::cache_store::{CacheEntry, CacheStore},
+    GlobalOptions, WorkspaceGlobals,
+};
+
+/// An entry of the workspace cache, along with the target execution state it holds, if any.
+pub struct CacheEntryDetails {
+    entry: CacheEntry,
+    double: Option<String>,
+    fingerprint: Option<String>,
+    state: Option<ExecutionCacheState>,
+}
+
+impl CacheEntryDetails {
+    pub fn entry(&self) -> &CacheEntry {
+        &self.entry
+    }
+
+    /// The target this entry is the execution state of.
+    pub fn double(&self) -> Option<&str> {
+        self.double.as_deref()
+    }
+
+    /// The fingerprint of the execution state, when the target uses the fingerprint mode.
+    pub fn fingerprint(&self) -> Option<&str> {
+        self.fingerprint.as_deref()
+    }
+
+    /// The execution state, for entries which belong to a target.
+    pub fn state(&self) -> Option<&ExecutionCacheState> {
+        self.state.as_ref()
+    }
+
+    fn load(cache: &CacheStore, entry: CacheEntry) -> Result<Self> {
+        let (double, fingerprint) = match TargetExecution::parse_cache_key(entry.key()) {
+            Some((double, fingerprint)) => {
+                (Some(double.to_owned()), fingerprint.map(str::to_owned))
+            }
+            None => (None, None),
+        };
+        let state = match double {
+            Some(_) => cache.inspect::<ExecutionCacheState>(entry.key())?,
+            None => None,
+        };
+        Ok(Self {
+            entry,
+            double,
+            fingerprint,
+            state,
+        })
+    }
+}
+
+/// List all entries of the workspace cache, sorted by key.
+pub fn list_cache_entries(
+    root: &Path,
+    global_options: GlobalOptions,
+) -> Result<Vec<CacheEntryDetails>> {
+    let globals = WorkspaceGlobals::new(root, global_options)?;
+    let cache = get_cache(&globals)?;
+    cache
+        .entries()?
+        .into_iter()
+        .map(|entry| CacheEntryDetails::load(cache, entry))
+        .collect()
+}
+
+/// Get the execution state entries of a target (`<project>:<target>`).
+/// Targets using the fingerprint mode might have several entries.
+pub fn show_cache_entries<D: AsRef<str>>(
+    root: &Path,
+    double: D,
+    global_options: GlobalOptions,
+) -> Result<Vec<CacheEntryDetails>> {
+    let double = double.as_ref();
+    if double.split_once(':').is_none() {
+        bail!("invalid target \"{double}\", expected <project>:<target>");
+    }
+
+    let entries = list_cache_entries(root, global_options)?
+        .into_iter()
+        .filter(|details| details.double() == Some(double))
+        .collect::<Vec<_>>();
+
+    if entries.is_empty() {
+        bail!("no cache entry was found for {double}");
+    }
+
+    Ok(entries)
+}
+
+#[derive(Default)]
+pub struct GcCacheOptions {
+    older_than: Option<Duration>,
+    max_size: Option<u64>,
+}
+
+impl GcCacheOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove entries which were not used for the provided duration.
+    pub fn with_older_than(mut self, duration: Duration) -> Self {
+        self.older_than = Some(duration);
+        self
+    }
+
+    /// Remove least recently used entries until the total size of the cache is below the provided amount of bytes.
+    pub fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+}
+
+/// Remove least recently used entries from the workspace cache. Returns the removed entries.
+pub fn gc_cache(
+    root: &Path,
+    options: GcCacheOptions,
+    global_options: GlobalOptions,
+) -> Result<Vec<CacheEntry>> {
+    let globals = WorkspaceGlobals::new(root, global_options)?;
+    let cache = get_cache(&globals)?;
+
+    let mut entries = cache.entries()?;
+    entries.sort_by_key(|entry| entry.last_used());
+
+    let current_time = now();
+    let mut size = entries.iter().map(CacheEntry::size).sum::<u64>();
+    let mut removed = vec![];
+
+    for entry in entries {
+        let is_expired = options.older_than.is_some_and(|older_than| {
+            current_time
+                .duration_since(entry.last_used())
+                .is_ok_and(|unused_for| unused_for > older_than)
+        });
+        let is_oversized = options.max_size.is_some_and(|max_size| size > max_size);
+
+        if !is_expired && !is_oversized {
+            continue;
+        }
+
+        cache.invalidate(entry.key())?;
+        size -= entry.size();
+        removed.push(entry);
+    }
+
+    cache.flush_index()?;
+
+    Ok(removed)
+}
+
+fn get_cache<'a>(globals: &'a WorkspaceGlobals) -> Result<&'a CacheStore> {
+    globals.cache().ok_or_else(|| anyhow!("cache is disabled"))
+}
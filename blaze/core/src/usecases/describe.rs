@@ -127,7 +127,10 @@ pub fn describe_project(
                             None => dependency.target().to_owned(),
                             Some(selector) => {
                                 Selection::from_source(SelectorSource::Provided(selector.clone()))
-                                    .select(SelectionContext { workspace })?
+                                    .select(SelectionContext {
+                                        workspace,
+                                        deserialization_context: globals.deserialization_context(),
+                                    })?
                                     .into_iter()
                                     .map(|(name, project_ref)| {
                                         ProjectHandle::from_root(
@@ -207,8 +210,10 @@ pub fn describe_workspace(
 
     let workspace = globals.workspace_handle().inner();
 
-    let projects =
-        Selection::from_source(options.selector_source).select(SelectionContext { workspace })?;
+    let projects = Selection::from_source(options.selector_source).select(SelectionContext {
+        workspace,
+        deserialization_context: globals.deserialization_context(),
+    })?;
 
     if options.summary {
         println!(
@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use blaze_common::{error::Result, parallelism::Parallelism, selector::ProjectSelector};
+
+use crate::{
+    executions::{
+        check::InvalidationReason,
+        execution::{CachedDependencyExecution, CachedExecutionContext, CachedExecutionState},
+        graph::{ExecutedGraph, ExecutionGraph, ExecutionGraphOptions},
+    },
+    global_init,
+    workspace::selection::{Selection, SelectorSource},
+    GlobalOptions, WorkspaceGlobals,
+};
+
+use super::run::resolve_graph_executors;
+
+pub struct ExplainCacheOptions {
+    double: String,
+}
+
+impl ExplainCacheOptions {
+    pub fn new<D: AsRef<str>>(double: D) -> Self {
+        Self {
+            double: double.as_ref().to_owned(),
+        }
+    }
+}
+
+/// What would happen to a target if it was run.
+#[derive(Debug)]
+pub enum CacheStatus {
+    /// The target has no cache, it would be executed.
+    NoCache,
+    /// The target execution would be restored from the cache.
+    Cached,
+    /// The target cache state would be invalidated for the provided reasons, and the target would be executed.
+    Invalidated(Vec<InvalidationReason>),
+}
+
+/// Evaluate the cache of a target (`<project>:<target>`) and of its dependencies, without executing them.
+/// Dependencies whose cache would be invalidated are considered executed again when evaluating the cache of their dependents.
+pub fn explain_cache<R: AsRef<Path>>(
+    root: R,
+    options: ExplainCacheOptions,
+    global_options: GlobalOptions,
+) -> Result<ExecutedGraph<CacheStatus>> {
+    let globals = WorkspaceGlobals::new(root.as_ref(), global_options)?;
+    global_init(&globals)?;
+
+    let workspace = globals.workspace_handle().inner();
+    let logger = globals.logger();
+
+    let (project, target) = match options.double.split_once(':') {
+        Some(double) => double,
+        None => bail!(
+            "invalid target \"{}\", expected <project>:<target>",
+            options.double
+        ),
+    };
+
+    let selection =
+        Selection::from_source(SelectorSource::Provided(ProjectSelector::array([project])));
+
+    let execution_graph = ExecutionGraph::try_new(
+        [(&selection, target)],
+        ExecutionGraphOptions {
+            workspace,
+            deserialization_context: globals.deserialization_context(),
+            max_depth: None,
+        },
+    )
+    .context("could not build execution graph")?;
+
+    let cache = match globals.cache() {
+        Some(cache) => cache,
+        None => bail!("cache is disabled"),
+    };
+
+    let executor_resolutions = resolve_graph_executors(&execution_graph, &globals)?;
+
+    let results = execution_graph.execute(Parallelism::None, |execution, child_executions| {
+        let explanation = execution
+            .explain_cache(
+                child_executions
+                    .iter()
+                    .map(|child| CachedDependencyExecution {
+                        double: child.execution.get_double(),
+                        execution: child.execution,
+                        state: child.result,
+                        source: child.dependency.as_ref(),
+                    })
+                    .collect::<Vec<_>>()
+                    .as_slice(),
+                execution
+                    .get_target()
+                    .executor()
+                    .and_then(|reference| executor_resolutions.get_for_reference(reference))
+                    .and_then(|resolution| resolution.resolution_cache()),
+                CachedExecutionContext {
+                    cache,
+                    logger: &logger,
+                    workspace,
+                    logs: None,
+                },
+            )
+            .with_context(|| format!("could not evaluate cache of target {execution}"))?;
+
+        Ok(match explanation {
+            None => CachedExecutionState::NoCache(CacheStatus::NoCache),
+            Some((reasons, Some(hash))) if reasons.is_empty() => CachedExecutionState::Cached(hash),
+            Some((reasons, _)) => CachedExecutionState::NoCache(CacheStatus::Invalidated(reasons)),
+        })
+    })?;
+
+    Ok(results.map_inner(|state| match state {
+        CachedExecutionState::Cached(_) => CacheStatus::Cached,
+        CachedExecutionState::New(_, status, _) | CachedExecutionState::NoCache(status) => status,
+    }))
+}
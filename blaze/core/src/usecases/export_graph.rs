@@ -0,0 +1,226 @@
+use std::{collections::HashMap, io::Write, path::Path};
+
+use blaze_common::{dependency::CachePropagation, error::Result, unit_enum_from_str};
+use serde::Serialize;
+use strum_macros::{Display, EnumIter};
+
+use crate::{
+    executions::graph::GraphNode, global_init, workspace::selection::SelectorSource,
+    WorkspaceGlobals,
+};
+
+use super::{run::build_execution_graph, GlobalOptions, RunOptions};
+
+/// Output format for an exported execution graph.
+#[derive(EnumIter, Display, Clone, Copy, Debug, Default)]
+pub enum GraphFormat {
+    #[default]
+    Dot,
+    Mermaid,
+    Json,
+}
+
+unit_enum_from_str!(GraphFormat);
+
+pub struct ExportGraphOptions {
+    run_options: RunOptions,
+    format: GraphFormat,
+}
+
+impl ExportGraphOptions {
+    pub fn new<T: AsRef<str>>(target: T) -> Self {
+        Self {
+            run_options: RunOptions::new(target),
+            format: GraphFormat::default(),
+        }
+    }
+
+    pub fn with_selector_source(mut self, source: SelectorSource) -> Self {
+        self.run_options = self.run_options.with_selector_source(source);
+        self
+    }
+
+    pub fn with_dependencies_depth(mut self, max: usize) -> Self {
+        self.run_options = self.run_options.with_dependencies_depth(max);
+        self
+    }
+
+    pub fn with_format(mut self, format: GraphFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// Build the execution graph of a target across a selection of projects, and write it to the output without executing anything.
+pub fn export_graph<R: AsRef<Path>, O: Write>(
+    root: R,
+    options: ExportGraphOptions,
+    mut output: O,
+    globals_options: GlobalOptions,
+) -> Result<()> {
+    let globals = WorkspaceGlobals::new(root.as_ref(), globals_options)?;
+    global_init(&globals)?;
+
+    let execution_graph = build_execution_graph(&globals, &options.run_options)?;
+    let nodes = execution_graph.nodes();
+
+    match options.format {
+        GraphFormat::Dot => write_dot(&mut output, &nodes)?,
+        GraphFormat::Mermaid => write_mermaid(&mut output, &nodes)?,
+        GraphFormat::Json => {
+            serde_json::to_writer_pretty(&mut output, &JsonGraph::new(&nodes))?;
+            writeln!(output)?;
+        }
+    }
+
+    output.flush()?;
+
+    Ok(())
+}
+
+/// Root targets are drawn in bold, and optional dependencies with dashed edges.
+/// Each edge is labeled with the dependency cache propagation.
+fn write_dot<O: Write>(output: &mut O, nodes: &[GraphNode]) -> Result<()> {
+    fn quote(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    writeln!(output, "digraph {{")?;
+    writeln!(output, "    node [shape=box];")?;
+
+    for node in nodes {
+        if node.root {
+            writeln!(output, "    {} [style=bold];", quote(node.double))?;
+        } else {
+            writeln!(output, "    {};", quote(node.double))?;
+        }
+    }
+
+    for node in nodes {
+        for (dependency_double, dependency) in &node.dependencies {
+            writeln!(
+                output,
+                "    {} -> {} [label={}, style={}];",
+                quote(node.double),
+                quote(dependency_double),
+                quote(&dependency.cache_propagation().to_string()),
+                if dependency.optional() {
+                    "dashed"
+                } else {
+                    "solid"
+                }
+            )?;
+        }
+    }
+
+    writeln!(output, "}}")?;
+
+    Ok(())
+}
+
+/// Same conventions as the DOT format, using a flowchart.
+fn write_mermaid<O: Write>(output: &mut O, nodes: &[GraphNode]) -> Result<()> {
+    fn quote(value: &str) -> String {
+        format!("\"{}\"", value.replace('"', "#quot;"))
+    }
+
+    // node identifiers are generated, since doubles may contain characters that are not supported by Mermaid.
+    let ids = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.double, format!("n{index}")))
+        .collect::<HashMap<_, _>>();
+
+    writeln!(output, "flowchart TD")?;
+
+    for node in nodes {
+        writeln!(output, "    {}[{}]", ids[node.double], quote(node.double))?;
+    }
+
+    for node in nodes {
+        for (dependency_double, dependency) in &node.dependencies {
+            let label = quote(&dependency.cache_propagation().to_string());
+            writeln!(
+                output,
+                "    {} {}|{label}| {}",
+                ids[node.double],
+                if dependency.optional() { "-.->" } else { "-->" },
+                ids[dependency_double]
+            )?;
+        }
+    }
+
+    let roots = nodes
+        .iter()
+        .filter(|node| node.root)
+        .map(|node| ids[node.double].as_str())
+        .collect::<Vec<_>>();
+
+    if !roots.is_empty() {
+        writeln!(output, "    classDef root font-weight:bold")?;
+        writeln!(output, "    class {} root", roots.join(","))?;
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonNode<'a> {
+    double: &'a str,
+    project: &'a str,
+    target: &'a str,
+    root: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    executor: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonEdge<'a> {
+    from: &'a str,
+    to: &'a str,
+    cache_propagation: CachePropagation,
+    optional: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonGraph<'a> {
+    nodes: Vec<JsonNode<'a>>,
+    edges: Vec<JsonEdge<'a>>,
+}
+
+impl<'a> JsonGraph<'a> {
+    fn new(nodes: &'a [GraphNode<'a>]) -> Self {
+        Self {
+            nodes: nodes
+                .iter()
+                .map(|node| JsonNode {
+                    double: node.double,
+                    project: node.execution.get_project_name(),
+                    target: node.execution.get_target_name(),
+                    root: node.root,
+                    executor: node
+                        .execution
+                        .get_target()
+                        .executor()
+                        .map(ToString::to_string),
+                })
+                .collect(),
+            edges: nodes
+                .iter()
+                .flat_map(|node| {
+                    node.dependencies
+                        .iter()
+                        .map(|(dependency_double, dependency)| JsonEdge {
+                            from: node.double,
+                            to: dependency_double,
+                            cache_propagation: dependency.cache_propagation(),
+                            optional: dependency.optional(),
+                        })
+                })
+                .collect(),
+        }
+    }
+}
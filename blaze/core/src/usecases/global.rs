@@ -74,6 +74,7 @@ pub struct WorkspaceGlobals<'a> {
     logger: Logger,
     log_level: LogLevel,
     cache: Option<CacheStore>,
+    local_cache: Option<CacheStore>,
     template_data: TemplateData<'a>,
     jpath: HashSet<PathBuf>,
 }
@@ -91,10 +92,24 @@ impl<'a> WorkspaceGlobals<'a> {
         self.log_level
     }
 
+    /// Cache used for target executions, which might be shared with other machines depending on workspace settings.
     pub fn cache(&self) -> Option<&CacheStore> {
         self.cache.as_ref()
     }
 
+    /// Cache that is always located in the workspace, for data that cannot be shared with other machines.
+    pub fn local_cache(&self) -> Option<&CacheStore> {
+        self.local_cache.as_ref()
+    }
+
+    /// Write pending index changes of both caches.
+    pub fn flush_caches(&self) -> Result<()> {
+        for cache in [&self.cache, &self.local_cache].into_iter().flatten() {
+            cache.flush_index()?;
+        }
+        Ok(())
+    }
+
     pub fn deserialization_context(&'a self) -> DeserializationContext<'a> {
         DeserializationContext {
             jpath: &self.jpath,
@@ -165,13 +180,22 @@ impl WorkspaceGlobals<'_> {
 
         template_data.extend_with_workspace(workspace_handle.inner())?;
 
-        let cache = (!options.no_cache)
+        let local_cache = (!options.no_cache)
             .then(|| CacheStore::load(&root))
             .transpose()
             .context("error while loading workspace cache")?;
 
+        let cache = (!options.no_cache)
+            .then(|| match workspace_handle.inner().settings().cache() {
+                Some(settings) => CacheStore::from_settings(&root, settings),
+                None => CacheStore::load(&root),
+            })
+            .transpose()
+            .context("error while loading cache backend")?;
+
         Ok(Self {
             cache,
+            local_cache,
             workspace_handle,
             log_level,
             template_data,
@@ -0,0 +1,141 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{error::Result, selector::ProjectSelector, settings::RunHook, value::to_value};
+
+use crate::{
+    executions::graph::{ExecutedGraph, ExecutionGraph, ExecutionGraphOptions},
+    executors::{get_workspace_env, run_commands},
+    logging::get_contextual_logger,
+    system::process::ProcessGroup,
+    workspace::selection::{Selection, SelectorSource},
+    WorkspaceGlobals,
+};
+
+use super::{
+    report::run_summary,
+    run::{execute_graph, resolve_graph_executors},
+    ExecutionDetails, RunOptions,
+};
+
+const BEFORE_RUN: &str = "beforeRun";
+const AFTER_RUN: &str = "afterRun";
+const ON_FAILURE: &str = "onFailure";
+
+/// Execute the hooks declared to run before targets, stopping at the first one that fails.
+pub(super) fn run_before_hooks(globals: &WorkspaceGlobals) -> Result<()> {
+    let hooks = globals.workspace_handle().inner().settings().hooks();
+    for hook in hooks.before_run() {
+        run_hook(globals, BEFORE_RUN, hook, None)?;
+    }
+    Ok(())
+}
+
+/// Execute the hooks declared to run after targets, with the run summary written to their standard input.
+/// Failures of these hooks are logged and do not change the run results.
+pub(super) fn run_after_hooks(
+    globals: &WorkspaceGlobals,
+    execution_results: &ExecutedGraph<ExecutionDetails>,
+) -> Result<()> {
+    let hooks = globals.workspace_handle().inner().settings().hooks();
+    if hooks.after_run().is_empty() && hooks.on_failure().is_empty() {
+        return Ok(());
+    }
+
+    let (summary, success) = run_summary(execution_results)?;
+    let summary = Arc::<[u8]>::from(summary);
+
+    let on_failure = if success { &[] } else { hooks.on_failure() };
+    let after_hooks = on_failure
+        .iter()
+        .map(|hook| (ON_FAILURE, hook))
+        .chain(hooks.after_run().iter().map(|hook| (AFTER_RUN, hook)));
+
+    for (kind, hook) in after_hooks {
+        if let Err(err) = run_hook(globals, kind, hook, Some(summary.clone())) {
+            globals.logger().error(format!("{err:?}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn run_hook(
+    globals: &WorkspaceGlobals,
+    kind: &str,
+    hook: &RunHook,
+    input: Option<Arc<[u8]>>,
+) -> Result<()> {
+    let logger = globals.logger();
+    let workspace = globals.workspace_handle().inner();
+
+    match hook {
+        RunHook::Target(double) => {
+            logger.info(format!("executing {kind} hook target {double}"));
+            run_hook_target(globals, double, input)
+                .with_context(|| format!("{kind} hook target {double} has failed"))
+        }
+        RunHook::Commands { .. } => {
+            logger.info(format!("executing {kind} hook commands"));
+            let process_group = match input {
+                Some(input) => ProcessGroup::default().with_input(input),
+                None => ProcessGroup::default(),
+            };
+            run_commands(
+                to_value(hook)?,
+                workspace.root(),
+                get_workspace_env(workspace)?,
+                &get_contextual_logger(globals.log_level(), kind),
+                &process_group,
+            )
+            .with_context(|| format!("{kind} hook commands have failed"))
+        }
+    }
+}
+
+/// Execute a hook target along with its dependencies.
+fn run_hook_target(
+    globals: &WorkspaceGlobals,
+    double: &str,
+    input: Option<Arc<[u8]>>,
+) -> Result<()> {
+    let (project, target) = double
+        .split_once(':')
+        .ok_or_else(|| anyhow!("invalid target {double}, expected <project>:<target>"))?;
+
+    let selection =
+        Selection::from_source(SelectorSource::Provided(ProjectSelector::array([project])));
+
+    let execution_graph = ExecutionGraph::try_new(
+        [(&selection, target)],
+        ExecutionGraphOptions {
+            workspace: globals.workspace_handle().inner(),
+            deserialization_context: globals.deserialization_context(),
+            max_depth: None,
+        },
+    )
+    .context("could not build execution graph")?;
+
+    let executor_resolutions = resolve_graph_executors(&execution_graph, globals)?;
+
+    let options = match input {
+        Some(input) => RunOptions::default().with_input(input),
+        None => RunOptions::default(),
+    };
+
+    let results = execute_graph(
+        execution_graph,
+        globals,
+        &executor_resolutions,
+        &options,
+        &HashMap::new(),
+    )?;
+
+    for (double, node) in results.execution() {
+        if let Some(Err(err)) = &node.result {
+            bail!("target {double} has failed: {err:#}");
+        }
+    }
+
+    Ok(())
+}
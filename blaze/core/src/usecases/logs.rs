@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail};
+use blaze_common::error::Result;
+
+use crate::{
+    executions::logs::{last_run_log, read_log},
+    GlobalOptions, WorkspaceGlobals,
+};
+
+pub struct LogsOptions {
+    double: String,
+}
+
+impl LogsOptions {
+    pub fn new<D: AsRef<str>>(double: D) -> Self {
+        Self {
+            double: double.as_ref().to_owned(),
+        }
+    }
+}
+
+/// Output captured during the last run of a target.
+pub struct TargetLogs {
+    run_id: String,
+    content: Vec<u8>,
+}
+
+impl TargetLogs {
+    /// Identifier of the run during which the output was captured.
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+}
+
+/// Get the output captured during the last run of a target (`<project>:<target>`).
+pub fn target_logs(
+    root: &Path,
+    options: LogsOptions,
+    global_options: GlobalOptions,
+) -> Result<TargetLogs> {
+    let globals = WorkspaceGlobals::new(root, global_options)?;
+    let workspace = globals.workspace_handle().inner();
+
+    let project = match options.double.split_once(':') {
+        Some((project, _)) => project,
+        None => bail!(
+            "invalid target \"{}\", expected <project>:<target>",
+            options.double
+        ),
+    };
+
+    if !workspace.projects().contains_key(project) {
+        bail!("project {project} does not exist");
+    }
+
+    let (run_id, path) = last_run_log(workspace.root(), &options.double)?
+        .ok_or_else(|| anyhow!("no logs were found for target {}", options.double))?;
+
+    let content = read_log(&path)?;
+
+    Ok(TargetLogs { run_id, content })
+}
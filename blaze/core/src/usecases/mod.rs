@@ -1,15 +1,30 @@
+mod cache_entries;
 mod describe;
+mod explain_cache;
+mod export_graph;
 mod global;
+mod hooks;
 mod init;
+mod logs;
 mod render;
+mod report;
 mod rm_cache;
 mod run;
 mod spawn;
+mod watch;
+mod worker;
 
+pub use cache_entries::*;
 pub use describe::*;
+pub use explain_cache::*;
+pub use export_graph::*;
 pub use global::*;
 pub use init::*;
+pub use logs::*;
 pub use render::*;
+pub use report::ReportFormat;
 pub use rm_cache::*;
 pub use run::*;
 pub use spawn::*;
+pub use watch::*;
+pub use worker::*;
@@ -0,0 +1,299 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::Context;
+use blaze_common::{error::Result, unit_enum_from_str};
+use serde::Serialize;
+use strum_macros::{Display, EnumIter};
+
+use crate::executions::{
+    check::InvalidationReason,
+    graph::{ExecutedGraph, ExecutedNode},
+    timeout::timed_out,
+};
+
+use super::ExecutionDetails;
+
+/// Format of a run report file.
+#[derive(EnumIter, Display, Clone, Copy, Debug)]
+pub enum ReportFormat {
+    Json,
+    Junit,
+}
+
+unit_enum_from_str!(ReportFormat);
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+enum ReportedState {
+    Executed,
+    Noop,
+    Cached,
+    Failed,
+    TimedOut,
+    Skipped,
+    Ignored,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportedTarget<'a> {
+    double: String,
+    project: &'a str,
+    target: &'a str,
+    root: bool,
+    dependencies: Vec<&'a str>,
+    state: ReportedState,
+    /// Execution time in seconds, only available for executed targets.
+    execution_time: Option<f64>,
+    /// Number of attempts, only available for executed targets.
+    attempts: Option<usize>,
+    /// Error messages, from the top-level error down to its root cause.
+    errors: Vec<String>,
+    /// Why the cache state could not be used, only available for executed targets.
+    invalidation_reasons: &'a [InvalidationReason],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report<'a> {
+    success: bool,
+    targets: Vec<ReportedTarget<'a>>,
+}
+
+impl<'a> ReportedTarget<'a> {
+    fn new(node: &'a ExecutedNode<ExecutionDetails>) -> Self {
+        let (state, execution_time, attempts, errors) = match &node.result {
+            Some(Ok(ExecutionDetails::Executed {
+                execution_time,
+                attempts,
+                ..
+            })) => (
+                ReportedState::Executed,
+                Some(execution_time.as_secs_f64()),
+                Some(*attempts),
+                vec![],
+            ),
+            Some(Ok(ExecutionDetails::Noop)) => (ReportedState::Noop, None, None, vec![]),
+            Some(Ok(ExecutionDetails::Cached)) => (ReportedState::Cached, None, None, vec![]),
+            Some(Err(err)) => (
+                if timed_out(err).is_some() {
+                    ReportedState::TimedOut
+                } else {
+                    ReportedState::Failed
+                },
+                None,
+                None,
+                err.chain().map(|cause| cause.to_string()).collect(),
+            ),
+            None if node.skipped => (ReportedState::Skipped, None, None, vec![]),
+            None => (ReportedState::Ignored, None, None, vec![]),
+        };
+
+        let mut dependencies = node
+            .dependencies
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        dependencies.sort();
+
+        Self {
+            double: node.execution.get_double(),
+            project: node.execution.get_project_name(),
+            target: node.execution.get_target_name(),
+            root: node.root,
+            dependencies,
+            state,
+            execution_time,
+            attempts,
+            errors,
+            invalidation_reasons: match &node.result {
+                Some(Ok(ExecutionDetails::Executed {
+                    invalidation_reasons,
+                    ..
+                })) => invalidation_reasons,
+                _ => &[],
+            },
+        }
+    }
+}
+
+impl<'a> Report<'a> {
+    fn new(targets: Vec<ReportedTarget<'a>>) -> Self {
+        Self {
+            success: targets.iter().filter(|target| target.root).all(|target| {
+                !matches!(
+                    target.state,
+                    ReportedState::Failed
+                        | ReportedState::TimedOut
+                        | ReportedState::Skipped
+                        | ReportedState::Ignored
+                )
+            }),
+            targets,
+        }
+    }
+}
+
+fn reported_targets(
+    execution_results: &ExecutedGraph<ExecutionDetails>,
+) -> Vec<ReportedTarget<'_>> {
+    execution_results
+        .execution()
+        .values()
+        .map(ReportedTarget::new)
+        .collect()
+}
+
+/// Write a report of all executed targets to a file, using the requested format.
+pub(super) fn write_report(
+    path: &Path,
+    format: ReportFormat,
+    execution_results: &ExecutedGraph<ExecutionDetails>,
+) -> Result<()> {
+    let targets = reported_targets(execution_results);
+
+    let file = File::create(path)
+        .with_context(|| format!("could not create report file at {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    match format {
+        ReportFormat::Json => serde_json::to_writer_pretty(&mut writer, &Report::new(targets))?,
+        ReportFormat::Junit => write_junit(&mut writer, &targets)?,
+    }
+
+    writer
+        .flush()
+        .with_context(|| format!("could not write report file at {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Summary of a run, with the same content as a JSON report.
+/// Returns the summary along with a flag telling if the run was successful.
+pub(super) fn run_summary(
+    execution_results: &ExecutedGraph<ExecutionDetails>,
+) -> Result<(Vec<u8>, bool)> {
+    let report = Report::new(reported_targets(execution_results));
+    Ok((serde_json::to_vec(&report)?, report.success))
+}
+
+/// Each project is reported as a test suite, and each of its targets as a test case.
+/// Failed targets are reported as failures and targets that could not be executed are reported as skipped.
+fn write_junit<W: Write>(writer: &mut W, targets: &[ReportedTarget]) -> Result<()> {
+    let count = |states: &[ReportedState], targets: &[&ReportedTarget]| {
+        targets
+            .iter()
+            .filter(|target| states.contains(&target.state))
+            .count()
+    };
+    let failed = &[ReportedState::Failed, ReportedState::TimedOut];
+    let skipped = &[ReportedState::Skipped, ReportedState::Ignored];
+    let total_time = |targets: &[&ReportedTarget]| {
+        targets
+            .iter()
+            .filter_map(|target| target.execution_time)
+            .sum::<f64>()
+    };
+
+    let all = targets.iter().collect::<Vec<_>>();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="blaze" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+        all.len(),
+        count(failed, &all),
+        count(skipped, &all),
+        total_time(&all)
+    )?;
+
+    let mut projects = targets
+        .iter()
+        .map(|target| target.project)
+        .collect::<Vec<_>>();
+    projects.dedup();
+
+    for project in projects {
+        let suite = targets
+            .iter()
+            .filter(|target| target.project == project)
+            .collect::<Vec<_>>();
+
+        writeln!(
+            writer,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+            escape_xml(project),
+            suite.len(),
+            count(failed, &suite),
+            count(skipped, &suite),
+            total_time(&suite)
+        )?;
+
+        for target in suite {
+            write!(
+                writer,
+                r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+                escape_xml(target.target),
+                escape_xml(project),
+                target.execution_time.unwrap_or_default()
+            )?;
+            match target.state {
+                ReportedState::Failed | ReportedState::TimedOut => {
+                    writeln!(writer, ">")?;
+                    writeln!(
+                        writer,
+                        r#"      <failure message="{}">{}</failure>"#,
+                        escape_xml(target.errors.last().map(String::as_str).unwrap_or_default()),
+                        escape_xml(&target.errors.join("\n\nCaused by:\n"))
+                    )?;
+                    writeln!(writer, "    </testcase>")?;
+                }
+                ReportedState::Skipped | ReportedState::Ignored => {
+                    writeln!(writer, ">")?;
+                    writeln!(
+                        writer,
+                        r#"      <skipped message="{}"/>"#,
+                        if target.state == ReportedState::Skipped {
+                            "target was not executed because of a failure"
+                        } else {
+                            "target was not executed"
+                        }
+                    )?;
+                    writeln!(writer, "    </testcase>")?;
+                }
+                ReportedState::Cached => {
+                    writeln!(writer, ">")?;
+                    writeln!(writer, "      <system-out>cached</system-out>")?;
+                    writeln!(writer, "    </testcase>")?;
+                }
+                ReportedState::Executed | ReportedState::Noop => writeln!(writer, "/>")?,
+            }
+        }
+
+        writeln!(writer, "  </testsuite>")?;
+    }
+
+    writeln!(writer, "</testsuites>")?;
+
+    Ok(())
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -45,13 +45,14 @@ pub fn rm_execution_caches(
 ) -> Result<()> {
     let globals = WorkspaceGlobals::new(root, global_options)?;
 
+    let selection = options
+        .selector_source
+        .clone()
+        .map(Selection::from_source)
+        .unwrap_or_default();
+
     let graph = ExecutionGraph::try_new(
-        &options
-            .selector_source
-            .clone()
-            .map(Selection::from_source)
-            .unwrap_or_default(),
-        &options.target,
+        [(&selection, options.target.as_str())],
         ExecutionGraphOptions {
             deserialization_context: globals.deserialization_context(),
             workspace: globals.workspace_handle().inner(),
@@ -72,6 +73,8 @@ pub fn rm_execution_caches(
             .is_some())
     })?;
 
+    cache.flush_index()?;
+
     results.fmt(&mut std::io::stdout(), |node| {
         format!(
             "{} ({})",
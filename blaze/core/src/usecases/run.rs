@@ -1,52 +1,112 @@
 use std::{
+    collections::HashMap,
     hash::{Hash, Hasher},
-    path::Path,
-    sync::Arc,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    thread::sleep,
     time::{Duration, Instant},
 };
 
-use anyhow::Context;
-use blaze_common::{error::Result, logger::Logger, parallelism::Parallelism};
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{
+    error::Result,
+    logger::Logger,
+    parallelism::Parallelism,
+    target::{RetryPolicy, TimeSpan},
+    workspace::Workspace,
+};
 use colored::{ColoredString, Colorize};
 
 use crate::{
     executions::{
+        check::InvalidationReason,
         execution::{
             CachedDependencyExecution, CachedExecutionContext, CachedExecutionState,
             TargetExecution,
         },
-        graph::{ExecutedGraph, ExecutedNode, ExecutionGraph, ExecutionGraphOptions},
+        file_hashes::{load_file_hashes, save_file_hashes},
+        graph::{
+            is_skipped, ExecutedGraph, ExecutedNode, ExecutionGraph, ExecutionGraphOptions, Skipped,
+        },
+        logs::{new_run_id, TargetLog},
+        remote::{RemoteExecutionRequest, WorkerPool},
+        timeout::{timed_out, with_timeout},
+        timings::ExecutionTimings,
     },
-    executors::{resolve_executors, CustomResolutionContext, ExecutorContext},
+    executors::{resolve_executors, CustomResolutionContext, ExecutorContext, ResolvedExecutors},
     global_init,
-    logging::{colorize, get_contextual_logger},
-    system::{hash::hasher, locks::ProcessLock},
+    logging::{colorize, get_contextual_logger, RunDisplay, TargetOutcome},
+    system::{
+        hash::hasher,
+        locks::ProcessLock,
+        process::{process_failure, ProcessGroup},
+    },
     workspace::selection::{Selection, SelectorSource},
     WorkspaceGlobals,
 };
 
-use super::GlobalOptions;
+use super::{
+    hooks::{run_after_hooks, run_before_hooks},
+    report::{write_report, ReportFormat},
+    GlobalOptions,
+};
+
+/// A target to run, with an optional projects selection overriding the default one.
+#[derive(Clone)]
+struct RunTarget {
+    target: String,
+    selector: Option<SelectorSource>,
+}
 
 #[derive(Default)]
 pub struct RunOptions {
     selector: Option<SelectorSource>,
-    target: String,
+    targets: Vec<RunTarget>,
     parallelism: Option<Parallelism>,
     is_dry_run: bool,
     display_graph: bool,
     dependencies_depth: Option<usize>,
+    report: Option<(PathBuf, ReportFormat)>,
+    fail_fast: Option<bool>,
+    tui: Option<usize>,
+    workers: Option<(Vec<String>, String)>,
+    input: Option<Arc<[u8]>>,
 }
 
 impl RunOptions {
     pub fn new<T: AsRef<str>>(target: T) -> Self {
-        Self {
-            target: target.as_ref().to_owned(),
-            ..Default::default()
-        }
+        Self::default().with_target(target)
     }
 
+    /// Replace all targets with the provided one.
     pub fn with_target<T: AsRef<str>>(mut self, target: T) -> Self {
-        target.as_ref().clone_into(&mut self.target);
+        self.targets = vec![RunTarget {
+            target: target.as_ref().to_owned(),
+            selector: None,
+        }];
+        self
+    }
+
+    /// Add another target, executed on the same projects selection as the other targets.
+    /// All targets are executed in a single execution graph.
+    pub fn and_target<T: AsRef<str>>(mut self, target: T) -> Self {
+        self.targets.push(RunTarget {
+            target: target.as_ref().to_owned(),
+            selector: None,
+        });
+        self
+    }
+
+    /// Add another target, executed on its own projects selection.
+    pub fn and_target_with_selector_source<T: AsRef<str>>(
+        mut self,
+        target: T,
+        source: SelectorSource,
+    ) -> Self {
+        self.targets.push(RunTarget {
+            target: target.as_ref().to_owned(),
+            selector: Some(source),
+        });
         self
     }
 
@@ -74,13 +134,75 @@ impl RunOptions {
         self.dependencies_depth = Some(max);
         self
     }
+
+    pub fn with_report<P: AsRef<Path>>(mut self, path: P, format: ReportFormat) -> Self {
+        self.report = Some((path.as_ref().to_owned(), format));
+        self
+    }
+
+    /// Stop executing targets as soon as one of them fails (when `true`), or keep executing targets that do not depend on failed ones (when `false`).
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = Some(fail_fast);
+        self
+    }
+
+    /// Display running targets and the last lines of their output in an interactive terminal UI.
+    /// Up to `tail_lines` lines of output are displayed for each running target (none when `0`).
+    /// The usual logs are displayed instead when the standard output is not a terminal.
+    pub fn with_tui(mut self, tail_lines: usize) -> Self {
+        self.tui = Some(tail_lines);
+        self
+    }
+
+    /// Execute targets on remote workers (see [`crate::worker`]) instead of the local machine.
+    /// Each address executes one target at a time, the same address can be provided multiple times to execute more targets at once on the same worker.
+    /// The `token` must match the one of each worker.
+    /// Remote executions are never cached, since their files are only available on the workers.
+    pub fn with_workers<S: AsRef<str>, I: IntoIterator<Item = S>, T: AsRef<str>>(
+        mut self,
+        addresses: I,
+        token: T,
+    ) -> Self {
+        self.workers = Some((
+            addresses
+                .into_iter()
+                .map(|address| address.as_ref().to_owned())
+                .collect(),
+            token.as_ref().to_owned(),
+        ));
+        self
+    }
+
+    /// Write some data to the standard input of processes spawned by targets (see [`ProcessGroup::with_input`]).
+    pub(super) fn with_input(mut self, input: Arc<[u8]>) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    pub(super) fn get_parallelism(&self, workspace: &Workspace) -> Parallelism {
+        self.parallelism
+            .or(workspace.settings().parallelism())
+            .unwrap_or_default()
+    }
+
+    pub(super) fn get_fail_fast(&self, workspace: &Workspace) -> bool {
+        self.fail_fast
+            .or(workspace.settings().fail_fast())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug)]
 pub enum ExecutionDetails {
     Cached,
     Noop,
-    Executed { execution_time: Duration },
+    Executed {
+        execution_time: Duration,
+        /// Number of executions, greater than 1 when the target was retried.
+        attempts: usize,
+        /// Why the cache state of the target could not be used (empty when the target has no cache).
+        invalidation_reasons: Vec<InvalidationReason>,
+    },
 }
 
 pub type RunResult = Result<ExecutedGraph<ExecutionDetails>>;
@@ -94,23 +216,9 @@ pub fn run<R: AsRef<Path>>(
     let globals = WorkspaceGlobals::new(root.as_ref(), globals_options)?;
     global_init(&globals)?;
 
-    let workspace = globals.workspace_handle().inner();
     let logger = globals.logger();
-    let cache = globals.cache();
-
-    let execution_graph = ExecutionGraph::try_new(
-        &options
-            .selector
-            .map(Selection::from_source)
-            .unwrap_or_default(),
-        &options.target,
-        ExecutionGraphOptions {
-            workspace,
-            deserialization_context: globals.deserialization_context(),
-            max_depth: options.dependencies_depth,
-        },
-    )
-    .context("could not build execution graph")?;
+
+    let execution_graph = build_execution_graph(&globals, &options)?;
 
     let targets_to_be_executed = execution_graph.targets();
 
@@ -125,130 +233,451 @@ pub fn run<R: AsRef<Path>>(
         targets_to_be_executed
     ));
 
-    let parallelism = options
-        .parallelism
-        .or(workspace.settings().parallelism())
-        .unwrap_or_default();
-
     let execution_results = if options.is_dry_run {
         execution_graph.ignore_all()?
     } else {
-        let executor_references = execution_graph.get_executor_references();
-
-        logger.info(format!(
-            "{} executor reference(s) will be resolved ({:?})",
-            executor_references.len(),
-            executor_references
-                .iter()
-                .map(|url| url.to_string())
-                .collect::<Vec<_>>()
-        ));
+        let executor_resolutions = resolve_graph_executors(&execution_graph, &globals)?;
+
+        run_before_hooks(&globals).context("run was aborted")?;
+
+        execute_graph(
+            execution_graph,
+            &globals,
+            &executor_resolutions,
+            &options,
+            &HashMap::new(),
+        )?
+        .map_inner(CachedExecutionState::into_details)
+    };
 
-        let executor_resolutions = Arc::new(
-            resolve_executors(
-                &executor_references,
-                CustomResolutionContext {
-                    cache,
-                    workspace,
-                    logger: &logger,
-                },
+    report_results(&options, &execution_results, &logger)?;
+
+    if !options.is_dry_run {
+        run_after_hooks(&globals, &execution_results)?;
+    }
+
+    Ok(execution_results)
+}
+
+/// Build the execution graph for the run options.
+pub(super) fn build_execution_graph(
+    globals: &WorkspaceGlobals,
+    options: &RunOptions,
+) -> Result<ExecutionGraph> {
+    let roots = options
+        .targets
+        .iter()
+        .map(|run_target| {
+            (
+                run_target
+                    .selector
+                    .as_ref()
+                    .or(options.selector.as_ref())
+                    .cloned()
+                    .map(Selection::from_source)
+                    .unwrap_or_default(),
+                run_target.target.as_str(),
             )
-            .context("error while resolving executors")?,
-        );
+        })
+        .collect::<Vec<_>>();
+
+    ExecutionGraph::try_new(
+        roots.iter().map(|(selection, target)| (selection, *target)),
+        ExecutionGraphOptions {
+            workspace: globals.workspace_handle().inner(),
+            deserialization_context: globals.deserialization_context(),
+            max_depth: options.dependencies_depth,
+        },
+    )
+    .context("could not build execution graph")
+}
+
+/// Resolve all executors that are referenced in the execution graph.
+pub(super) fn resolve_graph_executors(
+    execution_graph: &ExecutionGraph,
+    globals: &WorkspaceGlobals,
+) -> Result<ResolvedExecutors> {
+    let logger = globals.logger();
+    let executor_references = execution_graph.get_executor_references();
+
+    logger.info(format!(
+        "{} executor reference(s) will be resolved ({:?})",
+        executor_references.len(),
+        executor_references
+            .iter()
+            .map(|url| url.to_string())
+            .collect::<Vec<_>>()
+    ));
+
+    let resolutions = resolve_executors(
+        &executor_references,
+        CustomResolutionContext {
+            cache: globals.local_cache(),
+            workspace: globals.workspace_handle().inner(),
+            logger: &logger,
+        },
+    )
+    .context("error while resolving executors");
+
+    flush_caches(globals);
+
+    resolutions
+}
+
+/// The result of a previous execution, which can be reused instead of executing a target again.
+#[derive(Clone, Copy)]
+pub(super) enum ReusedExecution {
+    Cached(u64),
+    NotCached,
+}
 
-        let cache_arc_0 = Arc::new(cache);
+impl ReusedExecution {
+    /// Get the reusable result from a previous execution result, if it was successful.
+    pub(super) fn from_result<T>(result: &Result<CachedExecutionState<T>>) -> Option<Self> {
+        match result {
+            Ok(CachedExecutionState::Cached(hash) | CachedExecutionState::New(hash, ..)) => {
+                Some(Self::Cached(*hash))
+            }
+            Ok(CachedExecutionState::NoCache(_)) => Some(Self::NotCached),
+            Err(_) => None,
+        }
+    }
+
+    fn state(self) -> CachedExecutionState<ExecutionDetails> {
+        match self {
+            Self::Cached(hash) => CachedExecutionState::Cached(hash),
+            Self::NotCached => CachedExecutionState::NoCache(ExecutionDetails::Cached),
+        }
+    }
+}
+
+impl CachedExecutionState<ExecutionDetails> {
+    pub(super) fn into_details(self) -> ExecutionDetails {
+        match self {
+            CachedExecutionState::Cached(_) => ExecutionDetails::Cached,
+            CachedExecutionState::New(
+                _,
+                ExecutionDetails::Executed {
+                    execution_time,
+                    attempts,
+                    ..
+                },
+                invalidation_reasons,
+            ) => ExecutionDetails::Executed {
+                execution_time,
+                attempts,
+                invalidation_reasons,
+            },
+            CachedExecutionState::New(_, details, _) | CachedExecutionState::NoCache(details) => {
+                details
+            }
+        }
+    }
+}
+
+/// Process groups of all targets executed during a run, so that they can be killed when failing fast.
+struct RunningTargets {
+    /// Set to [`None`] once interrupted.
+    groups: Mutex<Option<Vec<ProcessGroup>>>,
+}
 
-        let arc_workspace = Arc::new(workspace);
-        let logger_2 = logger.clone();
-        let logger_3 = logger.clone();
+impl RunningTargets {
+    fn new() -> Self {
+        Self {
+            groups: Mutex::new(Some(vec![])),
+        }
+    }
 
-        let log_level = globals.log_level();
+    /// Register the process group of a target that is about to be executed.
+    /// Returns `false` if the run has already been interrupted, meaning that the target must not be executed.
+    fn register(&self, group: &ProcessGroup) -> Result<bool> {
+        match self.lock()?.as_mut() {
+            Some(groups) => {
+                groups.push(group.clone());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Kill processes of all registered targets.
+    fn interrupt(&self) -> Result<()> {
+        let groups = self.lock()?.take().unwrap_or_default();
+        for group in groups {
+            group.kill_all()?;
+        }
+        Ok(())
+    }
 
-        let execute = |execution: &TargetExecution| {
-            let executor_reference = match execution.get_target().executor() {
-                Some(reference) => reference,
-                None => return Ok(ExecutionDetails::Noop),
+    fn lock(&self) -> Result<MutexGuard<'_, Option<Vec<ProcessGroup>>>> {
+        self.groups
+            .lock()
+            .map_err(|_| anyhow!("poison error (Mutex on running targets)."))
+    }
+}
+
+/// Execute the graph with the resolved executors.
+/// Targets whose double is a key of `reused` are not executed, the provided value is returned instead.
+/// When failing fast, the processes of running targets are killed as soon as one target fails.
+pub(super) fn execute_graph(
+    mut execution_graph: ExecutionGraph,
+    globals: &WorkspaceGlobals,
+    executor_resolutions: &ResolvedExecutors,
+    options: &RunOptions,
+    reused: &HashMap<String, ReusedExecution>,
+) -> Result<ExecutedGraph<CachedExecutionState<ExecutionDetails>>> {
+    let workspace = globals.workspace_handle().inner();
+    // remote executions are not cached, since their output files are only available on the workers.
+    let cache = globals.cache().filter(|_| options.workers.is_none());
+    let parallelism = options.get_parallelism(workspace);
+    let fail_fast = options.get_fail_fast(workspace);
+    let log_level = globals.log_level();
+
+    let cache_arc_0 = Arc::new(cache);
+
+    let arc_workspace = Arc::new(workspace);
+
+    let mut timings = load_timings(globals);
+    execution_graph.prioritize(&timings);
+    load_hashes(globals);
+    execution_graph
+        .limit_resources(workspace.settings().resource_pools())
+        .context("invalid target resources")?;
+    execution_graph.fail_fast(fail_fast);
+
+    let (display, display_refresh) = match options.tui {
+        Some(tail_lines) if RunDisplay::is_available() => {
+            let (display, refresh) =
+                RunDisplay::start(execution_graph.targets().len(), tail_lines, log_level);
+            (Some(display), Some(refresh))
+        }
+        Some(_) => {
+            globals
+                .logger()
+                .debug("standard output is not a terminal, interactive display is disabled");
+            (None, None)
+        }
+        None => (None, None),
+    };
+
+    let logger = display
+        .as_ref()
+        .map(RunDisplay::logger)
+        .unwrap_or_else(|| globals.logger());
+    let logger_2 = logger.clone();
+    let logger_3 = logger.clone();
+
+    let running_targets = RunningTargets::new();
+    let run_id = new_run_id();
+    let workers = options
+        .workers
+        .as_ref()
+        .map(|(addresses, token)| WorkerPool::new(addresses, token));
+
+    let create_log = |execution: &TargetExecution| -> Result<Option<TargetLog>> {
+        if execution.get_target().executor().is_none() {
+            return Ok(None);
+        }
+        let double = execution.get_double();
+        TargetLog::create(
+            arc_workspace.root(),
+            &double,
+            &run_id,
+            display
+                .as_ref()
+                .map(|display| display.target_output(&double)),
+        )
+        .map(Some)
+        .with_context(|| format!("could not capture output of target {double}"))
+    };
+
+    let execute = |execution: &TargetExecution, log: Option<&TargetLog>| {
+        let executor_reference = match execution.get_target().executor() {
+            Some(reference) => reference,
+            None => return Ok(ExecutionDetails::Noop),
+        };
+
+        let double = execution.get_double();
+        let executor_resolution = executor_resolutions
+            .get_for_reference(executor_reference)
+            .unwrap();
+
+        logger_2.debug(format!("executing target {double}..."));
+
+        let executor_logger = match &display {
+            Some(display) => display.target_logger(&double),
+            None => get_contextual_logger(log_level, double.as_str()),
+        };
+        let executor_logger = match log {
+            Some(log) => log.logger(executor_logger),
+            None => executor_logger,
+        };
+
+        let retry = execution.get_target().retry();
+        let max_attempts = retry.map(RetryPolicy::max_attempts).unwrap_or(1);
+
+        let start = Instant::now();
+        let mut attempt = 1;
+
+        loop {
+            let process_group = match (log, &display) {
+                (Some(log), _) => ProcessGroup::with_output(log.sink()),
+                (None, Some(display)) => ProcessGroup::with_output(display.target_output(&double)),
+                (None, None) => ProcessGroup::default(),
             };
+            let process_group = match &options.input {
+                Some(input) => process_group.with_input(input.clone()),
+                None => process_group,
+            };
+
+            if fail_fast && !running_targets.register(&process_group)? {
+                return Err(Skipped.into());
+            }
 
-            let double = execution.get_double();
-            let executor_resolution = executor_resolutions
-                .get_for_reference(executor_reference)
-                .unwrap();
-
-            logger_2.debug(format!("executing target {double}..."));
-
-            let executor_logger = get_contextual_logger(log_level, double.as_str());
-
-            let start = Instant::now();
-            Ok(ExecutionDetails::Executed {
-                execution_time: executor_resolution
-                    .executor()
-                    .execute(
-                        ExecutorContext {
-                            project: &execution.get_project(),
-                            workspace: &arc_workspace.clone(),
-                            logger: &executor_logger,
-                            target: execution.get_target_name(),
+            let result = with_timeout(
+                execution.get_target().timeout().map(TimeSpan::duration),
+                &process_group,
+                &executor_logger,
+                || match &workers {
+                    Some(workers) => workers.execute(
+                        &RemoteExecutionRequest {
+                            project: execution.get_project_name().to_owned(),
+                            target: execution.get_target_name().to_owned(),
                         },
-                        execution.get_target().options().clone(),
-                    )
-                    .with_context(|| format!("executor failed for target {double}"))
-                    .map(|_| start.elapsed())?,
-            })
-        };
+                        &executor_logger,
+                        &process_group,
+                    ),
+                    None => executor_resolution
+                        .executor()
+                        .execute(
+                            ExecutorContext {
+                                project: &execution.get_project(),
+                                workspace: &arc_workspace.clone(),
+                                logger: &executor_logger,
+                                target: execution.get_target_name(),
+                                process_group: &process_group,
+                            },
+                            execution.get_target().options().clone(),
+                        )
+                        .with_context(|| format!("executor failed for target {double}")),
+                },
+            );
 
-        fn maybe_locked<T, F>(
-            root: &Path,
-            execution: &TargetExecution,
-            logger: Logger,
-            f: F,
-        ) -> Result<T>
-        where
-            F: FnOnce() -> T,
-        {
-            let target = execution.get_target();
-            if target.stateless() {
-                return Ok(f());
+            let is_timed_out = matches!(&result, Err(err) if timed_out(err).is_some());
+
+            if fail_fast && !is_timed_out && process_group.is_killed()? {
+                bail!("target {double} was interrupted because another target has failed");
             }
-            let double = execution.get_double();
-            let mut hasher = hasher();
-            double.hash(&mut hasher);
-            let mut lock = ProcessLock::try_new(root, hasher.finish())?;
-            lock.on_wait(move || {
-                logger.warn(format!(
-                    "waiting for {double} to terminate in another process"
-                ))
-            });
-            let result = lock.locked(f)?;
-            Ok(result)
+
+            let err = match result {
+                Ok(()) => {
+                    return Ok(ExecutionDetails::Executed {
+                        execution_time: start.elapsed(),
+                        attempts: attempt,
+                        invalidation_reasons: vec![],
+                    })
+                }
+                Err(err) => err,
+            };
+
+            match retry {
+                Some(retry) if attempt < max_attempts && is_retryable(retry, &err) => {
+                    let delay = retry.delay_after(attempt);
+                    executor_logger.warn(format!(
+                        "attempt {attempt}/{max_attempts} has failed ({:#}), retrying in {delay:?}",
+                        err
+                    ));
+                    sleep(delay);
+                    attempt += 1;
+                }
+                _ => {
+                    if fail_fast {
+                        running_targets.interrupt()?;
+                    }
+                    if attempt > 1 {
+                        return Err(err.context(format!(
+                            "target {double} has failed after {attempt} attempts"
+                        )));
+                    }
+                    return Err(err);
+                }
+            }
+        }
+    };
+
+    fn maybe_locked<T, F>(
+        root: &Path,
+        execution: &TargetExecution,
+        logger: Logger,
+        f: F,
+    ) -> Result<T>
+    where
+        F: FnOnce() -> T,
+    {
+        let target = execution.get_target();
+        if target.stateless() {
+            return Ok(f());
         }
+        let double = execution.get_double();
+        let mut hasher = hasher();
+        double.hash(&mut hasher);
+        let mut lock = ProcessLock::try_new(root, hasher.finish())?;
+        lock.on_wait(move || {
+            logger.warn(format!(
+                "waiting for {double} to terminate in another process"
+            ))
+        });
+        let result = lock.locked(f)?;
+        Ok(result)
+    }
+
+    let reuse = |execution: &TargetExecution| {
+        let reused_execution = reused.get(&execution.get_double())?;
+        logger_2.debug(format!("target {execution} is up to date"));
+        Some(Ok(reused_execution.state()))
+    };
 
-        match cache_arc_0.as_ref() {
-            None => execution_graph.execute(parallelism, |execution, _| {
+    let results = match cache_arc_0.as_ref() {
+        None => execution_graph.execute(parallelism, |execution, _| {
+            displayed(display.as_ref(), execution, || {
+                if let Some(result) = reuse(execution) {
+                    return result;
+                }
                 maybe_locked(arc_workspace.root(), execution, logger_3.clone(), || {
-                    let result = execute(execution);
+                    let result = create_log(execution)
+                        .and_then(|log| execute(execution, log.as_ref()))
+                        .map(CachedExecutionState::NoCache);
 
                     let double = execution.get_double();
 
                     match &result {
                         Ok(_) => logger_3.debug(format!("target {double} is done")),
+                        Err(err) if is_skipped(err) => {
+                            logger_3.debug(format!("target {double} is skipped"))
+                        }
                         Err(err) => logger_3.error(format!("target {double} has failed: {err:?}")),
                     };
 
                     result
                 })?
-            })?,
-            Some(cache) => execution_graph
-                .execute(parallelism, |execution, child_executions| {
-                    maybe_locked(arc_workspace.root(), execution, logger_2.clone(), || {
-                        let double = execution.get_double();
+            })
+        }),
+        Some(cache) => execution_graph.execute(parallelism, |execution, child_executions| {
+            displayed(display.as_ref(), execution, || {
+                if let Some(result) = reuse(execution) {
+                    return result;
+                }
+                maybe_locked(arc_workspace.root(), execution, logger_2.clone(), || {
+                    let double = execution.get_double();
 
-                        let cached_execution_result = execution
+                    let cached_execution_result = create_log(execution).and_then(|log| {
+                        execution
                             .cached(
                                 child_executions
                                     .iter()
                                     .map(|child| CachedDependencyExecution {
                                         double: child.execution.get_double(),
+                                        execution: child.execution,
                                         state: child.result,
                                         source: child.dependency.as_ref(),
                                     })
@@ -265,50 +694,207 @@ pub fn run<R: AsRef<Path>>(
                                     cache,
                                     logger: &logger,
                                     workspace: &arc_workspace,
+                                    logs: log.as_ref(),
                                 },
-                                || execute(execution),
+                                || execute(execution, log.as_ref()),
                             )
                             .with_context(|| {
                                 format!("cached execution failed unexpectedly for target {double}")
-                            });
-
-                        let double = execution.get_double();
-
-                        match &cached_execution_result {
-                            Ok(CachedExecutionState::Cached(hash)) => {
-                                logger_2.debug(format!("target {double} is cached ({hash:0>16x})"))
-                            }
-                            Ok(_) => logger_2.debug(format!("target {double} is done")),
-                            Err(err) => {
-                                logger_2.error(format!("target {double} has failed: {err:?}"))
-                            }
-                        };
-
-                        cached_execution_result
-                    })?
-                })
-                .map(|executed_graph| {
-                    executed_graph.map_inner(
-                        |cached_execution_result| match cached_execution_result {
-                            CachedExecutionState::Cached(_) => ExecutionDetails::Cached,
-                            CachedExecutionState::New(_, details)
-                            | CachedExecutionState::NoCache(details) => details,
-                        },
-                    )
-                })?,
+                            })
+                    });
+
+                    let double = execution.get_double();
+
+                    match &cached_execution_result {
+                        Ok(CachedExecutionState::Cached(hash)) => {
+                            logger_2.debug(format!("target {double} is cached ({hash:0>16x})"))
+                        }
+                        Ok(_) => logger_2.debug(format!("target {double} is done")),
+                        Err(err) if is_skipped(err) => {
+                            logger_2.debug(format!("target {double} is skipped"))
+                        }
+                        Err(err) => logger_2.error(format!("target {double} has failed: {err:?}")),
+                    };
+
+                    cached_execution_result
+                })?
+            })
+        }),
+    };
+
+    if let (Some(display), Some(refresh)) = (display, display_refresh) {
+        display.finish(refresh)?;
+    }
+
+    let results = results?;
+
+    save_timings(globals, &mut timings, &results);
+    save_hashes(globals);
+    flush_caches(globals);
+
+    Ok(results)
+}
+
+/// Run a target execution, keeping the interactive display (if any) up to date.
+fn displayed<F>(
+    display: Option<&RunDisplay>,
+    execution: &TargetExecution,
+    f: F,
+) -> Result<CachedExecutionState<ExecutionDetails>>
+where
+    F: FnOnce() -> Result<CachedExecutionState<ExecutionDetails>>,
+{
+    let display = match display {
+        Some(display) => display,
+        None => return f(),
+    };
+
+    let double = execution.get_double();
+    display.target_started(&double);
+
+    let result = f();
+
+    display.target_finished(
+        &double,
+        match &result {
+            Ok(
+                CachedExecutionState::New(_, ExecutionDetails::Executed { .. }, _)
+                | CachedExecutionState::NoCache(ExecutionDetails::Executed { .. }),
+            ) => TargetOutcome::Executed,
+            Ok(
+                CachedExecutionState::New(_, ExecutionDetails::Noop, _)
+                | CachedExecutionState::NoCache(ExecutionDetails::Noop),
+            ) => TargetOutcome::Noop,
+            Ok(_) => TargetOutcome::Cached,
+            Err(err) if is_skipped(err) => TargetOutcome::Skipped,
+            Err(_) => TargetOutcome::Failed,
+        },
+    );
+
+    result
+}
+
+/// Check if a failed execution can be retried according to the retry policy exit codes.
+fn is_retryable(retry: &RetryPolicy, err: &anyhow::Error) -> bool {
+    retry.exit_codes().is_none_or(|codes| {
+        process_failure(err)
+            .and_then(|failure| failure.code)
+            .is_some_and(|code| codes.contains(&code))
+    })
+}
+
+/// Load historical execution times from the local cache.
+/// Targets are executed in an arbitrary order if they cannot be loaded.
+fn load_timings(globals: &WorkspaceGlobals) -> ExecutionTimings {
+    let logger = globals.logger();
+    match globals
+        .local_cache()
+        .map(ExecutionTimings::load)
+        .transpose()
+    {
+        Ok(timings) => timings.unwrap_or_default(),
+        Err(err) => {
+            logger.warn(format!("could not load execution timings: {err:?}"));
+            ExecutionTimings::default()
         }
+    }
+}
+
+/// Record execution times of all executed targets and store them in the local cache.
+fn save_timings(
+    globals: &WorkspaceGlobals,
+    timings: &mut ExecutionTimings,
+    results: &ExecutedGraph<CachedExecutionState<ExecutionDetails>>,
+) {
+    let cache = match globals.local_cache() {
+        Some(cache) => cache,
+        None => return,
     };
 
+    let mut updated = false;
+
+    for (double, node) in results.execution() {
+        if let Some(Ok(
+            CachedExecutionState::New(_, ExecutionDetails::Executed { execution_time, .. }, _)
+            | CachedExecutionState::NoCache(ExecutionDetails::Executed { execution_time, .. }),
+        )) = &node.result
+        {
+            timings.record(double, *execution_time);
+            updated = true;
+        }
+    }
+
+    if !updated {
+        return;
+    }
+
+    if let Err(err) = timings.save(cache) {
+        globals
+            .logger()
+            .warn(format!("could not save execution timings: {err:?}"));
+    }
+}
+
+/// Load file checksums computed by previous runs from the local cache.
+fn load_hashes(globals: &WorkspaceGlobals) {
+    let root = globals.workspace_handle().inner().root();
+    if let Some(Err(err)) = globals
+        .local_cache()
+        .map(|cache| load_file_hashes(cache, root))
+    {
+        globals
+            .logger()
+            .warn(format!("could not load file hashes: {err:?}"));
+    }
+}
+
+/// Store file checksums computed during the run in the local cache.
+fn save_hashes(globals: &WorkspaceGlobals) {
+    let root = globals.workspace_handle().inner().root();
+    if let Some(Err(err)) = globals
+        .local_cache()
+        .map(|cache| save_file_hashes(cache, root))
+    {
+        globals
+            .logger()
+            .warn(format!("could not save file hashes: {err:?}"));
+    }
+}
+
+/// Write the cache index changes made during the run.
+fn flush_caches(globals: &WorkspaceGlobals) {
+    if let Err(err) = globals.flush_caches() {
+        globals
+            .logger()
+            .warn(format!("could not write cache index: {err:?}"));
+    }
+}
+
+/// Display the execution graph (if requested) and log statistics about executed targets.
+pub(super) fn report_results(
+    options: &RunOptions,
+    execution_results: &ExecutedGraph<ExecutionDetails>,
+    logger: &Logger,
+) -> Result<()> {
     if options.display_graph {
         print!("\nExecution graph results:\n\n");
         execution_results.fmt(
             &mut std::io::stdout(),
             |execution_result| match &execution_result.result {
-                Some(Ok(ExecutionDetails::Executed { execution_time })) => format!(
-                    "{} (executed in {execution_time:?})",
+                Some(Ok(ExecutionDetails::Executed {
+                    execution_time,
+                    attempts,
+                    ..
+                })) => format!(
+                    "{} (executed in {execution_time:?}{})",
                     colorize(execution_result.execution.get_double(), |colored| colored
                         .bold()
-                        .bright_green())
+                        .bright_green()),
+                    if *attempts > 1 {
+                        format!(" after {attempts} attempts")
+                    } else {
+                        String::default()
+                    }
                 ),
                 Some(Ok(ExecutionDetails::Noop)) => format!(
                     "{} (done)",
@@ -324,27 +910,49 @@ pub fn run<R: AsRef<Path>>(
                         ColoredString::green
                     )
                 ),
-                Some(Err(err)) => format!(
-                    "{} (failed: {})",
-                    colorize(
-                        execution_result.execution.get_double(),
-                        ColoredString::bright_red
+                Some(Err(err)) => match timed_out(err) {
+                    Some(timed_out) => format!(
+                        "{} ({timed_out})",
+                        colorize(
+                            execution_result.execution.get_double(),
+                            ColoredString::bright_red
+                        )
                     ),
-                    err.root_cause()
-                ),
+                    None => format!(
+                        "{} (failed: {})",
+                        colorize(
+                            execution_result.execution.get_double(),
+                            ColoredString::bright_red
+                        ),
+                        err.root_cause()
+                    ),
+                },
+                None if execution_result.skipped => {
+                    format!("{} (skipped)", execution_result.execution.get_double())
+                }
                 None => format!("{} (ignored)", execution_result.execution.get_double()),
             },
         )?;
         println!();
     }
 
-    let stats = RunStats::new(&execution_results);
+    if let Some((path, format)) = &options.report {
+        write_report(path, *format, execution_results)?;
+        logger.debug(format!("run report written to {}", path.display()));
+    }
+
+    let stats = RunStats::new(execution_results);
     logger.debug(format!("executed target(s): {}", stats.executed));
     logger.debug(format!("failed target(s): {}", stats.failed));
     logger.debug(format!("cached target(s): {}", stats.cached));
+    logger.debug(format!("retried target(s): {}", stats.retried));
+    logger.debug(format!(
+        "skipped target(s) because of a failure: {}",
+        stats.skipped
+    ));
     logger.debug(format!("pending target(s): {}", stats.pending));
 
-    Ok(execution_results)
+    Ok(())
 }
 
 #[derive(Debug, Default)]
@@ -352,6 +960,8 @@ struct RunStats {
     executed: usize,
     cached: usize,
     failed: usize,
+    retried: usize,
+    skipped: usize,
     pending: usize,
 }
 
@@ -360,6 +970,12 @@ impl RunStats {
         let mut stats: RunStats = Default::default();
 
         for result in graph.execution().values() {
+            if let Some(Ok(ExecutionDetails::Executed { attempts, .. })) = &result.result {
+                if *attempts > 1 {
+                    stats.retried += 1;
+                }
+            }
+
             *match result {
                 ExecutedNode {
                     result: Some(Ok(ExecutionDetails::Cached)),
@@ -373,6 +989,11 @@ impl RunStats {
                     result: Some(Err(_)),
                     ..
                 } => &mut stats.failed,
+                ExecutedNode {
+                    result: None,
+                    skipped: true,
+                    ..
+                } => &mut stats.skipped,
                 ExecutedNode { result: None, .. } => &mut stats.pending,
             } += 1;
         }
@@ -103,7 +103,10 @@ pub fn spawn(
         .selector_source
         .map(Selection::from_source)
         .unwrap_or_default()
-        .select(SelectionContext { workspace })
+        .select(SelectionContext {
+            workspace,
+            deserialization_context: globals.deserialization_context(),
+        })
         .context("error while selecting project references")?
         .into_iter()
         .collect::<Vec<_>>();
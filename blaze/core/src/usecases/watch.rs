@@ -0,0 +1,155 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use blaze_common::error::Result;
+
+use crate::{
+    executions::{
+        graph::{ExecutedGraph, ExecutionGraph},
+        outputs::output_paths,
+    },
+    global_init,
+    system::watch::PollingWatcher,
+    WorkspaceGlobals,
+};
+
+use super::{
+    run::{
+        build_execution_graph, execute_graph, report_results, resolve_graph_executors,
+        ExecutionDetails, ReusedExecution,
+    },
+    GlobalOptions, RunOptions,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
+
+/// Run a target across a selection of projects, then run it again each time input files change.
+/// Only the targets whose input files have changed are executed again, along with the targets that depend on them.
+/// The `on_run` function is called after each run, and watching stops when it returns [`ControlFlow::Break`].
+pub fn watch<R, F>(
+    root: R,
+    options: RunOptions,
+    globals_options: GlobalOptions,
+    mut on_run: F,
+) -> Result<()>
+where
+    R: AsRef<Path>,
+    F: FnMut(&ExecutedGraph<ExecutionDetails>) -> ControlFlow<()>,
+{
+    let globals = WorkspaceGlobals::new(root.as_ref(), globals_options)?;
+    global_init(&globals)?;
+
+    let logger = globals.logger();
+
+    let execution_graph = build_execution_graph(&globals, &options)?;
+
+    let targets_to_be_executed = execution_graph.targets();
+
+    if targets_to_be_executed.is_empty() {
+        logger.warn("nothing to execute");
+        return Ok(());
+    }
+
+    logger.info(format!(
+        "{} target(s) will be executed ({:?})",
+        targets_to_be_executed.len(),
+        targets_to_be_executed
+    ));
+
+    let executor_resolutions = resolve_graph_executors(&execution_graph, &globals)?;
+
+    let mut watcher =
+        PollingWatcher::try_new(execution_graph.executions().flat_map(|execution| {
+            let project = execution.get_project();
+            execution
+                .get_target()
+                .cache()
+                .and_then(|cache| cache.invalidate_when().input_changes())
+                .into_iter()
+                .flatten()
+                .map(move |matcher| {
+                    (
+                        execution.get_double(),
+                        project.root().to_owned(),
+                        matcher.clone(),
+                    )
+                })
+        }))?;
+
+    if watcher.is_empty() {
+        logger.warn(
+            "no input files are declared in the selected targets, changes will not be detected",
+        );
+    }
+
+    let mut reused = HashMap::<String, ReusedExecution>::new();
+
+    loop {
+        let outputs_before_run = output_files(&execution_graph)?;
+
+        let execution_results = execute_graph(
+            execution_graph.duplicate()?,
+            &globals,
+            &executor_resolutions,
+            &options,
+            &reused,
+        )?;
+
+        // changes of output files are made by the targets themselves and must not trigger a new run,
+        // but other files might have been changed while targets were running.
+        let mut outputs = output_files(&execution_graph)?;
+        outputs.extend(outputs_before_run);
+        let changed_during_run = watcher.refresh_ignoring(&outputs)?;
+
+        reused = execution_results
+            .execution()
+            .iter()
+            .filter_map(|(double, node)| {
+                node.result
+                    .as_ref()
+                    .and_then(ReusedExecution::from_result)
+                    .map(|reused_execution| (double.to_owned(), reused_execution))
+            })
+            .collect();
+
+        let execution_results = execution_results.map_inner(|state| state.into_details());
+
+        report_results(&options, &execution_results, &logger)?;
+
+        if on_run(&execution_results).is_break() {
+            return Ok(());
+        }
+
+        let changed = if changed_during_run.is_empty() {
+            logger.info("waiting for file changes...");
+            watcher.wait_for_changes(POLL_INTERVAL, DEBOUNCE_DURATION)?
+        } else {
+            changed_during_run
+        };
+        let affected = execution_graph.with_dependents(changed.iter().map(String::as_str));
+
+        logger.info(format!(
+            "file changes detected, {} target(s) will be executed again ({:?})",
+            affected.len(),
+            affected.iter().collect::<BTreeSet<_>>()
+        ));
+
+        reused.retain(|double, _| !affected.contains(double));
+    }
+}
+
+/// Get the declared output files of all targets of the graph.
+fn output_files(execution_graph: &ExecutionGraph) -> Result<HashSet<PathBuf>> {
+    let mut paths = HashSet::new();
+    for execution in execution_graph.executions() {
+        if let Some(cache) = execution.get_target().cache() {
+            paths.extend(output_paths(execution, cache.outputs())?);
+        }
+    }
+    Ok(paths)
+}
@@ -0,0 +1,224 @@
+use std::{
+    io::{BufRead, BufReader, Read},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    path::Path,
+    thread::scope,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context};
+use blaze_common::{error::Result, logger::Logger, selector::ProjectSelector};
+
+use crate::{
+    executions::{
+        graph::{ExecutionGraph, ExecutionGraphOptions},
+        remote::{
+            RemoteError, RemoteExecutionMessage, RemoteExecutionRequest, RemoteExecutionSender,
+            RemoteHandshake,
+        },
+    },
+    executors::ExecutorContext,
+    global_init,
+    system::{
+        process::ProcessGroup,
+        thread::{join, thread},
+    },
+    workspace::selection::{Selection, SelectorSource},
+    WorkspaceGlobals,
+};
+
+use super::{run::resolve_graph_executors, GlobalOptions};
+
+/// Maximum time a coordinator has to send the handshake and the execution request once connected.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct WorkerOptions {
+    address: String,
+    token: String,
+}
+
+impl WorkerOptions {
+    /// Listen on the provided address (for e.g `127.0.0.1:7780`).
+    /// Coordinators must provide the same `token` (see [`crate::RunOptions::with_workers`]) in order to execute targets.
+    pub fn new<A: AsRef<str>, T: AsRef<str>>(address: A, token: T) -> Self {
+        Self {
+            address: address.as_ref().to_owned(),
+            token: token.as_ref().to_owned(),
+        }
+    }
+}
+
+/// Execute targets on behalf of a coordinator (see [`crate::RunOptions::with_workers`]), until the process is stopped.
+/// Each connection executes a single target in the workspace located at `root`, connections are served concurrently.
+/// The `on_listening` function is called with the address of the worker once it accepts connections.
+pub fn worker<R, F>(
+    root: R,
+    options: WorkerOptions,
+    globals_options: GlobalOptions,
+    on_listening: F,
+) -> Result<()>
+where
+    R: AsRef<Path>,
+    F: FnOnce(SocketAddr),
+{
+    if options.token.is_empty() {
+        bail!("worker token must not be empty");
+    }
+
+    let globals = WorkspaceGlobals::new(root.as_ref(), globals_options)?;
+    global_init(&globals)?;
+
+    let logger = globals.logger();
+
+    let listener = TcpListener::bind(&options.address)
+        .with_context(|| format!("could not listen on {}", options.address))?;
+    let address = listener.local_addr()?;
+
+    logger.info(format!("worker is listening on {address}"));
+    on_listening(address);
+
+    scope(|scope| {
+        for connection in listener.incoming() {
+            let connection = match connection {
+                Ok(connection) => connection,
+                Err(err) => {
+                    logger.warn(format!("could not accept connection: {err}"));
+                    continue;
+                }
+            };
+            let (token, globals, logger) = (&options.token, &globals, &logger);
+            let _ = thread!(scope, move || {
+                if let Err(err) = serve(connection, token, globals, logger) {
+                    logger.error(format!("remote execution has failed: {err:?}"));
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+/// Execute the target requested through a connection, and send back its output and result.
+/// Requests are only read once the coordinator has provided the worker token.
+fn serve(
+    connection: TcpStream,
+    token: &str,
+    globals: &WorkspaceGlobals,
+    logger: &Logger,
+) -> Result<()> {
+    // a coordinator which does not send anything must not keep the connection open forever.
+    connection.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let peer = connection.peer_addr()?;
+
+    let mut reader = BufReader::new(connection.try_clone()?);
+    let mut line = String::new();
+    let _ = reader
+        .read_line(&mut line)
+        .with_context(|| format!("no handshake received from {peer}"))?;
+
+    let is_authenticated = serde_json::from_str::<RemoteHandshake>(&line)
+        .is_ok_and(|handshake| handshake.is_valid(token));
+
+    if !is_authenticated {
+        let sender = RemoteExecutionSender::new(connection.try_clone()?);
+        let _ = sender.send(&RemoteExecutionMessage::Done {
+            error: Some(RemoteError::new(&anyhow!("invalid worker token"))),
+        });
+        let _ = connection.shutdown(Shutdown::Both);
+        bail!("rejected connection from {peer} (invalid worker token)");
+    }
+
+    line.clear();
+    let _ = reader
+        .read_line(&mut line)
+        .context("could not read remote execution request")?;
+
+    // the connection is then only read in order to detect when the coordinator closes it.
+    connection.set_read_timeout(None)?;
+
+    let request = serde_json::from_str::<RemoteExecutionRequest>(&line)
+        .context("invalid remote execution request")?;
+    let double = request.get_double();
+
+    logger.info(format!("executing target {double}..."));
+
+    let sender = RemoteExecutionSender::new(connection.try_clone()?);
+    let process_group = ProcessGroup::with_output(sender.output());
+
+    scope(|scope| {
+        // the coordinator closes the connection when the execution must be interrupted.
+        let watcher_group = process_group.clone();
+        let watcher = thread!(scope, move || -> Result<()> {
+            let mut buffer = [0_u8; 64];
+            while matches!(reader.read(&mut buffer), Ok(read) if read > 0) {}
+            watcher_group.kill_all()
+        });
+
+        let result = execute(&request, globals, &sender.logger(), &process_group);
+
+        match &result {
+            Ok(()) => logger.info(format!("target {double} is done")),
+            Err(err) => logger.error(format!("target {double} has failed: {err:?}")),
+        }
+
+        let sent = sender.send(&RemoteExecutionMessage::Done {
+            error: result.as_ref().err().map(RemoteError::new),
+        });
+        let _ = connection.shutdown(Shutdown::Both);
+        join!(watcher)?;
+
+        sent.context("could not send execution result")
+    })
+}
+
+fn execute(
+    request: &RemoteExecutionRequest,
+    globals: &WorkspaceGlobals,
+    logger: &Logger,
+    process_group: &ProcessGroup,
+) -> Result<()> {
+    let double = request.get_double();
+    let workspace = globals.workspace_handle().inner();
+
+    let selection = Selection::from_source(SelectorSource::Provided(ProjectSelector::array([
+        &request.project,
+    ])));
+
+    let execution_graph = ExecutionGraph::try_new(
+        [(&selection, request.target.as_str())],
+        ExecutionGraphOptions {
+            workspace,
+            deserialization_context: globals.deserialization_context(),
+            max_depth: Some(0),
+        },
+    )
+    .with_context(|| format!("could not load target {double}"))?;
+
+    let execution = execution_graph
+        .executions()
+        .find(|execution| execution.get_double() == double)
+        .ok_or_else(|| anyhow!("target {double} was not found"))?;
+
+    let executor_reference = execution
+        .get_target()
+        .executor()
+        .ok_or_else(|| anyhow!("target {double} has no executor"))?;
+
+    let executor_resolutions = resolve_graph_executors(&execution_graph, globals)?;
+
+    executor_resolutions
+        .get_for_reference(executor_reference)
+        .ok_or_else(|| anyhow!("executor of target {double} was not resolved"))?
+        .executor()
+        .execute(
+            ExecutorContext {
+                project: &execution.get_project(),
+                workspace,
+                logger,
+                target: &request.target,
+                process_group,
+            },
+            execution.get_target().options().clone(),
+        )
+        .with_context(|| format!("executor failed for target {double}"))
+}
@@ -0,0 +1,221 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    panic::{AssertUnwindSafe, RefUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use blaze_common::{
+    error::Result,
+    executor::{HttpAuthentication, HttpTransport},
+};
+use fs4::FileExt;
+use reqwest::{Method, StatusCode};
+use url::Url;
+
+use crate::system::http::HttpSession;
+
+/// Storage for raw cache entries.
+/// Entry names are always made of alphanumeric characters, so that they can be used as file names or URL segments.
+pub trait CacheBackend: Send + Sync + RefUnwindSafe {
+    /// Read an entry. Returns [`None`] if it does not exist.
+    fn read(&self, entry: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Create or overwrite an entry.
+    fn write(&self, entry: &str, content: &[u8]) -> Result<()>;
+
+    /// Remove an entry. Removing an entry that does not exist is not an error.
+    fn remove(&self, entry: &str) -> Result<()>;
+
+    /// Replace an entry with the content computed from its current content.
+    /// The default implementation does not prevent concurrent updates of the same entry.
+    fn update(
+        &self,
+        entry: &str,
+        f: &mut dyn FnMut(Option<Vec<u8>>) -> Result<Vec<u8>>,
+    ) -> Result<()> {
+        let content = f(self.read(entry)?)?;
+        self.write(entry, &content)
+    }
+}
+
+/// Store cache entries as files in a local directory.
+pub struct LocalDirectoryBackend {
+    root: PathBuf,
+}
+
+impl LocalDirectoryBackend {
+    pub fn try_new(root: &Path) -> Result<Self> {
+        let is_dir = match fs::metadata(root) {
+            Ok(metadata) => metadata.is_dir(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+            Err(err) => return Err(err.into()),
+        };
+
+        if !is_dir {
+            std::fs::create_dir_all(root).with_context(|| {
+                format!("failed to created cache directory at {}", root.display())
+            })?;
+        }
+
+        Ok(Self {
+            root: root.to_owned(),
+        })
+    }
+}
+
+impl CacheBackend for LocalDirectoryBackend {
+    fn read(&self, entry: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.root.join(entry);
+
+        let mut file = match OpenOptions::new().read(true).open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("could not read cache entry at {}", path.display()))
+            }
+        };
+
+        let mut content = vec![];
+        file.lock_shared()?;
+        let read = file.read_to_end(&mut content);
+        file.unlock()?;
+        read.with_context(|| format!("could not read cache entry at {}", path.display()))?;
+
+        Ok(Some(content))
+    }
+
+    fn write(&self, entry: &str, content: &[u8]) -> Result<()> {
+        let path = self.root.join(entry);
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("could not open cache entry at {}", path.display()))?;
+
+        file.lock_exclusive()?;
+        file.set_len(0)?;
+        let written = file.write_all(content);
+        file.unlock()?;
+        written.with_context(|| format!("could not write cache entry at {}", path.display()))?;
+
+        Ok(())
+    }
+
+    fn update(
+        &self,
+        entry: &str,
+        f: &mut dyn FnMut(Option<Vec<u8>>) -> Result<Vec<u8>>,
+    ) -> Result<()> {
+        let path = self.root.join(entry);
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("could not open cache entry at {}", path.display()))?;
+
+        // the file is locked during the whole update, so that concurrent updates are not lost.
+        file.lock_exclusive()?;
+        let updated = (|| -> Result<()> {
+            let mut content = vec![];
+            let _ = file.read_to_end(&mut content)?;
+            let content = f((!content.is_empty()).then_some(content))?;
+            file.set_len(0)?;
+            file.seek(SeekFrom::Start(0))?;
+            file.write_all(&content)?;
+            Ok(())
+        })();
+        file.unlock()?;
+        updated.with_context(|| format!("could not update cache entry at {}", path.display()))
+    }
+
+    fn remove(&self, entry: &str) -> Result<()> {
+        let path = self.root.join(entry);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err)
+                .with_context(|| format!("could not remove cache entry at {}", path.display())),
+        }
+    }
+}
+
+/// Store cache entries on an HTTP server.
+/// Entries are fetched with `GET <url>/<entry>`, stored with `PUT <url>/<entry>` and removed with `DELETE <url>/<entry>`.
+pub struct HttpBackend {
+    url: Url,
+    // the HTTP client does not hold any state that could be left inconsistent by a panic.
+    session: AssertUnwindSafe<HttpSession>,
+}
+
+impl HttpBackend {
+    pub fn try_new(
+        url: &Url,
+        transport: HttpTransport,
+        authentication: Option<HttpAuthentication>,
+    ) -> Result<Self> {
+        let mut url = url.clone();
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Ok(Self {
+            url,
+            session: AssertUnwindSafe(HttpSession::try_new(transport, authentication)?),
+        })
+    }
+
+    fn entry_url(&self, entry: &str) -> Result<Url> {
+        self.url
+            .join(entry)
+            .with_context(|| format!("invalid cache entry URL for {entry}"))
+    }
+}
+
+impl CacheBackend for HttpBackend {
+    fn read(&self, entry: &str) -> Result<Option<Vec<u8>>> {
+        let url = self.entry_url(entry)?;
+        let response = self.session.send(Method::GET, &url, |request| request)?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            response
+                .error_for_status()
+                .and_then(|response| response.bytes())
+                .with_context(|| format!("could not read cache entry at {url}"))?
+                .to_vec(),
+        ))
+    }
+
+    fn write(&self, entry: &str, content: &[u8]) -> Result<()> {
+        let url = self.entry_url(entry)?;
+        self.session
+            .send(Method::PUT, &url, |request| request.body(content.to_vec()))?
+            .error_for_status()
+            .with_context(|| format!("could not write cache entry at {url}"))?;
+        Ok(())
+    }
+
+    fn remove(&self, entry: &str) -> Result<()> {
+        let url = self.entry_url(entry)?;
+        let response = self.session.send(Method::DELETE, &url, |request| request)?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+
+        response
+            .error_for_status()
+            .with_context(|| format!("could not remove cache entry at {url}"))?;
+        Ok(())
+    }
+}
@@ -1,54 +1,93 @@
 use std::{
-    fs::{self, OpenOptions},
-    io,
-    path::{Path, PathBuf},
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::Mutex,
+    time::SystemTime,
 };
 
 use anyhow::Context;
-use blaze_common::error::Result;
+use blaze_common::{
+    error::Result, settings::CacheBackendSettings, time::system_time_as_timestamps,
+};
 
-use fs4::FileExt;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use xxhash_rust::xxh3;
 
+use crate::system::time::now;
+
+use super::cache_backend::{CacheBackend, HttpBackend, LocalDirectoryBackend};
+
 pub struct CacheStore {
-    root: PathBuf,
+    backend: Box<dyn CacheBackend>,
+    index_updates: Mutex<HashMap<String, IndexUpdate>>,
+    /// Whether restored entries are marked as used in the index.
+    /// Shared stores do not, since every reader would have to write the index.
+    records_usage: bool,
 }
 
 const CACHE_FOLDER_NAME: &str = ".blaze/cache";
 
+/// Name of the entry which holds the index. It cannot collide with hashed entry names.
+const INDEX_ENTRY_NAME: &str = "index";
+
+/// Description of a cache entry, kept in the cache index so that original keys can be recovered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry {
+    key: String,
+    size: u64,
+    #[serde(with = "system_time_as_timestamps")]
+    created: SystemTime,
+    #[serde(with = "system_time_as_timestamps")]
+    last_used: SystemTime,
+}
+
+impl CacheEntry {
+    /// Key used to store the entry.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Size of the entry content, in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Time at which the entry was written.
+    pub fn created(&self) -> SystemTime {
+        self.created
+    }
+
+    /// Time at which the entry was last written or restored.
+    pub fn last_used(&self) -> SystemTime {
+        self.last_used
+    }
+}
+
+/// A change of the index which has not been written yet.
+enum IndexUpdate {
+    Written(CacheEntry),
+    Used(CacheEntry),
+    Removed,
+}
+
 impl CacheStore {
     /// Cache an object.
     pub fn cache<T>(&self, key: &str, value: &T) -> Result<()>
     where
         T: Serialize,
     {
-        let file_path = self.get_entry_filename(key);
-
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .open(&file_path)
-            .with_context(|| format!("could not open cache entry at {}", file_path.display()))?;
-
-        file.lock_exclusive()?;
-        file.set_len(0)?;
-        serde_cbor::to_writer(&file, value)?;
-        file.unlock()?;
-
-        Ok(())
+        let content = serde_cbor::to_vec(value)
+            .with_context(|| format!("could not serialize cache entry for {key}"))?;
+        self.cache_blob(key, &content)
     }
 
     /// Invalidate a cache key and remove it.
     pub fn invalidate(&self, key: &str) -> Result<()> {
-        let path = self.get_entry_filename(key);
-        match std::fs::remove_file(&path) {
-            Ok(()) => Ok(()),
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
-            Err(err) => Err(err)
-                .with_context(|| format!("could not remove cache entry at {}", path.display())),
-        }
+        let entry_name = Self::get_entry_name(key);
+        self.backend.remove(&entry_name)?;
+        self.record(entry_name, IndexUpdate::Removed);
+        Ok(())
     }
 
     /// Tries to restore an object from cache based on its type and key.
@@ -58,46 +97,179 @@ impl CacheStore {
     where
         T: DeserializeOwned,
     {
-        let path = self.get_entry_filename(key);
-
-        let file = match OpenOptions::new().read(true).open(&path) {
-            Ok(file) => file,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-            Err(err) => {
-                return Err(err)
-                    .with_context(|| format!("could not read cache entry at {}", path.display()))
-            }
-        };
+        self.restore_blob(key)?
+            .map(|content| {
+                serde_cbor::from_slice(&content)
+                    .with_context(|| format!("could not deserialize cache entry for {key}"))
+            })
+            .transpose()
+    }
+
+    /// Restore an object like [`Self::restore`], without marking its entry as used.
+    pub fn inspect<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.backend
+            .read(&Self::get_entry_name(key))?
+            .map(|content| {
+                serde_cbor::from_slice(&content)
+                    .with_context(|| format!("could not deserialize cache entry for {key}"))
+            })
+            .transpose()
+    }
 
-        file.lock_shared()?;
-        let object = serde_cbor::from_reader(&file);
-        file.unlock()?;
+    /// Store raw bytes, without any serialization.
+    pub fn cache_blob(&self, key: &str, content: &[u8]) -> Result<()> {
+        let entry_name = Self::get_entry_name(key);
+        self.backend.write(&entry_name, content)?;
+        let time = now();
+        self.record(
+            entry_name,
+            IndexUpdate::Written(CacheEntry {
+                key: key.to_owned(),
+                size: content.len() as u64,
+                created: time,
+                last_used: time,
+            }),
+        );
+        Ok(())
+    }
 
-        Ok(Some(object.with_context(|| {
-            format!("could not deserialize cache entry at {}", path.display())
-        })?))
+    /// Restore raw bytes previously stored with [`Self::cache_blob`].
+    pub fn restore_blob(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let entry_name = Self::get_entry_name(key);
+        let content = self.backend.read(&entry_name)?;
+        if let Some(content) = content.as_ref().filter(|_| self.records_usage) {
+            let time = now();
+            self.record(
+                entry_name,
+                IndexUpdate::Used(CacheEntry {
+                    key: key.to_owned(),
+                    size: content.len() as u64,
+                    created: time,
+                    last_used: time,
+                }),
+            );
+        }
+        Ok(content)
     }
 
+    /// Get all indexed entries, sorted by key.
+    /// Entries written before the index was introduced are only listed once they are restored.
+    pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let mut index = self.read_index()?;
+        Self::apply_updates(&mut index, self.index_updates.lock().unwrap().iter());
+        let mut entries = index.into_values().collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(entries)
+    }
+
+    /// Write pending changes of the index.
+    /// Changes are lost if the store is dropped before this method is called.
+    pub fn flush_index(&self) -> Result<()> {
+        let updates = std::mem::take(&mut *self.index_updates.lock().unwrap());
+        if updates.is_empty() {
+            return Ok(());
+        }
+        self.backend
+            .update(INDEX_ENTRY_NAME, &mut |content| {
+                let mut index = match content {
+                    Some(content) => Self::deserialize_index(&content)?,
+                    None => BTreeMap::new(),
+                };
+                Self::apply_updates(&mut index, updates.iter());
+                serde_cbor::to_vec(&index).context("could not serialize cache index")
+            })
+            .context("could not write cache index")
+    }
+
+    /// Load the cache store located in the workspace `.blaze` folder.
     pub fn load(root: &Path) -> Result<Self> {
-        let root = root.join(CACHE_FOLDER_NAME);
-
-        let is_dir = match fs::metadata(&root) {
-            Ok(metadata) => metadata.is_dir(),
-            Err(err) if err.kind() == io::ErrorKind::NotFound => false,
-            Err(err) => return Err(err.into()),
-        };
-
-        if !is_dir {
-            std::fs::create_dir_all(&root).with_context(|| {
-                format!("failed to created cache directory at {}", root.display())
-            })?;
+        Ok(Self::with_backend(
+            Box::new(LocalDirectoryBackend::try_new(
+                &root.join(CACHE_FOLDER_NAME),
+            )?),
+            true,
+        ))
+    }
+
+    /// Load a cache store from the workspace settings.
+    pub fn from_settings(root: &Path, settings: &CacheBackendSettings) -> Result<Self> {
+        Ok(match settings {
+            CacheBackendSettings::Local { path } => Self::with_backend(
+                Box::new(LocalDirectoryBackend::try_new(&root.join(path))?),
+                true,
+            ),
+            CacheBackendSettings::Http {
+                url,
+                transport,
+                authentication,
+            } => Self::with_backend(
+                Box::new(HttpBackend::try_new(
+                    url,
+                    transport.clone(),
+                    authentication.clone(),
+                )?),
+                false,
+            ),
+        })
+    }
+
+    fn with_backend(backend: Box<dyn CacheBackend>, records_usage: bool) -> Self {
+        Self {
+            backend,
+            index_updates: Mutex::default(),
+            records_usage,
         }
+    }
+
+    fn record(&self, entry_name: String, update: IndexUpdate) {
+        let mut updates = self.index_updates.lock().unwrap();
+        // a pending write must not be downgraded, since the entry might not be indexed yet.
+        if let (IndexUpdate::Used(used), Some(IndexUpdate::Written(written))) =
+            (&update, updates.get_mut(&entry_name))
+        {
+            written.last_used = used.last_used;
+            return;
+        }
+        let _ = updates.insert(entry_name, update);
+    }
 
-        Ok(Self { root })
+    fn read_index(&self) -> Result<BTreeMap<String, CacheEntry>> {
+        Ok(match self.backend.read(INDEX_ENTRY_NAME)? {
+            Some(content) => Self::deserialize_index(&content)?,
+            None => BTreeMap::new(),
+        })
+    }
+
+    fn deserialize_index(content: &[u8]) -> Result<BTreeMap<String, CacheEntry>> {
+        serde_cbor::from_slice(content).context("could not deserialize cache index")
+    }
+
+    fn apply_updates<'a, I: Iterator<Item = (&'a String, &'a IndexUpdate)>>(
+        index: &mut BTreeMap<String, CacheEntry>,
+        updates: I,
+    ) {
+        for (entry_name, update) in updates {
+            match update {
+                IndexUpdate::Written(entry) => {
+                    let _ = index.insert(entry_name.clone(), entry.clone());
+                }
+                IndexUpdate::Used(entry) => match index.get_mut(entry_name) {
+                    Some(indexed) => indexed.last_used = entry.last_used,
+                    None => {
+                        let _ = index.insert(entry_name.clone(), entry.clone());
+                    }
+                },
+                IndexUpdate::Removed => {
+                    let _ = index.remove(entry_name);
+                }
+            }
+        }
     }
 
-    fn get_entry_filename(&self, key: &str) -> PathBuf {
-        self.root
-            .join(format!("{:0>16x}", xxh3::xxh3_64(key.as_bytes())))
+    fn get_entry_name(key: &str) -> String {
+        format!("{:0>16x}", xxh3::xxh3_64(key.as_bytes()))
     }
 }
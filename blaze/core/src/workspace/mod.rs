@@ -1,3 +1,4 @@
+pub mod cache_backend;
 pub mod cache_store;
 pub mod configurations;
 pub mod init;
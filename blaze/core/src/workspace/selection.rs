@@ -1,18 +1,27 @@
 use anyhow::{anyhow, bail, Context};
 use regex::Regex;
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet},
     str::FromStr,
 };
 
 use blaze_common::{
     error::Result,
-    selector::ProjectSelector,
+    selector::{AffectedOptions, ProjectSelector},
     workspace::{ProjectRef, Workspace},
 };
 
+use crate::system::repository::changed_files;
+
+use super::{
+    configurations::DeserializationContext,
+    project_handle::{ProjectHandle, ProjectOptions},
+};
+
+#[derive(Clone, Copy)]
 pub struct SelectionContext<'a> {
     pub workspace: &'a Workspace,
+    pub deserialization_context: DeserializationContext<'a>,
 }
 
 #[derive(Debug, Clone)]
@@ -60,9 +69,90 @@ impl Selection {
                 self.select_include_exclude_project_refs(ctx, include, exclude)
             }
             ProjectSelector::Tagged(tags) => Ok(self.select_tagged_project_refs(ctx, tags)),
+            ProjectSelector::Affected { affected } => {
+                self.select_affected_project_refs(ctx, affected)
+            }
         }
     }
 
+    /// Select projects that contain files changed since the base revision, along with all projects that depend on them.
+    fn select_affected_project_refs<'s>(
+        &self,
+        ctx: SelectionContext<'s>,
+        options: &AffectedOptions,
+    ) -> Result<HashMap<&'s String, &'s ProjectRef>> {
+        let root = ctx.workspace.root();
+        let project_roots = ctx
+            .workspace
+            .projects()
+            .iter()
+            .map(|(name, project_ref)| (name, root.join(project_ref.path())))
+            .collect::<Vec<_>>();
+
+        // each changed file belongs to the project with the most specific root directory.
+        let mut affected = changed_files(root, options.base())?
+            .iter()
+            .filter_map(|path| {
+                project_roots
+                    .iter()
+                    .filter(|(_, project_root)| path.starts_with(project_root))
+                    .max_by_key(|(_, project_root)| project_root.components().count())
+                    .map(|(name, _)| *name)
+            })
+            .collect::<HashSet<_>>();
+
+        let mut dependents = HashMap::<&String, HashSet<&String>>::new();
+
+        for (name, project_ref) in ctx.workspace.projects() {
+            let project = ProjectHandle::from_root(
+                root.join(project_ref.path()),
+                ProjectOptions {
+                    name,
+                    deserialization_context: ctx.deserialization_context,
+                },
+            )
+            .with_context(|| format!("error while reading \"{name}\" project configuration"))?
+            .unwrap_inner();
+
+            for dependency in project
+                .targets()
+                .values()
+                .flat_map(|target| target.dependencies())
+            {
+                let selector = match dependency.projects() {
+                    // affected selectors are ignored in dependencies, since they would be resolved recursively.
+                    Some(ProjectSelector::Affected { .. }) | None => continue,
+                    Some(selector) => selector,
+                };
+
+                for dependency_name in
+                    Selection::from_source(SelectorSource::Provided(selector.clone()))
+                        .select(ctx)?
+                        .into_keys()
+                {
+                    dependents.entry(dependency_name).or_default().insert(name);
+                }
+            }
+        }
+
+        let mut next = affected.iter().copied().collect::<Vec<_>>();
+
+        while let Some(name) = next.pop() {
+            for dependent in dependents.get(name).into_iter().flatten() {
+                if affected.insert(dependent) {
+                    next.push(dependent);
+                }
+            }
+        }
+
+        Ok(ctx
+            .workspace
+            .projects()
+            .iter()
+            .filter(|(name, _)| affected.contains(name))
+            .collect())
+    }
+
     fn select_all_project_refs<'s>(
         &self,
         ctx: SelectionContext<'s>,
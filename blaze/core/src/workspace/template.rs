@@ -286,7 +286,7 @@ impl HelperDef for ShHelper {
             .map_err(|err| generic_error(&format!("could not wait for command process ({err})")))?;
 
         if !status.success {
-            return Err(generic_error(&format!(
+            Err(generic_error(&format!(
                 "command failed with status code: {:?}",
                 status.code
             )))?;
@@ -34,7 +34,8 @@ const targetDependencySchema = z.object({
     target: z.string().min(1),
     cachePropagation: z.union([
         z.literal('Always'),
-        z.literal('Never')
+        z.literal('Never'),
+        z.literal('OnOutputChange')
     ]),
     optional: z.boolean()
 })
@@ -42,7 +43,8 @@ const targetDependencySchema = z.object({
 const matchingBehaviorSchema = z.union([
     z.literal('Mixed'),
     z.literal('Hash'),
-    z.literal('Timestamps')
+    z.literal('Timestamps'),
+    z.literal('Git')
 ])
 
 const fileChangesMatchersSchema = z.object({
@@ -27,6 +27,10 @@ export const dependenciesSchema = {
                             {
                                 const: 'Never',
                                 description: 'Never propagate cache for this dependency.'
+                            },
+                            {
+                                const: 'OnOutputChange',
+                                description: 'Propagate cache only when the output files of this dependency have changed.'
                             }
                         ]
                     }
@@ -12,7 +12,7 @@ const gitPlainAuthentication = strictObject({
     }
 })
 
-const httpTransportProperties: Record<string, Schema> = {
+export const httpTransportProperties: Record<string, Schema> = {
     insecure: {
         description: 'Disable SSL/TLS certificate verification. Only for debugging purpose.',
         type: 'boolean',
@@ -72,6 +72,53 @@ const gitOptionsProperties: Record<string, Schema> = {
     }
 }
 
+const registryProperties: Record<string, Schema> = {
+    version: {
+        ...notEmptyString,
+        description: 'Version requirement for the package. Defaults to the latest version.'
+    },
+    token: {
+        ...notEmptyString,
+        description: 'Token to use when authenticating to the registry.'
+    },
+    insecure: {
+        description: 'Disable SSL/TLS certificate verification. Only for debugging purpose.',
+        type: 'boolean',
+        default: false
+    }
+}
+
+export const httpAuthentication = {
+    oneOf: [
+        strictObject({
+            description: 'HTTP basic authentication.',
+            properties: {
+                mode: { const: 'Basic' },
+                username: notEmptyString,
+                password: notEmptyString
+            },
+            required: ['mode', 'username', 'password']
+        }),
+        strictObject({
+            description: 'HTTP digest authentication.',
+            properties: {
+                mode: { const: 'Digest' },
+                username: notEmptyString,
+                password: notEmptyString
+            },
+            required: ['mode', 'username', 'password']
+        }),
+        strictObject({
+            description: 'HTTP bearer token authentication.',
+            properties: {
+                mode: { const: 'Bearer' },
+                token: notEmptyString
+            },
+            required: ['mode', 'token']
+        })
+    ]
+} as const satisfies Schema
+
 const sshAuthentication = {
     oneOf: [
         strictObject({
@@ -159,6 +206,33 @@ export const executorSchema = {
             },
             required: ['url', 'format'],
         }),
+        strictObject({
+            properties: {
+                url: {
+                    description: 'Tarball HTTP URL',
+                    type: 'string',
+                    pattern: '^https?://.+$'
+                },
+                format: {
+                    description: 'Tells Blaze that the HTTP resource is a tarball.',
+                    const: 'Tarball'
+                },
+                authentication: {
+                    ...httpAuthentication,
+                    description: 'Authentication to use when downloading the tarball.'
+                },
+                compression: {
+                    enum: ['Deflate', 'Zlib', 'Gzip'],
+                    description: 'Compression format of the tarball. The tarball is considered uncompressed if not provided.'
+                },
+                kind: {
+                    ...executorKindSchema,
+                    description: 'Specify executor type if Blaze cannot infer it.'
+                },
+                ...httpTransportProperties
+            },
+            required: ['url', 'format'],
+        }),
         strictObject({
             properties: {
                 url: {
@@ -173,6 +247,28 @@ export const executorSchema = {
                 ...gitOptionsProperties,
                 ...sshTransportProperties
             }
+        }),
+        strictObject({
+            properties: {
+                url: {
+                    type: 'string',
+                    description: 'NPM package URL.',
+                    pattern: '^npm://.+$'
+                },
+                ...registryProperties
+            },
+            required: ['url']
+        }),
+        strictObject({
+            properties: {
+                url: {
+                    type: 'string',
+                    description: 'Cargo crate URL.',
+                    pattern: '^cargo://.+$'
+                },
+                ...registryProperties
+            },
+            required: ['url']
         })
     ]
 } as const satisfies Schema
\ No newline at end of file
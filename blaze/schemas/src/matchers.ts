@@ -27,7 +27,8 @@ export const fileChangesMatcherSchema = {
                     enum: [
                         'Mixed',
                         'Timestamps',
-                        'Hash'
+                        'Hash',
+                        'Git'
                     ],
                     description: 'Defines the change detection strategy for this matcher.',
                     default: 'Mixed'
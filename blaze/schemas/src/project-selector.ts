@@ -47,6 +47,21 @@ export const projectSelectorSchema = {
                 }
             },
             required: ['include']
+        }),
+        strictObject({
+            properties: {
+                affected: strictObject({
+                    description: 'Select projects affected by changes since a base git revision, along with all projects that depend on them.',
+                    properties: {
+                        base: {
+                            description: 'The base git revision (for e.g main, origin/main or a commit hash). Changes are computed from the merge base of this revision and HEAD.',
+                            ...notEmptyString
+                        }
+                    },
+                    required: ['base']
+                })
+            },
+            required: ['affected']
         })
     ]
 } as const satisfies Schema
\ No newline at end of file
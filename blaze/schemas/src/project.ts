@@ -36,7 +36,85 @@ export const projectSchema: Schema = {
                             type: 'boolean',
                             description: 'Can the target run concurrently (in multiple Blaze processes) ?',
                             default: false
-                        }
+                        },
+                        timeout: strictObject({
+                            description: 'A maximum execution duration for this target. When exceeded, all processes spawned by the target are killed and the target fails.',
+                            properties: {
+                                unit: {
+                                    description: 'The time unit to use.',
+                                    enum: [
+                                        'Milliseconds',
+                                        'Seconds',
+                                        'Minutes',
+                                        'Hours',
+                                        'Days'
+                                    ]
+                                },
+                                amount: {
+                                    description: 'The maximum execution duration.',
+                                    type: 'integer',
+                                    minimum: 1
+                                }
+                            },
+                            required: ['unit', 'amount']
+                        }),
+                        retry: strictObject({
+                            description: 'Execute the target again when it fails.',
+                            properties: {
+                                maxAttempts: {
+                                    description: 'Maximum number of executions, including the first one.',
+                                    type: 'integer',
+                                    minimum: 1
+                                },
+                                delay: strictObject({
+                                    description: 'A duration to wait before executing the target again.',
+                                    properties: {
+                                        unit: {
+                                            description: 'The time unit to use.',
+                                            enum: [
+                                                'Milliseconds',
+                                                'Seconds',
+                                                'Minutes',
+                                                'Hours',
+                                                'Days'
+                                            ]
+                                        },
+                                        amount: {
+                                            description: 'The duration to wait.',
+                                            type: 'integer',
+                                            minimum: 1
+                                        }
+                                    },
+                                    required: ['unit', 'amount']
+                                }),
+                                backoff: {
+                                    description: 'How the delay evolves between attempts. `Fixed` always waits for the same delay, `Exponential` doubles the delay after each attempt.',
+                                    enum: ['Fixed', 'Exponential'],
+                                    default: 'Fixed'
+                                },
+                                exitCodes: {
+                                    description: 'Only retry failures of processes exiting with one of these codes. By default, all failures are retried.',
+                                    type: 'array',
+                                    items: {
+                                        type: 'integer',
+                                        minimum: -2147483648,
+                                        maximum: 2147483647
+                                    }
+                                }
+                            },
+                            required: ['maxAttempts']
+                        }),
+                        resources: strictObject({
+                            default: {},
+                            description: 'Amounts of resources used by this target, indexed by resource pool name. Pools must be declared in the workspace settings. The target will only be executed when enough resources are available in each pool.',
+                            examples: [{ db: 1 }],
+                            patternProperties: {
+                                '^.+$': {
+                                    type: 'integer',
+                                    minimum: 1
+                                }
+                            }
+                        })
                     }
                 })
             }
@@ -56,6 +56,18 @@ export const targetCacheSchema = strictObject({
                     },
                     required: ['program']
                 }),
+                envChanges: strictObject({
+                    description: 'Cache will be invalidated when any of the watched environment variables changes.',
+                    properties: {
+                        variables: {
+                            type: 'array',
+                            description: 'Names of the watched environment variables.',
+                            items: notEmptyString,
+                            uniqueItems: true
+                        }
+                    },
+                    required: ['variables']
+                }),
                 expired: strictObject({
                     description: 'A TTL configuration object. Cache will be invalidated after the given duration.',
                     properties: {
@@ -78,6 +90,17 @@ export const targetCacheSchema = strictObject({
                     required: ['unit', 'amount']
                 })
             }
-        })
+        }),
+        mode: {
+            description: 'How cache entries are stored. `LastState` keeps a single entry validated against the current state, `Fingerprint` keeps an entry for each fingerprint of the target inputs.',
+            enum: ['LastState', 'Fingerprint'],
+            default: 'LastState'
+        },
+        outputs: {
+            type: 'array',
+            description: 'An array of output file matchers. Matched files are archived in the cache store after a successful execution, and restored when the target is cached.',
+            items: fileChangesMatcherSchema,
+            uniqueItems: true
+        }
     }
 })
\ No newline at end of file
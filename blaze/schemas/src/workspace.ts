@@ -1,3 +1,4 @@
+import { httpAuthentication, httpTransportProperties } from './executor.js'
 import { logLevelSchema } from './log-level.js'
 import { parallelismSchema } from './parallelism.js'
 import { projectSelectorSchema } from './project-selector.js'
@@ -9,6 +10,35 @@ const projectPathSchema = {
     ...notEmptyString 
 }
 
+const runHooksSchema = {
+    type: 'array',
+    default: [],
+    items: {
+        oneOf: [
+            {
+                type: 'string',
+                description: 'A target to execute along with its dependencies, formatted as <project>:<target>.',
+                pattern: '^[^:]+:[^:]+$'
+            },
+            strictObject({
+                description: 'Commands executed from the workspace root, with the same options as the std:commands executor.',
+                properties: {
+                    commands: {
+                        type: 'array',
+                        items: {
+                            oneOf: [notEmptyString, { type: 'object' }]
+                        }
+                    },
+                    shell: {
+                        oneOf: [{ const: true }, notEmptyString, { type: 'object' }]
+                    }
+                },
+                required: ['commands']
+            })
+        ]
+    }
+} satisfies Schema
+
 export const workspaceSchema: Schema = {
     $schema: 'https://json-schema.org/draft/2020-12/schema',
     $id: 'https://blaze-monorepo.dev/schemas/workspace',
@@ -69,6 +99,71 @@ export const workspaceSchema: Schema = {
                 resolutionParallelism: {
                     ...parallelismSchema,
                     description: 'The default parallelism level to use when resolving executors.'
+                },
+                failFast: {
+                    type: 'boolean',
+                    description: 'Stop executing targets as soon as one of them fails, instead of only skipping the targets that depend on it.',
+                    default: false
+                },
+                hooks: strictObject({
+                    default: {},
+                    description: 'Hooks executed around runs, in the order they are declared.',
+                    properties: {
+                        beforeRun: {
+                            ...runHooksSchema,
+                            description: 'Hooks executed before targets. The run is aborted if one of them fails.'
+                        },
+                        afterRun: {
+                            ...runHooksSchema,
+                            description: 'Hooks executed after targets, whether the run was successful or not. The run summary is written to their standard input as JSON.'
+                        },
+                        onFailure: {
+                            ...runHooksSchema,
+                            description: 'Hooks executed after targets, only if the run has failed. The run summary is written to their standard input as JSON.'
+                        }
+                    }
+                }),
+                resourcePools: strictObject({
+                    default: {},
+                    description: 'Named resource pools, with the maximum amount of resources that can be used by targets at the same time.',
+                    examples: [{ db: 2, memoryHeavy: 1 }],
+                    patternProperties: {
+                        '^.+$': {
+                            type: 'integer',
+                            minimum: 1
+                        }
+                    }
+                }),
+                cache: {
+                    description: 'Where target execution cache states are stored. Defaults to the .blaze/cache folder of the workspace.',
+                    oneOf: [
+                        strictObject({
+                            description: 'Store cache entries in a local directory.',
+                            properties: {
+                                path: {
+                                    ...notEmptyString,
+                                    description: 'Path to the cache directory, relative to the workspace root.'
+                                }
+                            },
+                            required: ['path']
+                        }),
+                        strictObject({
+                            description: 'Store cache entries on an HTTP server.',
+                            properties: {
+                                url: {
+                                    type: 'string',
+                                    description: 'Base URL for cache entries.',
+                                    pattern: '^https?://.+$'
+                                },
+                                authentication: {
+                                    ...httpAuthentication,
+                                    description: 'Authentication to use when sending requests to the cache server.'
+                                },
+                                ...httpTransportProperties
+                            },
+                            required: ['url']
+                        })
+                    ]
                 }
             }
         })
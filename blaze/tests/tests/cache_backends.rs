@@ -0,0 +1,129 @@
+use blaze_core::time::{now, set_current_time};
+use blaze_core::SelectorSource;
+use blaze_core::{common::selector::ProjectSelector, run, GlobalOptions, RunOptions};
+use std::{path::Path, time::Duration};
+use testing::{
+    with_test_workspace, CacheServer, Executions, ExpectedExecution, TestWorkspaceConfiguration,
+};
+
+use serde_json::json;
+
+mod testing;
+
+fn workspace_with_cache(cache: serde_json::Value) -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace-name",
+            "projects": {
+                "project-name": "project-root"
+            },
+            "settings": {
+                "cache": cache
+            }
+        }),
+        [(
+            "project-root",
+            json!({
+                "targets": {
+                    "build": {
+                        "executor": "std:commands",
+                        "options": {
+                            "commands": [
+                                "echo 'some compiled code' >> artifact.bin"
+                            ],
+                            "shell": true
+                        },
+                        "cache": {}
+                    }
+                }
+            }),
+        )],
+        [],
+    )
+}
+
+fn run_cached_target(root: &Path) -> Executions {
+    let results = run(
+        root,
+        RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+            ProjectSelector::array(["project-name"]),
+        )),
+        GlobalOptions::default(),
+    );
+    Executions::from_run_result(results)
+}
+
+#[test]
+fn local_directory_backend() {
+    with_test_workspace(
+        workspace_with_cache(json!({ "path": "shared-cache" })),
+        |root| {
+            run_cached_target(root)
+                .assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            assert!(
+                std::fs::read_dir(root.join("shared-cache"))
+                    .expect("could not read cache directory")
+                    .count()
+                    > 0
+            );
+
+            run_cached_target(root)
+                .assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "some compiled code\n",
+                std::fs::read_to_string(root.join("project-root/artifact.bin"))
+                    .expect("failed to read test file")
+            );
+        },
+    )
+}
+
+#[test]
+fn http_backend() {
+    let server = CacheServer::start();
+
+    with_test_workspace(
+        workspace_with_cache(json!({ "url": server.url() })),
+        |root| {
+            run_cached_target(root)
+                .assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            assert!(server.entries_count() > 0);
+
+            run_cached_target(root)
+                .assert_targets([("project-name:build", ExpectedExecution::cached())]);
+        },
+    );
+
+    // another workspace pointing to the same server reuses the cached state
+    with_test_workspace(
+        workspace_with_cache(json!({ "url": server.url() })),
+        |root| {
+            run_cached_target(root)
+                .assert_targets([("project-name:build", ExpectedExecution::cached())]);
+        },
+    );
+}
+
+#[test]
+fn http_backend_index_is_not_updated_when_restoring() {
+    let server = CacheServer::start();
+
+    with_test_workspace(
+        workspace_with_cache(json!({ "url": server.url() })),
+        |root| {
+            run_cached_target(root)
+                .assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            let index = server.entry("index").expect("index must be written");
+
+            set_current_time(now() + Duration::from_secs(60));
+            run_cached_target(root)
+                .assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            assert_eq!(server.entry("index"), Some(index));
+        },
+    );
+}
@@ -71,7 +71,7 @@ fn command_fails() {
         |root| {
             let run_cached_target = || -> Executions {
                 let results = run(
-                    &root,
+                    root,
                     RunOptions::new("build").with_selector_source(SelectorSource::Provided(
                         ProjectSelector::array(["project-name"]),
                     )),
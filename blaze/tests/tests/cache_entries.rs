@@ -0,0 +1,147 @@
+mod testing;
+
+use std::{path::Path, time::Duration};
+
+use blaze_core::{
+    common::selector::ProjectSelector,
+    gc_cache, list_cache_entries, run, show_cache_entries,
+    time::{now, set_current_time},
+    GcCacheOptions, RunOptions, SelectorSource,
+};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn setup() -> TestWorkspaceConfiguration {
+    let cached_target = json!({
+        "executor": "std:commands",
+        "options": {
+            "commands": ["echo built"],
+            "shell": true
+        },
+        "cache": {}
+    });
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "project": "project"
+            }
+        }),
+        [(
+            "project",
+            json!({
+                "targets": {
+                    "build": cached_target,
+                    "test": cached_target
+                }
+            }),
+        )],
+        [],
+    )
+}
+
+fn run_target(root: &Path, target: &str) -> Executions {
+    Executions::from_run_result(run(
+        root,
+        RunOptions::new(target).with_selector_source(SelectorSource::Provided(
+            ProjectSelector::array(["project"]),
+        )),
+        Default::default(),
+    ))
+}
+
+#[test]
+fn entries_are_listed_with_their_keys() {
+    with_test_workspace(setup(), |root| {
+        run_target(root, "build").assert_targets([("project:build", ExpectedExecution::success())]);
+
+        let entries = list_cache_entries(root, Default::default()).expect("could not list entries");
+
+        let state = entries
+            .iter()
+            .find(|details| details.double() == Some("project:build"))
+            .expect("execution state must be listed");
+        assert_eq!(state.entry().key(), "executions/project:build");
+        assert!(state.entry().size() > 0);
+        assert!(state.state().is_some());
+
+        let shown = show_cache_entries(root, "project:build", Default::default())
+            .expect("could not show entries");
+        assert_eq!(shown.len(), 1);
+        assert_eq!(
+            shown[0].state().map(|state| state.hash),
+            state.state().map(|state| state.hash)
+        );
+
+        assert!(show_cache_entries(root, "project:test", Default::default()).is_err());
+    })
+}
+
+#[test]
+fn unused_entries_are_removed() {
+    with_test_workspace(setup(), |root| {
+        set_current_time(now());
+        run_target(root, "build").assert_targets([("project:build", ExpectedExecution::success())]);
+
+        set_current_time(now() + Duration::from_secs(3600));
+        run_target(root, "test").assert_targets([("project:test", ExpectedExecution::success())]);
+
+        let removed = gc_cache(
+            root,
+            GcCacheOptions::new().with_older_than(Duration::from_secs(60)),
+            Default::default(),
+        )
+        .expect("could not remove entries");
+
+        let removed_keys = removed.iter().map(|entry| entry.key()).collect::<Vec<_>>();
+        assert!(removed_keys.contains(&"executions/project:build"));
+        assert!(!removed_keys.contains(&"executions/project:test"));
+
+        run_target(root, "build").assert_targets([("project:build", ExpectedExecution::success())]);
+        run_target(root, "test").assert_targets([("project:test", ExpectedExecution::cached())]);
+    })
+}
+
+#[test]
+fn least_recently_used_entries_are_removed_above_max_size() {
+    with_test_workspace(setup(), |root| {
+        set_current_time(now());
+        run_target(root, "build").assert_targets([("project:build", ExpectedExecution::success())]);
+
+        set_current_time(now() + Duration::from_secs(1));
+        run_target(root, "test").assert_targets([("project:test", ExpectedExecution::success())]);
+
+        set_current_time(now() + Duration::from_secs(1));
+        run_target(root, "build").assert_targets([("project:build", ExpectedExecution::cached())]);
+
+        let total_size = list_cache_entries(root, Default::default())
+            .expect("could not list entries")
+            .iter()
+            .map(|details| details.entry().size())
+            .sum::<u64>();
+
+        let removed = gc_cache(
+            root,
+            GcCacheOptions::new().with_max_size(total_size - 1),
+            Default::default(),
+        )
+        .expect("could not remove entries");
+
+        let removed_keys = removed.iter().map(|entry| entry.key()).collect::<Vec<_>>();
+        assert!(!removed_keys.is_empty());
+        assert!(!removed_keys.contains(&"executions/project:build"));
+
+        let remaining =
+            list_cache_entries(root, Default::default()).expect("could not list entries");
+        assert!(
+            remaining
+                .iter()
+                .map(|details| details.entry().size())
+                .sum::<u64>()
+                < total_size
+        );
+        assert!(remaining
+            .iter()
+            .all(|details| !removed_keys.contains(&details.entry().key())));
+    })
+}
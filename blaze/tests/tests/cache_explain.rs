@@ -0,0 +1,173 @@
+mod testing;
+
+use std::{path::Path, time::Duration};
+
+use blaze_core::{
+    common::selector::ProjectSelector,
+    explain_cache, run,
+    time::{now, set_current_time},
+    CacheStatus, ExplainCacheOptions, FileChangeKind, InvalidationReason, RunOptions,
+    SelectorSource,
+};
+use filetime::{set_file_mtime, FileTime};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn setup() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "app": "app",
+                "lib": "lib"
+            }
+        }),
+        [
+            (
+                "app",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["true"],
+                                "shell": true
+                            },
+                            "dependencies": [{ "target": "build", "projects": ["lib"] }],
+                            "cache": {}
+                        }
+                    }
+                }),
+            ),
+            (
+                "lib",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["true"],
+                                "shell": true
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "inputChanges": ["source.txt"]
+                                }
+                            }
+                        }
+                    }
+                }),
+            ),
+        ],
+        [],
+    )
+}
+
+fn run_app(root: &Path) -> Executions {
+    Executions::from_run_result(run(
+        root,
+        RunOptions::new("build")
+            .with_selector_source(SelectorSource::Provided(ProjectSelector::array(["app"]))),
+        Default::default(),
+    ))
+}
+
+fn explain(root: &Path) -> Vec<(String, CacheStatus)> {
+    explain_cache(
+        root,
+        ExplainCacheOptions::new("app:build"),
+        Default::default(),
+    )
+    .expect("cache must be explained")
+    .execution()
+    .iter()
+    .map(|(double, node)| {
+        let status = match node.result.as_ref().expect("target must be evaluated") {
+            Ok(CacheStatus::NoCache) => CacheStatus::NoCache,
+            Ok(CacheStatus::Cached) => CacheStatus::Cached,
+            Ok(CacheStatus::Invalidated(reasons)) => CacheStatus::Invalidated(reasons.clone()),
+            Err(err) => panic!("could not evaluate {double}: {err:#}"),
+        };
+        (double.clone(), status)
+    })
+    .collect()
+}
+
+#[cfg(not(windows))]
+#[test]
+fn invalidation_reasons_are_explained() {
+    with_test_workspace(setup(), |root| {
+        let source_path = root.join("lib/source.txt");
+        std::fs::write(&source_path, "first\n").expect("could not write source file");
+        set_file_mtime(&source_path, FileTime::from_system_time(now()))
+            .expect("could not set modified date");
+
+        let statuses = explain(root);
+        assert_eq!(statuses.len(), 2);
+        for (double, status) in &statuses {
+            match status {
+                CacheStatus::Invalidated(reasons) => assert!(
+                    matches!(reasons.as_slice(), [InvalidationReason::NotCached]),
+                    "unexpected reasons for {double}: {reasons:?}"
+                ),
+                _ => panic!("{double} must not be cached"),
+            }
+        }
+
+        run_app(root).assert_targets([
+            ("lib:build", ExpectedExecution::success()),
+            ("app:build", ExpectedExecution::success()),
+        ]);
+
+        for (double, status) in explain(root) {
+            assert!(
+                matches!(status, CacheStatus::Cached),
+                "{double} must be cached"
+            );
+        }
+
+        let change_time = now() + Duration::from_secs(1);
+        set_current_time(change_time);
+        std::fs::write(&source_path, "second\n").expect("could not write source file");
+        set_file_mtime(&source_path, FileTime::from_system_time(change_time))
+            .expect("could not set modified date");
+
+        let statuses = explain(root);
+
+        let (_, lib_status) = statuses
+            .iter()
+            .find(|(double, _)| double == "lib:build")
+            .expect("lib:build must be evaluated");
+        match lib_status {
+            CacheStatus::Invalidated(reasons) => match reasons.as_slice() {
+                [InvalidationReason::InputFilesChanged { files }] => {
+                    assert_eq!(files.len(), 1);
+                    assert_eq!(files[0].path, Path::new("source.txt"));
+                    assert_eq!(files[0].change, FileChangeKind::Modified);
+                }
+                _ => panic!("unexpected reasons for lib:build: {reasons:?}"),
+            },
+            _ => panic!("lib:build cache must be invalidated"),
+        }
+
+        let (_, app_status) = statuses
+            .iter()
+            .find(|(double, _)| double == "app:build")
+            .expect("app:build must be evaluated");
+        match app_status {
+            CacheStatus::Invalidated(reasons) => match reasons.as_slice() {
+                [InvalidationReason::DependenciesChanged { dependencies }] => {
+                    assert_eq!(dependencies, &["lib:build"]);
+                }
+                _ => panic!("unexpected reasons for app:build: {reasons:?}"),
+            },
+            _ => panic!("app:build cache must be invalidated"),
+        }
+
+        // explaining the cache must not execute anything
+        run_app(root).assert_targets([
+            ("lib:build", ExpectedExecution::success()),
+            ("app:build", ExpectedExecution::success()),
+        ]);
+    })
+}
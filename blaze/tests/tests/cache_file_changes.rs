@@ -52,7 +52,7 @@ fn single_input_file() {
         |root| {
             let run_cached_target = || -> Executions {
                 let results = run(
-                    &root,
+                    root,
                     RunOptions::new("target-name").with_selector_source(SelectorSource::Provided(
                         ProjectSelector::array(["project-name"]),
                     )),
@@ -141,7 +141,7 @@ fn exclude_input_files() {
         |root| {
             let run_cached_target = || -> Executions {
                 let results = run(
-                    &root,
+                    root,
                     RunOptions::new("target-name").with_selector_source(SelectorSource::Provided(
                         ProjectSelector::array(["project-name"]),
                     )),
@@ -193,3 +193,173 @@ fn exclude_input_files() {
         },
     )
 }
+
+#[cfg(not(windows))]
+#[test]
+fn git_behavior() {
+    use blaze_core::SelectorSource;
+    use testing::cmd;
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "target-name": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "echo 'Hello world!' >> {{ project.root }}/history.txt"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "inputChanges": [
+                                        {
+                                            "pattern": "src/**",
+                                            "behavior": "Git"
+                                        }
+                                    ]
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let run_cached_target = || -> Executions {
+                let results = run(
+                    root,
+                    RunOptions::new("target-name").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    GlobalOptions::default(),
+                );
+                Executions::from_run_result(results)
+            };
+
+            let git = |args: &str| {
+                cmd(format!(
+                    "git -C '{}' -c user.name=test -c user.email=test@example.com {args}",
+                    root.display()
+                ))
+            };
+
+            let source_path = root.join("project-root/src/main.txt");
+            std::fs::create_dir_all(source_path.parent().unwrap())
+                .expect("could not create source directory");
+            std::fs::write(&source_path, "initial").expect("could not write source file");
+            std::fs::write(root.join(".gitignore"), ".blaze\nhistory.txt\n")
+                .expect("could not write .gitignore");
+            git("init -q -b main");
+            git("add -A");
+            git("commit -q -m initial");
+
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::success())]);
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::cached())]);
+
+            // modified tracked file
+            std::fs::write(&source_path, "modified content").expect("could not write source file");
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::success())]);
+
+            // committing the modification does not change the file content id
+            git("commit -q -a -m modified");
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::cached())]);
+
+            // untracked file
+            std::fs::write(root.join("project-root/src/new.txt"), "new")
+                .expect("could not write new file");
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::success())]);
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "Hello world!\n".repeat(3),
+                std::fs::read_to_string(root.join("project-root/history.txt"))
+                    .expect("could not read history file")
+            );
+        },
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn git_behavior_outside_repository() {
+    use blaze_core::SelectorSource;
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "target-name": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["true"],
+                                "shell": true
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "inputChanges": [
+                                        {
+                                            "pattern": "source.txt",
+                                            "behavior": "Git"
+                                        }
+                                    ]
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [("files/source.txt", "project-root/")],
+        ),
+        |root| {
+            let run_cached_target = || -> Executions {
+                let results = run(
+                    root,
+                    RunOptions::new("target-name").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    GlobalOptions::default(),
+                );
+                Executions::from_run_result(results)
+            };
+
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::success())]);
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::cached())]);
+
+            std::fs::write(
+                root.join("project-root/source.txt"),
+                "Content has changed !\n",
+            )
+            .expect("could not write source file");
+            run_cached_target()
+                .assert_targets([("project-name:target-name", ExpectedExecution::success())]);
+        },
+    )
+}
@@ -0,0 +1,162 @@
+use std::time::{Duration, SystemTime};
+
+use blaze_core::{
+    common::selector::ProjectSelector, list_cache_entries, run, GlobalOptions, RunOptions,
+    SelectorSource,
+};
+use filetime::{set_file_mtime, FileTime};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+mod testing;
+
+#[cfg(not(windows))]
+#[test]
+fn file_hashes_are_stored_in_cache() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["true"],
+                                "shell": true
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "inputChanges": [
+                                        {
+                                            "pattern": "source.txt",
+                                            "behavior": "Hash"
+                                        }
+                                    ]
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let run_build = || -> Executions {
+                Executions::from_run_result(run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    GlobalOptions::default(),
+                ))
+            };
+
+            let source_path = root.join("project/source.txt");
+
+            // checksums of recently modified files are not cached.
+            std::fs::write(&source_path, "first").expect("could not write source file");
+            set_file_mtime(
+                &source_path,
+                FileTime::from_system_time(SystemTime::now() - Duration::from_secs(3600)),
+            )
+            .expect("could not set modified date");
+
+            run_build().assert_targets([("project:build", ExpectedExecution::success())]);
+            run_build().assert_targets([("project:build", ExpectedExecution::cached())]);
+
+            assert!(list_cache_entries(root, Default::default())
+                .expect("could not list cache entries")
+                .iter()
+                .any(|details| details.entry().key() == "file-hashes"));
+
+            std::fs::write(&source_path, "second content").expect("could not write source file");
+            set_file_mtime(
+                &source_path,
+                FileTime::from_system_time(SystemTime::now() - Duration::from_secs(1800)),
+            )
+            .expect("could not set modified date");
+
+            run_build().assert_targets([("project:build", ExpectedExecution::success())]);
+            run_build().assert_targets([("project:build", ExpectedExecution::cached())]);
+        },
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn files_with_unchanged_metadata_are_not_read_again() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["true"],
+                                "shell": true
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "inputChanges": [
+                                        {
+                                            "pattern": "source.txt",
+                                            "behavior": "Hash"
+                                        }
+                                    ]
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let run_build = || -> Executions {
+                Executions::from_run_result(run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    GlobalOptions::default(),
+                ))
+            };
+
+            let source_path = root.join("project/source.txt");
+            let mtime = SystemTime::now() - Duration::from_secs(3600);
+
+            std::fs::write(&source_path, "first").expect("could not write source file");
+            set_file_mtime(&source_path, FileTime::from_system_time(mtime))
+                .expect("could not set modified date");
+
+            run_build().assert_targets([("project:build", ExpectedExecution::success())]);
+
+            // same size, same modification time and same inode: the cached checksum is used.
+            std::fs::write(&source_path, "other").expect("could not write source file");
+            set_file_mtime(&source_path, FileTime::from_system_time(mtime))
+                .expect("could not set modified date");
+
+            run_build().assert_targets([("project:build", ExpectedExecution::cached())]);
+
+            // recently modified files are always read.
+            std::fs::write(&source_path, "third").expect("could not write source file");
+
+            run_build().assert_targets([("project:build", ExpectedExecution::success())]);
+        },
+    )
+}
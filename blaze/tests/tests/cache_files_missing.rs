@@ -46,7 +46,7 @@ fn files_missing() {
         |root| {
             let run_cached_target = || -> Executions {
                 let results = run(
-                    &root,
+                    root,
                     RunOptions::new("build").with_selector_source(SelectorSource::Provided(
                         ProjectSelector::array(["project-name"]),
                     )),
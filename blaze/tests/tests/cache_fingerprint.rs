@@ -0,0 +1,148 @@
+use blaze_core::SelectorSource;
+use blaze_core::{common::selector::ProjectSelector, run, GlobalOptions, RunOptions};
+use testing::{
+    get_fixtures_root, with_test_workspace, CacheServer, Executions, ExpectedExecution,
+    TestWorkspaceConfiguration,
+};
+
+use serde_json::json;
+
+mod testing;
+
+#[test]
+fn previously_seen_inputs_are_cached() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "cat '{{ project.root }}/src/input.txt' >> '{{ project.root }}/runs.log'"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "mode": "Fingerprint",
+                                "invalidateWhen": {
+                                    "inputChanges": ["src/**"]
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let run_cached_target = || -> Executions {
+                let results = run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    GlobalOptions::default(),
+                );
+                Executions::from_run_result(results)
+            };
+
+            let project_root = root.join("project-root");
+            let input_path = project_root.join("src/input.txt");
+            std::fs::create_dir_all(project_root.join("src")).expect("could not create directory");
+
+            std::fs::write(&input_path, "a\n").expect("could not write test file");
+            run_cached_target()
+                .assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            std::fs::write(&input_path, "b\n").expect("could not write test file");
+            run_cached_target()
+                .assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            // back to the first state
+            std::fs::write(&input_path, "a\n").expect("could not write test file");
+            run_cached_target()
+                .assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            std::fs::write(&input_path, "b\n").expect("could not write test file");
+            run_cached_target()
+                .assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "a\nb\n",
+                std::fs::read_to_string(project_root.join("runs.log"))
+                    .expect("failed to read test file")
+            );
+        },
+    )
+}
+
+#[test]
+#[cfg(not(target_env = "musl"))]
+fn custom_executor_fingerprints_are_shared_across_workspaces() {
+    let server = CacheServer::start();
+    let executor_root = get_fixtures_root().join("executors/rust-checker");
+
+    let configuration = || {
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                },
+                "settings": {
+                    "cache": { "url": server.url() }
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": format!("file://{}", executor_root.display()),
+                            "options": {
+                                "number": 1,
+                                "string": "hello",
+                                "bool": true,
+                                "array": [1, 2, 3],
+                                "null": null,
+                                "float": 1.0
+                            },
+                            "cache": {
+                                "mode": "Fingerprint"
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        )
+    };
+
+    let run_cached_target = |root: &std::path::Path| -> Executions {
+        Executions::from_run_result(run(
+            root,
+            RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                ProjectSelector::array(["project"]),
+            )),
+            GlobalOptions::default(),
+        ))
+    };
+
+    with_test_workspace(configuration(), |root| {
+        run_cached_target(root).assert_targets([("project:target", ExpectedExecution::success())]);
+    });
+
+    // the executor is resolved again in another workspace, it must lead to the same fingerprint.
+    with_test_workspace(configuration(), |root| {
+        run_cached_target(root).assert_targets([("project:target", ExpectedExecution::cached())]);
+    });
+}
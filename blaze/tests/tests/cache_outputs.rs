@@ -0,0 +1,95 @@
+use blaze_core::SelectorSource;
+use blaze_core::{common::selector::ProjectSelector, run, GlobalOptions, RunOptions};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+use serde_json::json;
+
+mod testing;
+
+#[test]
+fn restore_outputs() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root"
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "echo 'build' >> '{{ project.root }}/runs.log'",
+                                    "mkdir -p '{{ project.root }}/dist/nested'",
+                                    "echo 'some compiled code' > '{{ project.root }}/dist/artifact.bin'",
+                                    "echo 'some other code' > '{{ project.root }}/dist/nested/other.bin'"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "outputs": ["dist/**"]
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let run_cached_target = || -> Executions {
+                let results = run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    GlobalOptions::default(),
+                );
+                Executions::from_run_result(results)
+            };
+
+            let project_root = root.join("project-root");
+
+            run_cached_target()
+                .assert_targets([("project-name:build", ExpectedExecution::success())]);
+
+            std::fs::remove_dir_all(project_root.join("dist")).expect("could not remove outputs");
+
+            run_cached_target()
+                .assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "some compiled code\n",
+                std::fs::read_to_string(project_root.join("dist/artifact.bin"))
+                    .expect("failed to read test file")
+            );
+            assert_eq!(
+                "some other code\n",
+                std::fs::read_to_string(project_root.join("dist/nested/other.bin"))
+                    .expect("failed to read test file")
+            );
+            assert_eq!(
+                "build\n",
+                std::fs::read_to_string(project_root.join("runs.log"))
+                    .expect("failed to read test file")
+            );
+
+            // modified outputs are restored as well
+            std::fs::write(project_root.join("dist/artifact.bin"), "modified")
+                .expect("could not write test file");
+
+            run_cached_target()
+                .assert_targets([("project-name:build", ExpectedExecution::cached())]);
+
+            assert_eq!(
+                "some compiled code\n",
+                std::fs::read_to_string(project_root.join("dist/artifact.bin"))
+                    .expect("failed to read test file")
+            );
+        },
+    )
+}
@@ -224,3 +224,102 @@ false
         },
     )
 }
+
+#[cfg(not(windows))]
+#[test]
+fn on_output_change() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace-name",
+                "projects": {
+                    "project-name": "project-root",
+                }
+            }),
+            [(
+                "project-root",
+                json!({
+                    "targets": {
+                        "a": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "echo 'Hello world!' >> '{{ project.root }}/a.txt'"
+                                ],
+                                "shell": true
+                            },
+                            "dependencies": [
+                                {
+                                    "target": "b",
+                                    "cachePropagation": "OnOutputChange"
+                                }
+                            ],
+                            "cache": {}
+                        },
+                        "b": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [
+                                    "grep -v '^#' '{{ project.root }}/source.txt' > '{{ project.root }}/b.txt'"
+                                ],
+                                "shell": true
+                            },
+                            "cache": {
+                                "invalidateWhen": {
+                                    "inputChanges": ["source.txt"]
+                                },
+                                "outputs": ["b.txt"]
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let source_path = root.join("project-root/source.txt");
+
+            let run_target = || {
+                Executions::from_run_result(run(
+                    root,
+                    RunOptions::new("a").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project-name"]),
+                    )),
+                    GlobalOptions::default(),
+                ))
+            };
+
+            std::fs::write(&source_path, "content\n").expect("could not write source file");
+            run_target().assert_targets([
+                ("project-name:a", ExpectedExecution::success()),
+                ("project-name:b", ExpectedExecution::success()),
+            ]);
+
+            // dependency is executed again, but produces the same outputs
+            std::fs::write(&source_path, "# comment\ncontent\n")
+                .expect("could not write source file");
+            run_target().assert_targets([
+                ("project-name:a", ExpectedExecution::cached()),
+                ("project-name:b", ExpectedExecution::success()),
+            ]);
+
+            std::fs::write(&source_path, "# comment\nother content\n")
+                .expect("could not write source file");
+            run_target().assert_targets([
+                ("project-name:a", ExpectedExecution::success()),
+                ("project-name:b", ExpectedExecution::success()),
+            ]);
+
+            run_target().assert_targets([
+                ("project-name:a", ExpectedExecution::cached()),
+                ("project-name:b", ExpectedExecution::cached()),
+            ]);
+
+            assert_eq!(
+                "Hello world!\n".repeat(2),
+                std::fs::read_to_string(root.join("project-root/a.txt"))
+                    .expect("could not read a.txt")
+            );
+        },
+    )
+}
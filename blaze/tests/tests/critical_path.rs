@@ -0,0 +1,79 @@
+mod testing;
+
+use std::path::Path;
+
+use blaze_core::{
+    common::{parallelism::Parallelism, selector::ProjectSelector},
+    run, RunOptions, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn build_target(command: &str, dependencies: Value) -> Value {
+    json!({
+        "targets": {
+            "build": {
+                "executor": "std:commands",
+                "options": {
+                    "commands": [command],
+                    "shell": true
+                },
+                "dependencies": dependencies
+            }
+        }
+    })
+}
+
+fn run_sequentially(root: &Path) -> Vec<String> {
+    Executions::from_run_result(run(
+        root,
+        RunOptions::new("build")
+            .with_selector_source(SelectorSource::Provided(ProjectSelector::all()))
+            .with_parallelism(Parallelism::None),
+        Default::default(),
+    ))
+    .assert_targets([
+        ("a:build", ExpectedExecution::success()),
+        ("y:build", ExpectedExecution::success()),
+        ("z:build", ExpectedExecution::success()),
+    ]);
+
+    let order = std::fs::read_to_string(root.join("order")).expect("could not read order file");
+    std::fs::remove_file(root.join("order")).expect("could not remove order file");
+    order.lines().map(str::to_owned).collect()
+}
+
+#[test]
+fn targets_on_the_longest_path_are_executed_first() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "a": "a",
+                    "y": "y",
+                    "z": "z"
+                }
+            }),
+            [
+                ("a", build_target("echo a >> ../order", json!([]))),
+                ("y", build_target("echo y >> ../order", json!([]))),
+                (
+                    "z",
+                    build_target(
+                        "sleep 0.5; echo z >> ../order",
+                        json!([{ "target": "build", "projects": ["y"] }]),
+                    ),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            // without any history, ready targets are executed in alphabetical order.
+            assert_eq!(vec!["a", "y", "z"], run_sequentially(root));
+
+            // y:build and z:build are now known to be the longest path.
+            assert_eq!(vec!["y", "z", "a"], run_sequentially(root));
+        },
+    )
+}
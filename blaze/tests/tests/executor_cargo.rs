@@ -0,0 +1,275 @@
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use flate2::{write::GzEncoder, Compression};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use testing::{with_test_workspace, FileServer, TestWorkspaceConfiguration};
+
+mod testing;
+
+const INDEX_FILE_PATH: &str = "/my/-e/my-executor";
+
+/// Build a crate archive which only contains a file with the crate version.
+fn crate_archive(version: &str) -> Vec<u8> {
+    let mut builder = tar::Builder::new(GzEncoder::new(vec![], Compression::default()));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(version.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(
+            &mut header,
+            format!("my-executor-{version}/version.txt"),
+            version.as_bytes(),
+        )
+        .expect("could not append file to crate archive");
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .expect("could not build crate archive")
+}
+
+fn checksum(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[test]
+fn greatest_matching_release_is_downloaded() {
+    let registry = FileServer::start();
+
+    let index = [
+        ("1.0.0", false),
+        ("1.2.0", false),
+        ("1.3.0", true),
+        ("2.0.0", false),
+    ]
+    .into_iter()
+    .map(|(version, yanked)| {
+        let archive = crate_archive(version);
+        registry.serve(
+            format!("/crates/my-executor/{version}/download"),
+            archive.clone(),
+            None,
+        );
+        json!({
+            "name": "my-executor",
+            "vers": version,
+            "cksum": checksum(&archive),
+            "yanked": yanked
+        })
+        .to_string()
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+
+    registry.serve(INDEX_FILE_PATH, index, None);
+    registry.serve(
+        "/config.json",
+        json!({ "dl": format!("{}/crates", registry.url()) }).to_string(),
+        None,
+    );
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": format!("cargo://{}/my-executor", registry.address()),
+                                "version": "^1"
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            // the downloaded crate is not a valid executor, so loading it fails.
+            let _ = run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            );
+
+            let downloads = registry
+                .requests()
+                .into_iter()
+                .filter(|request| request.path.starts_with("/crates/"))
+                .map(|request| (request.path, request.status))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                downloads,
+                [("/crates/my-executor/1.2.0/download".to_owned(), 200)]
+            );
+
+            let unpacked = std::fs::read_dir(root.join(".blaze/cargo"))
+                .expect("could not read crates folder")
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path().join("my-executor-1.2.0/version.txt"))
+                .find(|path| path.exists())
+                .expect("crate must be unpacked");
+            assert_eq!(
+                std::fs::read_to_string(unpacked).expect("could not read unpacked file"),
+                "1.2.0"
+            );
+        },
+    );
+}
+
+#[test]
+fn checksum_mismatch_is_rejected() {
+    let registry = FileServer::start();
+
+    registry.serve(
+        "/crates/my-executor/1.0.0/download",
+        crate_archive("1.0.0"),
+        None,
+    );
+    registry.serve(
+        INDEX_FILE_PATH,
+        json!({
+            "name": "my-executor",
+            "vers": "1.0.0",
+            "cksum": checksum(b"other content"),
+            "yanked": false
+        })
+        .to_string(),
+        None,
+    );
+    registry.serve(
+        "/config.json",
+        json!({ "dl": format!("{}/crates", registry.url()) }).to_string(),
+        None,
+    );
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": format!("cargo://{}/my-executor", registry.address())
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let error = run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            )
+            .err()
+            .map(|err| format!("{err:?}"))
+            .expect("executor resolution must fail");
+
+            assert!(
+                error.contains("checksum mismatch for crate my-executor 1.0.0"),
+                "{error}"
+            );
+            assert!(!root.join(".blaze/cargo").exists());
+        },
+    );
+}
+
+#[test]
+fn token_is_only_sent_to_the_index_host() {
+    let registry = FileServer::start();
+    let downloads = FileServer::start();
+
+    let archive = crate_archive("1.0.0");
+    downloads.serve("/crates/my-executor/1.0.0/download", archive.clone(), None);
+    registry.serve(
+        INDEX_FILE_PATH,
+        json!({
+            "name": "my-executor",
+            "vers": "1.0.0",
+            "cksum": checksum(&archive),
+            "yanked": false
+        })
+        .to_string(),
+        None,
+    );
+
+    for auth_required in [false, true] {
+        registry.serve(
+            "/config.json",
+            json!({
+                "dl": format!("{}/crates", downloads.url()),
+                "auth-required": auth_required
+            })
+            .to_string(),
+            None,
+        );
+
+        with_test_workspace(
+            TestWorkspaceConfiguration::new(
+                json!({
+                    "name": "workspace",
+                    "projects": {
+                        "project": "project"
+                    }
+                }),
+                [(
+                    "project",
+                    json!({
+                        "targets": {
+                            "target": {
+                                "executor": {
+                                    "url": format!("cargo://{}/my-executor", registry.address()),
+                                    "token": "registry-token"
+                                }
+                            }
+                        }
+                    }),
+                )],
+                [],
+            ),
+            |root| {
+                let _ = run(
+                    root,
+                    RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    Default::default(),
+                );
+            },
+        );
+
+        assert!(registry.requests().iter().all(|request| request
+            .headers
+            .get("authorization")
+            .is_some_and(|value| value == "registry-token")));
+
+        let download = downloads
+            .requests()
+            .pop()
+            .expect("crate must be downloaded");
+        assert_eq!(download.status, 200);
+        assert_eq!(
+            download.headers.get("authorization").map(String::as_str),
+            auth_required.then_some("registry-token")
+        );
+    }
+}
@@ -0,0 +1,165 @@
+#![cfg(unix)]
+
+use blaze_core::{
+    common::{
+        executor::{ExecutorReference, Location},
+        selector::ProjectSelector,
+    },
+    run, RunOptions, SelectorSource,
+};
+use serde_json::json;
+use testing::{get_fixtures_root, with_test_workspace, Executions, TestWorkspaceConfiguration};
+
+mod testing;
+
+const TOKEN: &str = "npm-registry-secret";
+
+fn use_fake_npm() {
+    std::env::set_var(
+        "BLAZE_NPM_LOCATION",
+        get_fixtures_root().join("scripts/fake-npm.sh"),
+    );
+}
+
+#[test]
+fn options_are_parsed() {
+    let reference = serde_json::from_value::<ExecutorReference>(json!({
+        "url": "npm://npm.my-company.com/my-executor",
+        "version": "^1.2.0",
+        "insecure": true,
+        "token": TOKEN
+    }))
+    .expect("could not parse executor reference");
+
+    let ExecutorReference::Custom {
+        location: Location::Npm { options },
+        ..
+    } = reference
+    else {
+        panic!("expected an npm executor reference");
+    };
+
+    assert_eq!(options.version(), Some("^1.2.0"));
+    assert!(options.insecure());
+    assert_eq!(options.token(), Some(TOKEN));
+
+    let reference = serde_json::from_value::<ExecutorReference>(json!("npm:///my-executor"))
+        .expect("could not parse executor url");
+
+    assert!(matches!(
+        reference,
+        ExecutorReference::Custom {
+            location: Location::Npm { options },
+            ..
+        } if options == Default::default()
+    ));
+}
+
+#[test]
+fn token_is_not_passed_as_an_argument() {
+    use_fake_npm();
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": "npm://127.0.0.1:4873/my-executor",
+                                "version": "1.0.0",
+                                "insecure": true,
+                                "token": TOKEN
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let executions = Executions::from_run_result(run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            ));
+            executions.assert_global_failure();
+
+            let log = std::fs::read_to_string(root.join("npm.log")).expect("npm was not called");
+            let lines = log.lines().collect::<Vec<_>>();
+
+            assert!(lines[0].starts_with("args: pack my-executor@1.0.0 --json --pack-destination "));
+            assert!(lines[0].ends_with(" --registry=https://127.0.0.1:4873/ --strict-ssl=false"));
+            assert!(!lines[0].contains(TOKEN));
+            assert_eq!(lines[1], format!("token: {TOKEN}"));
+            assert_eq!(
+                lines[2],
+                "config: //127.0.0.1:4873/:_authToken=${BLAZE_NPM_TOKEN}"
+            );
+
+            let npmrc_files = std::fs::read_dir(root.join(".blaze/npm"))
+                .expect("could not read packages folder")
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "npmrc"))
+                .count();
+            assert_eq!(
+                npmrc_files, 0,
+                "temporary npm configuration must be removed"
+            );
+        },
+    );
+}
+
+#[test]
+fn failed_npm_pack_is_reported() {
+    use_fake_npm();
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": "npm:///my-executor",
+                                "token": TOKEN
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let error = run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            )
+            .err()
+            .map(|err| format!("{err:?}"))
+            .expect("executor resolution must fail");
+
+            assert!(error.contains("npm pack failed (exitcode=Some(1))"));
+            assert!(!error.contains(TOKEN));
+        },
+    );
+}
@@ -0,0 +1,361 @@
+use std::path::Path;
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{
+    get_fixtures_root, with_test_workspace, Executions, ExpectedExecution, FileServer,
+    ServerAuthentication, TestWorkspaceConfiguration,
+};
+
+mod testing;
+
+const TARBALL_PATH: &str = "/executor.tar";
+
+/// Build an uncompressed tarball which does not contain a valid executor.
+fn invalid_executor_tarball() -> Vec<u8> {
+    let content = b"not an executor";
+    let mut builder = tar::Builder::new(vec![]);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "executor/file.txt", &content[..])
+        .expect("could not append file to tarball");
+    builder.into_inner().expect("could not build tarball")
+}
+
+fn is_unpacked(root: &Path) -> bool {
+    std::fs::read_dir(root.join(".blaze/tarballs"))
+        .expect("could not read tarballs folder")
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.path().join("executor/file.txt").exists())
+}
+
+#[test]
+fn basic_authentication() {
+    let server = FileServer::start().with_authentication(ServerAuthentication::Basic {
+        username: "user".into(),
+        password: "secret".into(),
+    });
+    server.serve(TARBALL_PATH, invalid_executor_tarball(), None);
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": format!("{}{TARBALL_PATH}", server.url()),
+                                "format": "Tarball",
+                                "authentication": {
+                                    "mode": "Basic",
+                                    "username": "user",
+                                    "password": "secret"
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            // the tarball is not a valid executor, so loading it fails.
+            let _ = run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            );
+
+            let statuses = server
+                .requests()
+                .into_iter()
+                .map(|request| request.status)
+                .collect::<Vec<_>>();
+            assert_eq!(statuses, [200]);
+            assert!(is_unpacked(root));
+        },
+    );
+}
+
+#[test]
+fn bearer_authentication() {
+    let server = FileServer::start().with_authentication(ServerAuthentication::Bearer {
+        token: "secret-token".into(),
+    });
+    server.serve(TARBALL_PATH, invalid_executor_tarball(), None);
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": format!("{}{TARBALL_PATH}", server.url()),
+                                "format": "Tarball",
+                                "authentication": {
+                                    "mode": "Bearer",
+                                    "token": "secret-token"
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let _ = run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            );
+
+            let statuses = server
+                .requests()
+                .into_iter()
+                .map(|request| request.status)
+                .collect::<Vec<_>>();
+            assert_eq!(statuses, [200]);
+            assert!(is_unpacked(root));
+        },
+    );
+}
+
+#[test]
+fn digest_authentication() {
+    let server = FileServer::start().with_authentication(ServerAuthentication::Digest {
+        username: "user".into(),
+        password: "secret".into(),
+    });
+    server.serve(TARBALL_PATH, invalid_executor_tarball(), None);
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": format!("{}{TARBALL_PATH}", server.url()),
+                                "format": "Tarball",
+                                "authentication": {
+                                    "mode": "Digest",
+                                    "username": "user",
+                                    "password": "secret"
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let _ = run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            );
+
+            // the first request gets the challenge, the second one answers it.
+            let statuses = server
+                .requests()
+                .into_iter()
+                .map(|request| request.status)
+                .collect::<Vec<_>>();
+            assert_eq!(statuses, [401, 200]);
+            assert!(is_unpacked(root));
+        },
+    );
+}
+
+#[test]
+fn wrong_credentials_are_rejected() {
+    let server = FileServer::start().with_authentication(ServerAuthentication::Digest {
+        username: "user".into(),
+        password: "secret".into(),
+    });
+    server.serve(TARBALL_PATH, invalid_executor_tarball(), None);
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": format!("{}{TARBALL_PATH}", server.url()),
+                                "format": "Tarball",
+                                "authentication": {
+                                    "mode": "Digest",
+                                    "username": "user",
+                                    "password": "wrong"
+                                }
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            let error = run(
+                root,
+                RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["project"]),
+                )),
+                Default::default(),
+            )
+            .err()
+            .map(|err| format!("{err:?}"))
+            .expect("executor resolution must fail");
+
+            assert!(error.contains("could not download tarball"));
+            assert!(!root.join(".blaze/tarballs").exists());
+        },
+    );
+}
+
+#[test]
+#[cfg(not(target_env = "musl"))]
+fn not_modified_tarball_is_reused() {
+    let executor_root = get_fixtures_root().join("executors/rust-checker");
+    let devkit_root = executor_root
+        .join("../../../../../rust/devkit")
+        .canonicalize()
+        .expect("could not find devkit");
+
+    // the executor manifest refers to the devkit with a relative path, which must be made absolute.
+    let manifest = std::fs::read_to_string(executor_root.join("Cargo.toml"))
+        .expect("could not read executor manifest")
+        .replace(
+            "../../../../../rust/devkit",
+            devkit_root.to_str().expect("invalid devkit path"),
+        );
+
+    let mut builder = tar::Builder::new(vec![]);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "rust-checker/Cargo.toml", manifest.as_bytes())
+        .expect("could not append manifest to tarball");
+    builder
+        .append_path_with_name(executor_root.join("Cargo.lock"), "rust-checker/Cargo.lock")
+        .expect("could not append lock file to tarball");
+    builder
+        .append_dir_all("rust-checker/src", executor_root.join("src"))
+        .expect("could not append sources to tarball");
+
+    let server = FileServer::start();
+    server.serve(
+        TARBALL_PATH,
+        builder.into_inner().expect("could not build tarball"),
+        Some("\"v1\""),
+    );
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [(
+                "project",
+                json!({
+                    "targets": {
+                        "target": {
+                            "executor": {
+                                "url": format!("{}{TARBALL_PATH}", server.url()),
+                                "format": "Tarball"
+                            },
+                            "options": {
+                                "number": 1,
+                                "string": "hello",
+                                "bool": true,
+                                "array": [1, 2, 3],
+                                "null": null,
+                                "float": 1.0
+                            }
+                        }
+                    }
+                }),
+            )],
+            [],
+        ),
+        |root| {
+            for _ in 0..2 {
+                Executions::from_run_result(run(
+                    root,
+                    RunOptions::new("target").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    Default::default(),
+                ))
+                .assert_targets([("project:target", ExpectedExecution::success())]);
+            }
+
+            let requests = server.requests();
+            assert_eq!(
+                requests
+                    .iter()
+                    .map(|request| request.status)
+                    .collect::<Vec<_>>(),
+                [200, 304]
+            );
+            assert_eq!(
+                requests[1].headers.get("if-none-match").map(String::as_str),
+                Some("\"v1\"")
+            );
+
+            assert_eq!(
+                std::fs::read_dir(root.join(".blaze/tarballs"))
+                    .expect("could not read tarballs folder")
+                    .count(),
+                1,
+                "the tarball must not be unpacked again"
+            );
+        },
+    );
+}
@@ -0,0 +1,131 @@
+mod testing;
+
+use blaze_core::{
+    common::selector::ProjectSelector, export_graph, ExportGraphOptions, GraphFormat,
+    SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, TestWorkspaceConfiguration};
+
+fn setup() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "app": "app",
+                "lib": "lib",
+                "other": "other"
+            }
+        }),
+        [
+            (
+                "app",
+                json!({
+                    "targets": {
+                        "build": {
+                            "dependencies": [
+                                { "target": "build", "projects": ["lib"] },
+                                {
+                                    "target": "build",
+                                    "projects": ["other"],
+                                    "optional": true,
+                                    "cachePropagation": "Never"
+                                }
+                            ]
+                        }
+                    }
+                }),
+            ),
+            (
+                "lib",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": []
+                            }
+                        }
+                    }
+                }),
+            ),
+            (
+                "other",
+                json!({
+                    "targets": {
+                        "build": {}
+                    }
+                }),
+            ),
+        ],
+        [],
+    )
+}
+
+fn export(root: &std::path::Path, format: GraphFormat) -> String {
+    let mut output = Vec::<u8>::new();
+    export_graph(
+        root,
+        ExportGraphOptions::new("build")
+            .with_selector_source(SelectorSource::Provided(ProjectSelector::array(["app"])))
+            .with_format(format),
+        &mut output,
+        Default::default(),
+    )
+    .expect("could not export graph");
+    String::from_utf8(output).expect("graph output must be valid UTF-8")
+}
+
+#[test]
+fn json_graph() {
+    with_test_workspace(setup(), |root| {
+        let graph: Value =
+            serde_json::from_str(&export(root, GraphFormat::Json)).expect("invalid json graph");
+
+        assert_eq!(
+            json!({
+                "nodes": [
+                    { "double": "app:build", "project": "app", "target": "build", "root": true },
+                    {
+                        "double": "lib:build",
+                        "project": "lib",
+                        "target": "build",
+                        "root": false,
+                        "executor": "std:commands"
+                    },
+                    { "double": "other:build", "project": "other", "target": "build", "root": false }
+                ],
+                "edges": [
+                    { "from": "app:build", "to": "lib:build", "cachePropagation": "Always", "optional": false },
+                    { "from": "app:build", "to": "other:build", "cachePropagation": "Never", "optional": true }
+                ]
+            }),
+            graph
+        );
+    })
+}
+
+#[test]
+fn dot_graph() {
+    with_test_workspace(setup(), |root| {
+        let graph = export(root, GraphFormat::Dot);
+
+        assert!(graph.starts_with("digraph {"));
+        assert!(graph.contains(r#""app:build" [style=bold];"#));
+        assert!(graph.contains(r#""app:build" -> "lib:build" [label="Always", style=solid];"#));
+        assert!(graph.contains(r#""app:build" -> "other:build" [label="Never", style=dashed];"#));
+    })
+}
+
+#[test]
+fn mermaid_graph() {
+    with_test_workspace(setup(), |root| {
+        let graph = export(root, GraphFormat::Mermaid);
+
+        assert!(graph.starts_with("flowchart TD"));
+        assert!(graph.contains(r#"n0["app:build"]"#));
+        assert!(graph.contains(r#"n0 -->|"Always"| n1"#));
+        assert!(graph.contains(r#"n0 -.->|"Never"| n2"#));
+        assert!(graph.contains("class n0 root"));
+    })
+}
@@ -0,0 +1,159 @@
+mod testing;
+
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use blaze_core::{
+    common::{parallelism::Parallelism, selector::ProjectSelector},
+    run, RunOptions, RunResult, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn build_target(command: &str, dependencies: Value) -> Value {
+    json!({
+        "targets": {
+            "build": {
+                "executor": "std:commands",
+                "options": {
+                    "commands": [command],
+                    "shell": true
+                },
+                "dependencies": dependencies
+            }
+        }
+    })
+}
+
+fn setup(slow_command: &str) -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "a-failing": "a-failing",
+                "b-slow": "b-slow",
+                "c-later": "c-later",
+                "d-dependent": "d-dependent"
+            }
+        }),
+        [
+            ("a-failing", build_target("sleep 0.3; exit 1", json!([]))),
+            ("b-slow", build_target(slow_command, json!([]))),
+            ("c-later", build_target("touch done", json!([]))),
+            (
+                "d-dependent",
+                build_target(
+                    "touch done",
+                    json!([{ "target": "build", "projects": ["a-failing"] }]),
+                ),
+            ),
+        ],
+        [],
+    )
+}
+
+fn run_all(root: &Path, fail_fast: bool) -> RunResult {
+    // only two targets can run at the same time, so c-later:build has to wait for a slot.
+    run(
+        root,
+        RunOptions::new("build")
+            .with_selector_source(SelectorSource::Provided(ProjectSelector::all()))
+            .with_parallelism(Parallelism::Count(2.try_into().unwrap()))
+            .with_fail_fast(fail_fast),
+        Default::default(),
+    )
+}
+
+#[test]
+fn fail_fast_stops_all_executions() {
+    with_test_workspace(setup("sleep 30 && touch done"), |root| {
+        let start = Instant::now();
+        let result = run_all(root, true);
+
+        assert!(start.elapsed() < Duration::from_secs(20));
+
+        let executions = result.as_ref().expect("run error").execution();
+        assert!(executions["c-later:build"].skipped);
+        assert!(executions["d-dependent:build"].skipped);
+
+        Executions::from_run_result(result).assert_targets([
+            ("a-failing:build", ExpectedExecution::failure()),
+            ("b-slow:build", ExpectedExecution::failure()),
+            ("c-later:build", ExpectedExecution::not_executed()),
+            ("d-dependent:build", ExpectedExecution::not_executed()),
+        ]);
+
+        assert!(!root.join("b-slow/done").exists());
+        assert!(!root.join("c-later/done").exists());
+    })
+}
+
+#[test]
+fn keep_going_only_skips_dependents() {
+    with_test_workspace(setup("sleep 0.5 && touch done"), |root| {
+        let result = run_all(root, false);
+
+        let executions = result.as_ref().expect("run error").execution();
+        assert!(executions["d-dependent:build"].skipped);
+
+        Executions::from_run_result(result).assert_targets([
+            ("a-failing:build", ExpectedExecution::failure()),
+            ("b-slow:build", ExpectedExecution::success()),
+            ("c-later:build", ExpectedExecution::success()),
+            ("d-dependent:build", ExpectedExecution::not_executed()),
+        ]);
+
+        assert!(root.join("b-slow/done").exists());
+        assert!(root.join("c-later/done").exists());
+    })
+}
+
+#[test]
+fn targets_started_after_a_failure_are_skipped() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "a-failing": "a-failing",
+                    "b-locked": "b-locked"
+                }
+            }),
+            [
+                ("a-failing", build_target("sleep 0.3; exit 1", json!([]))),
+                ("b-locked", build_target("sleep 2", json!([]))),
+            ],
+            [],
+        ),
+        |root| {
+            std::thread::scope(|scope| {
+                // another run holds the lock of b-locked:build, so it only starts once a-failing:build has failed.
+                let other_run = scope.spawn(|| {
+                    run(
+                        root,
+                        RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                            ProjectSelector::array(["b-locked"]),
+                        )),
+                        Default::default(),
+                    )
+                });
+                std::thread::sleep(Duration::from_millis(500));
+
+                let result = run_all(root, true);
+
+                let executions = result.as_ref().expect("run error").execution();
+                assert!(executions["b-locked:build"].skipped);
+
+                Executions::from_run_result(result).assert_targets([
+                    ("a-failing:build", ExpectedExecution::failure()),
+                    ("b-locked:build", ExpectedExecution::not_executed()),
+                ]);
+
+                Executions::from_run_result(other_run.join().expect("could not join run"))
+                    .assert_targets([("b-locked:build", ExpectedExecution::success())]);
+            })
+        },
+    )
+}
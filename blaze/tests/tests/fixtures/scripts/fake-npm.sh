@@ -0,0 +1,13 @@
+#!/bin/sh
+# Records how it was invoked in the workspace root, then fails like npm does when a package cannot be found.
+cwd="$(pwd)"
+root="${cwd%%/.blaze*}"
+{
+    echo "args: $*"
+    echo "token: $BLAZE_NPM_TOKEN"
+    if [ -n "$NPM_CONFIG_USERCONFIG" ]; then
+        echo "config: $(cat "$NPM_CONFIG_USERCONFIG")"
+    fi
+} >> "$root/npm.log"
+echo "npm error 404 Not Found" >&2
+exit 1
@@ -0,0 +1,253 @@
+mod testing;
+
+use std::{
+    net::{SocketAddr, TcpStream},
+    path::Path,
+    sync::mpsc::channel,
+    time::{Duration, Instant},
+};
+
+use blaze_core::{
+    common::{parallelism::Parallelism, selector::ProjectSelector},
+    run, worker, RunOptions, SelectorSource, WorkerOptions,
+};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+const TOKEN: &str = "worker-token";
+
+fn setup() -> TestWorkspaceConfiguration {
+    let build = |command: &str| {
+        json!({
+            "targets": {
+                "build": {
+                    "executor": "std:commands",
+                    "options": {
+                        "commands": [command],
+                        "shell": true
+                    },
+                    "cache": {}
+                }
+            }
+        })
+    };
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "first": "first",
+                "second": "second",
+                "failing": "failing",
+                "slow": "slow"
+            }
+        }),
+        [
+            ("first", build("touch built")),
+            ("second", build("touch built")),
+            ("failing", build("exit 3")),
+            (
+                "slow",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": ["sleep 1.5; touch built"],
+                                "shell": true
+                            },
+                            "timeout": {
+                                "unit": "Milliseconds",
+                                "amount": 500
+                            }
+                        }
+                    }
+                }),
+            ),
+        ],
+        [],
+    )
+}
+
+/// Start a worker in the background and return its address.
+/// Workers keep running until the end of the tests.
+fn start_worker(root: &Path) -> SocketAddr {
+    let root = root.to_path_buf();
+    let (sender, receiver) = channel();
+    std::thread::spawn(move || {
+        worker(
+            root,
+            WorkerOptions::new("127.0.0.1:0", TOKEN),
+            Default::default(),
+            |address| sender.send(address).expect("could not send worker address"),
+        )
+    });
+    receiver.recv().expect("worker did not start")
+}
+
+fn run_on_workers(
+    root: &Path,
+    worker_root: &Path,
+    projects: &[&str],
+    token: &str,
+) -> blaze_core::RunResult {
+    let workers =
+        [start_worker(worker_root), start_worker(worker_root)].map(|address| address.to_string());
+    run(
+        root,
+        RunOptions::new("build")
+            .with_selector_source(SelectorSource::Provided(ProjectSelector::array(projects)))
+            .with_parallelism(Parallelism::Infinite)
+            .with_workers(workers, token),
+        Default::default(),
+    )
+}
+
+#[test]
+fn targets_are_executed_on_workers() {
+    with_test_workspace(setup(), |root| {
+        Executions::from_run_result(run_on_workers(root, root, &["first", "second"], TOKEN))
+            .assert_targets([
+                ("first:build", ExpectedExecution::success()),
+                ("second:build", ExpectedExecution::success()),
+            ]);
+
+        assert!(root.join("first/built").exists());
+        assert!(root.join("second/built").exists());
+    })
+}
+
+#[test]
+fn connections_are_served_concurrently() {
+    with_test_workspace(setup(), |root| {
+        let address = start_worker(root);
+
+        // a connection which never sends its handshake must not block other coordinators.
+        let _idle = TcpStream::connect(address).expect("could not connect to worker");
+
+        Executions::from_run_result(run(
+            root,
+            RunOptions::new("build")
+                .with_selector_source(SelectorSource::Provided(ProjectSelector::array([
+                    "first", "second",
+                ])))
+                .with_parallelism(Parallelism::Infinite)
+                .with_workers([address.to_string(), address.to_string()], TOKEN),
+            Default::default(),
+        ))
+        .assert_targets([
+            ("first:build", ExpectedExecution::success()),
+            ("second:build", ExpectedExecution::success()),
+        ]);
+    })
+}
+
+#[test]
+fn invalid_token_is_rejected() {
+    with_test_workspace(setup(), |root| {
+        let result = run_on_workers(root, root, &["first"], "wrong-token");
+
+        let error = result.as_ref().expect("run error").execution()["first:build"]
+            .result
+            .as_ref()
+            .expect("first:build must be executed")
+            .as_ref()
+            .expect_err("first:build must fail");
+
+        assert!(format!("{error:#}").contains("invalid worker token"));
+        assert!(!root.join("first/built").exists());
+    })
+}
+
+#[test]
+fn options_are_resolved_by_workers() {
+    with_test_workspace(setup(), |worker_root| {
+        // the coordinator workspace has different options, which must not be used.
+        with_test_workspace(
+            TestWorkspaceConfiguration::new(
+                json!({
+                    "name": "workspace",
+                    "projects": {
+                        "first": "first"
+                    }
+                }),
+                [(
+                    "first",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": "std:commands",
+                                "options": {
+                                    "commands": ["exit 1"],
+                                    "shell": true
+                                }
+                            }
+                        }
+                    }),
+                )],
+                [],
+            ),
+            |root| {
+                Executions::from_run_result(run_on_workers(root, worker_root, &["first"], TOKEN))
+                    .assert_targets([("first:build", ExpectedExecution::success())]);
+
+                assert!(worker_root.join("first/built").exists());
+            },
+        )
+    })
+}
+
+#[test]
+fn remote_executions_are_not_cached() {
+    with_test_workspace(setup(), |root| {
+        for _ in 0..2 {
+            Executions::from_run_result(run_on_workers(root, root, &["first"], TOKEN))
+                .assert_targets([("first:build", ExpectedExecution::success())]);
+        }
+    })
+}
+
+#[test]
+fn remote_failures_are_reported() {
+    with_test_workspace(setup(), |root| {
+        let result = run_on_workers(root, root, &["first", "failing"], TOKEN);
+
+        let error = result.as_ref().expect("run error").execution()["failing:build"]
+            .result
+            .as_ref()
+            .expect("failing:build must be executed")
+            .as_ref()
+            .expect_err("failing:build must fail");
+
+        let message = format!("{error:#}");
+        assert!(message.contains("execution failed on worker"));
+        assert!(message.contains("exit 3"));
+
+        Executions::from_run_result(result).assert_targets([
+            ("first:build", ExpectedExecution::success()),
+            ("failing:build", ExpectedExecution::failure()),
+        ]);
+    })
+}
+
+#[test]
+fn remote_execution_is_interrupted_on_timeout() {
+    with_test_workspace(setup(), |root| {
+        let start = Instant::now();
+        let result = run_on_workers(root, root, &["slow"], TOKEN);
+
+        assert!(start.elapsed() < Duration::from_millis(1500));
+
+        let error = result.as_ref().expect("run error").execution()["slow:build"]
+            .result
+            .as_ref()
+            .expect("slow:build must be executed")
+            .as_ref()
+            .expect_err("slow:build must fail");
+
+        assert!(format!("{error:#}").contains("timed out after 500ms"));
+
+        // the worker must have killed the target processes.
+        std::thread::sleep(Duration::from_secs(2));
+        assert!(!root.join("slow/built").exists());
+    })
+}
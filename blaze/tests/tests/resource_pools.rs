@@ -0,0 +1,87 @@
+mod testing;
+
+use std::path::Path;
+
+use blaze_core::{
+    common::{parallelism::Parallelism, selector::ProjectSelector},
+    run, RunOptions, RunResult, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn database_target(resources: Value) -> Value {
+    json!({
+        "targets": {
+            "test": {
+                "executor": "std:commands",
+                "options": {
+                    // creating the directory fails if another target is using the "database" at the same time.
+                    "commands": ["mkdir ../database && sleep 0.3 && rmdir ../database"],
+                    "shell": true
+                },
+                "resources": resources
+            }
+        }
+    })
+}
+
+fn setup(resources: Value) -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "first": "first",
+                "second": "second",
+                "third": "third"
+            },
+            "settings": {
+                "resourcePools": {
+                    "db": 1
+                }
+            }
+        }),
+        [
+            ("first", database_target(resources.clone())),
+            ("second", database_target(resources.clone())),
+            ("third", database_target(resources)),
+        ],
+        [],
+    )
+}
+
+fn run_all(root: &Path) -> RunResult {
+    run(
+        root,
+        RunOptions::new("test")
+            .with_selector_source(SelectorSource::Provided(ProjectSelector::all()))
+            .with_parallelism(Parallelism::Infinite),
+        Default::default(),
+    )
+}
+
+#[test]
+fn targets_do_not_exceed_resource_pool_capacity() {
+    with_test_workspace(setup(json!({ "db": 1 })), |root| {
+        Executions::from_run_result(run_all(root)).assert_targets([
+            ("first:test", ExpectedExecution::success()),
+            ("second:test", ExpectedExecution::success()),
+            ("third:test", ExpectedExecution::success()),
+        ]);
+    })
+}
+
+#[test]
+fn unknown_resource_pool_is_an_error() {
+    with_test_workspace(setup(json!({ "gpu": 1 })), |root| {
+        let error = run_all(root).expect_err("run must fail");
+        assert!(format!("{error:#}").contains("unknown resource pool \"gpu\""));
+    })
+}
+
+#[test]
+fn resource_amount_exceeding_pool_capacity_is_an_error() {
+    with_test_workspace(setup(json!({ "db": 2 })), |root| {
+        let error = run_all(root).expect_err("run must fail");
+        assert!(format!("{error:#}").contains("which has a capacity of 1"));
+    })
+}
@@ -0,0 +1,125 @@
+mod testing;
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn setup(hooks: Value) -> TestWorkspaceConfiguration {
+    let commands = |command: &str| {
+        json!({
+            "executor": "std:commands",
+            "options": {
+                "commands": [command],
+                "shell": true
+            }
+        })
+    };
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "project": "project",
+                "hooks": "hooks"
+            },
+            "settings": {
+                "hooks": hooks
+            }
+        }),
+        [
+            (
+                "project",
+                json!({
+                    "targets": {
+                        "build": commands("test -f ../started && touch built"),
+                        "fail": commands("exit 1")
+                    }
+                }),
+            ),
+            (
+                "hooks",
+                json!({
+                    "targets": {
+                        "report": commands("cat > ../reported.json")
+                    }
+                }),
+            ),
+        ],
+        [],
+    )
+}
+
+fn run_target(root: &std::path::Path, target: &str) -> blaze_core::RunResult {
+    run(
+        root,
+        RunOptions::new(target).with_selector_source(SelectorSource::Provided(
+            ProjectSelector::array(["project"]),
+        )),
+        Default::default(),
+    )
+}
+
+fn read_summary(path: std::path::PathBuf) -> Value {
+    serde_json::from_slice(&std::fs::read(path).expect("summary was not written"))
+        .expect("summary must be valid JSON")
+}
+
+#[test]
+fn hooks_are_executed_around_run() {
+    with_test_workspace(
+        setup(json!({
+            "beforeRun": [{ "commands": ["touch started"], "shell": true }],
+            "afterRun": [
+                { "commands": ["cat > summary.json"], "shell": true },
+                "hooks:report"
+            ],
+            "onFailure": [{ "commands": ["touch failed"], "shell": true }]
+        })),
+        |root| {
+            Executions::from_run_result(run_target(root, "build"))
+                .assert_targets([("project:build", ExpectedExecution::success())]);
+
+            assert!(root.join("project/built").exists());
+            assert!(!root.join("failed").exists());
+
+            for path in ["summary.json", "reported.json"] {
+                let summary = read_summary(root.join(path));
+                assert_eq!(summary["success"], json!(true));
+                assert_eq!(summary["targets"][0]["double"], json!("project:build"));
+            }
+        },
+    )
+}
+
+#[test]
+fn failure_hooks_are_executed_when_run_fails() {
+    with_test_workspace(
+        setup(json!({
+            "onFailure": [{ "commands": ["cat > summary.json"], "shell": true }]
+        })),
+        |root| {
+            Executions::from_run_result(run_target(root, "fail"))
+                .assert_targets([("project:fail", ExpectedExecution::failure())]);
+
+            let summary = read_summary(root.join("summary.json"));
+            assert_eq!(summary["success"], json!(false));
+            assert_eq!(summary["targets"][0]["state"], json!("failed"));
+        },
+    )
+}
+
+#[test]
+fn failing_before_hook_aborts_run() {
+    with_test_workspace(
+        setup(json!({
+            "beforeRun": [{ "commands": ["exit 1"], "shell": true }],
+            "afterRun": [{ "commands": ["touch finished"], "shell": true }]
+        })),
+        |root| {
+            let error = run_target(root, "build").expect_err("run must be aborted");
+
+            assert!(format!("{error:#}").contains("beforeRun hook commands have failed"));
+            assert!(!root.join("project/built").exists());
+            assert!(!root.join("finished").exists());
+        },
+    )
+}
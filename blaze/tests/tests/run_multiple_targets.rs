@@ -0,0 +1,123 @@
+mod testing;
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn setup() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "app": "app",
+                "lib": "lib"
+            },
+            "settings": {
+                "defaultSelector": ["app", "lib"]
+            }
+        }),
+        [
+            (
+                "app",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                // each execution is recorded as a line in the builds file.
+                                "commands": ["echo build >> builds"],
+                                "shell": true
+                            }
+                        },
+                        "lint": {},
+                        "test": {
+                            "dependencies": ["build"]
+                        },
+                        "package": {
+                            "dependencies": ["build"]
+                        }
+                    }
+                }),
+            ),
+            (
+                "lib",
+                json!({
+                    "targets": {
+                        "lint": {},
+                        "test": {}
+                    }
+                }),
+            ),
+        ],
+        [],
+    )
+}
+
+#[test]
+fn multiple_targets_share_one_graph() {
+    with_test_workspace(setup(), |root| {
+        let result = run(
+            root,
+            RunOptions::new("test")
+                .and_target("package")
+                .and_target("lint"),
+            Default::default(),
+        );
+
+        let roots = result
+            .as_ref()
+            .expect("run error")
+            .root_executions()
+            .into_keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "app:lint",
+                "app:package",
+                "app:test",
+                "lib:lint",
+                "lib:test"
+            ],
+            roots
+        );
+
+        Executions::from_run_result(result).assert_targets([
+            ("app:build", ExpectedExecution::success()),
+            ("app:lint", ExpectedExecution::success()),
+            ("app:package", ExpectedExecution::success()),
+            ("app:test", ExpectedExecution::success()),
+            ("lib:lint", ExpectedExecution::success()),
+            ("lib:test", ExpectedExecution::success()),
+        ]);
+
+        // app:build is a dependency of both app:test and app:package, but must be executed once.
+        let builds =
+            std::fs::read_to_string(root.join("app/builds")).expect("could not read builds file");
+        assert_eq!(1, builds.lines().count());
+    })
+}
+
+#[test]
+fn multiple_targets_with_their_own_selection() {
+    with_test_workspace(setup(), |root| {
+        Executions::from_run_result(run(
+            root,
+            RunOptions::default()
+                .and_target_with_selector_source(
+                    "test",
+                    SelectorSource::Provided(ProjectSelector::array(["app"])),
+                )
+                .and_target_with_selector_source(
+                    "lint",
+                    SelectorSource::Provided(ProjectSelector::array(["lib"])),
+                ),
+            Default::default(),
+        ))
+        .assert_targets([
+            ("app:build", ExpectedExecution::success()),
+            ("app:test", ExpectedExecution::success()),
+            ("lib:lint", ExpectedExecution::success()),
+        ]);
+    })
+}
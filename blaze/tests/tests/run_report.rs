@@ -0,0 +1,117 @@
+mod testing;
+
+use std::path::Path;
+
+use blaze_core::{
+    common::selector::ProjectSelector, run, ReportFormat, RunOptions, SelectorSource,
+};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, TestWorkspaceConfiguration};
+
+fn setup() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "app": "app",
+                "lib": "lib"
+            }
+        }),
+        [
+            (
+                "app",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [{ "program": "true" }]
+                            },
+                            "dependencies": [{ "target": "build", "projects": ["lib"] }]
+                        }
+                    }
+                }),
+            ),
+            (
+                "lib",
+                json!({
+                    "targets": {
+                        "build": {
+                            "executor": "std:commands",
+                            "options": {
+                                "commands": [{ "program": "false" }]
+                            }
+                        }
+                    }
+                }),
+            ),
+        ],
+        [],
+    )
+}
+
+fn run_with_report(root: &Path, format: ReportFormat) -> String {
+    let report_path = root.join("report");
+
+    let _ = run(
+        root,
+        RunOptions::new("build")
+            .with_selector_source(SelectorSource::Provided(ProjectSelector::array(["app"])))
+            .with_report(&report_path, format),
+        Default::default(),
+    )
+    .expect("run error");
+
+    std::fs::read_to_string(report_path).expect("could not read report file")
+}
+
+#[test]
+fn json_report() {
+    with_test_workspace(setup(), |root| {
+        let report: Value = serde_json::from_str(&run_with_report(root, ReportFormat::Json))
+            .expect("invalid json report");
+
+        assert_eq!(Some(false), report["success"].as_bool());
+
+        let targets = report["targets"]
+            .as_array()
+            .expect("targets must be an array");
+        assert_eq!(2, targets.len());
+
+        let app = &targets[0];
+        assert_eq!(json!("app:build"), app["double"]);
+        assert_eq!(json!("app"), app["project"]);
+        assert_eq!(json!("build"), app["target"]);
+        assert_eq!(json!(true), app["root"]);
+        assert_eq!(json!(["lib:build"]), app["dependencies"]);
+        assert_eq!(json!("skipped"), app["state"]);
+        assert_eq!(Value::Null, app["executionTime"]);
+
+        let lib = &targets[1];
+        assert_eq!(json!("lib:build"), lib["double"]);
+        assert_eq!(json!(false), lib["root"]);
+        assert_eq!(json!([]), lib["dependencies"]);
+        assert_eq!(json!("failed"), lib["state"]);
+        assert!(!lib["errors"]
+            .as_array()
+            .expect("errors must be an array")
+            .is_empty());
+    })
+}
+
+#[test]
+fn junit_report() {
+    with_test_workspace(setup(), |root| {
+        let report = run_with_report(root, ReportFormat::Junit);
+
+        assert!(report.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(report.contains(r#"<testsuites name="blaze" tests="2" failures="1" skipped="1""#));
+        assert!(report.contains(r#"<testsuite name="app" tests="1" failures="0" skipped="1""#));
+        assert!(report.contains(r#"<testsuite name="lib" tests="1" failures="1" skipped="0""#));
+        assert!(
+            report.contains(r#"<skipped message="target was not executed because of a failure"/>"#)
+        );
+        assert!(report.contains("<failure message="));
+        assert!(report.trim_end().ends_with("</testsuites>"));
+    })
+}
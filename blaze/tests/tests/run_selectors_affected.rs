@@ -0,0 +1,135 @@
+mod testing;
+
+use std::path::Path;
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{cmd, with_test_workspace, TestWorkspaceConfiguration};
+
+use crate::testing::{Executions, ExpectedExecution};
+
+fn setup() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "app": "app",
+                "lib": "lib",
+                "other": "other"
+            }
+        }),
+        [
+            (
+                "app",
+                json!({
+                    "targets": {
+                        "build": {
+                            "dependencies": [{ "target": "build", "projects": ["lib"] }]
+                        },
+                        "test": {}
+                    }
+                }),
+            ),
+            (
+                "lib",
+                json!({
+                    "targets": {
+                        "build": {},
+                        "test": {}
+                    }
+                }),
+            ),
+            (
+                "other",
+                json!({
+                    "targets": {
+                        "test": {}
+                    }
+                }),
+            ),
+        ],
+        [],
+    )
+}
+
+fn git(root: &Path, args: &str) {
+    cmd(format!(
+        "git -C '{}' -c user.name=test -c user.email=test@example.com {args}",
+        root.display()
+    ))
+}
+
+fn init_repository(root: &Path) {
+    std::fs::write(root.join(".gitignore"), ".blaze\n").expect("could not write test file");
+    for project in ["app", "lib", "other"] {
+        std::fs::write(root.join(project).join("main.txt"), "initial")
+            .expect("could not write test file");
+    }
+    git(root, "init -q -b main");
+    git(root, "add -A");
+    git(root, "commit -q -m initial");
+}
+
+fn run_and_verify_affected_projects<const N: usize>(root: &Path, base: &str, expected: [&str; N]) {
+    let results = run(
+        root,
+        RunOptions::new("test")
+            .with_selector_source(SelectorSource::Provided(ProjectSelector::affected(base))),
+        Default::default(),
+    );
+
+    let doubles = expected
+        .into_iter()
+        .map(|name| format!("{name}:test"))
+        .collect::<Vec<_>>();
+
+    Executions::from_run_result(results).assert_targets(
+        doubles
+            .iter()
+            .map(|double| (double.as_str(), ExpectedExecution::success())),
+    );
+}
+
+#[test]
+fn uncommitted_changes_select_project_and_dependents() {
+    with_test_workspace(setup(), |root| {
+        init_repository(root);
+
+        std::fs::write(root.join("lib/main.txt"), "modified").expect("could not write test file");
+
+        run_and_verify_affected_projects(root, "HEAD", ["app", "lib"]);
+    })
+}
+
+#[test]
+fn untracked_files_select_project() {
+    with_test_workspace(setup(), |root| {
+        init_repository(root);
+
+        std::fs::write(root.join("other/new.txt"), "new").expect("could not write test file");
+
+        run_and_verify_affected_projects(root, "HEAD", ["other"]);
+    })
+}
+
+#[test]
+fn committed_changes_since_base_select_project() {
+    with_test_workspace(setup(), |root| {
+        init_repository(root);
+
+        git(root, "checkout -q -b feature");
+        std::fs::write(root.join("app/main.txt"), "modified").expect("could not write test file");
+        git(root, "commit -q -am change");
+
+        run_and_verify_affected_projects(root, "main", ["app"]);
+    })
+}
+
+#[test]
+fn no_changes_select_nothing() {
+    with_test_workspace(setup(), |root| {
+        init_repository(root);
+
+        run_and_verify_affected_projects(root, "HEAD", []);
+    })
+}
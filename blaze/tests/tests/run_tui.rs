@@ -0,0 +1,59 @@
+mod testing;
+
+use blaze_core::{run, RunOptions};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn setup() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "project": "project"
+            },
+            "settings": {
+                "defaultSelector": ["project"]
+            }
+        }),
+        [(
+            "project",
+            json!({
+                "targets": {
+                    "build": {
+                        "executor": "std:commands",
+                        "options": {
+                            "commands": ["echo building && touch built"],
+                            "shell": true
+                        }
+                    },
+                    "test": {
+                        "executor": "std:commands",
+                        "options": {
+                            "commands": ["exit 1"],
+                            "shell": true
+                        },
+                        "dependencies": ["build"]
+                    }
+                }
+            }),
+        )],
+        [],
+    )
+}
+
+#[test]
+fn tui_falls_back_to_logs_when_not_attached_to_a_terminal() {
+    with_test_workspace(setup(), |root| {
+        Executions::from_run_result(run(
+            root,
+            RunOptions::new("test").with_tui(5),
+            Default::default(),
+        ))
+        .assert_targets([
+            ("project:build", ExpectedExecution::success()),
+            ("project:test", ExpectedExecution::failure()),
+        ]);
+
+        assert!(root.join("project/built").exists());
+    })
+}
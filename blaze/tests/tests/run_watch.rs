@@ -0,0 +1,196 @@
+use std::ops::ControlFlow;
+
+use blaze_core::{
+    common::selector::ProjectSelector, watch, GlobalOptions, RunOptions, SelectorSource,
+};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+mod testing;
+
+fn build_target(dependencies: serde_json::Value) -> serde_json::Value {
+    json!({
+        "targets": {
+            "build": {
+                "executor": "std:commands",
+                "options": {
+                    "commands": [
+                        "echo 'build' >> '{{ project.root }}/runs.log'"
+                    ],
+                    "shell": true
+                },
+                "cache": {
+                    "invalidateWhen": {
+                        "inputChanges": ["src/**"]
+                    }
+                },
+                "dependencies": dependencies
+            }
+        }
+    })
+}
+
+#[test]
+fn affected_targets_and_dependents_are_executed_again() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "app": "app",
+                    "lib": "lib",
+                    "other": "other"
+                }
+            }),
+            [
+                (
+                    "app",
+                    build_target(json!([{ "target": "build", "projects": ["lib"] }])),
+                ),
+                ("lib", build_target(json!([]))),
+                ("other", build_target(json!([]))),
+            ],
+            [],
+        ),
+        |root| {
+            for project in ["app", "lib", "other"] {
+                std::fs::create_dir_all(root.join(project).join("src"))
+                    .expect("could not create directory");
+                std::fs::write(root.join(project).join("src/main.txt"), "initial")
+                    .expect("could not write test file");
+            }
+
+            let mut runs = 0;
+
+            watch(
+                root,
+                RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["app", "other"]),
+                )),
+                GlobalOptions::default(),
+                |results| {
+                    runs += 1;
+                    match runs {
+                        1 => {
+                            Executions::assert_graph_targets(
+                                results,
+                                [
+                                    ("app:build", ExpectedExecution::success()),
+                                    ("lib:build", ExpectedExecution::success()),
+                                    ("other:build", ExpectedExecution::success()),
+                                ],
+                            );
+                            std::fs::write(root.join("lib/src/main.txt"), "modified")
+                                .expect("could not write test file");
+                            ControlFlow::Continue(())
+                        }
+                        _ => {
+                            Executions::assert_graph_targets(
+                                results,
+                                [
+                                    ("app:build", ExpectedExecution::success()),
+                                    ("lib:build", ExpectedExecution::success()),
+                                    ("other:build", ExpectedExecution::cached()),
+                                ],
+                            );
+                            ControlFlow::Break(())
+                        }
+                    }
+                },
+            )
+            .expect("watch error");
+
+            let read_runs = |project: &str| {
+                std::fs::read_to_string(root.join(project).join("runs.log"))
+                    .expect("failed to read test file")
+            };
+
+            assert_eq!(2, runs);
+            assert_eq!("build\nbuild\n", read_runs("app"));
+            assert_eq!("build\nbuild\n", read_runs("lib"));
+            assert_eq!("build\n", read_runs("other"));
+        },
+    )
+}
+
+#[test]
+fn files_changed_during_a_run_trigger_a_new_run() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "lib": "lib",
+                    "gen": "gen"
+                }
+            }),
+            [
+                (
+                    "lib",
+                    build_target(json!([{ "target": "build", "projects": ["gen"] }])),
+                ),
+                (
+                    "gen",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": "std:commands",
+                                "options": {
+                                    "commands": [
+                                        "test -f '{{ workspace.root }}/lib/src/generated.txt' || echo 'generated' > '{{ workspace.root }}/lib/src/generated.txt'"
+                                    ],
+                                    "shell": true
+                                }
+                            }
+                        }
+                    }),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            std::fs::create_dir_all(root.join("lib/src")).expect("could not create directory");
+            std::fs::write(root.join("lib/src/main.txt"), "initial")
+                .expect("could not write test file");
+
+            let mut runs = 0;
+
+            watch(
+                root,
+                RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["lib"]),
+                )),
+                GlobalOptions::default(),
+                |results| {
+                    runs += 1;
+                    match runs {
+                        1 => {
+                            Executions::assert_graph_targets(
+                                results,
+                                [
+                                    ("lib:build", ExpectedExecution::success()),
+                                    ("gen:build", ExpectedExecution::success()),
+                                ],
+                            );
+                            ControlFlow::Continue(())
+                        }
+                        _ => {
+                            // the generated file was already present when lib:build was executed.
+                            Executions::assert_graph_targets(
+                                results,
+                                [
+                                    ("lib:build", ExpectedExecution::cached()),
+                                    ("gen:build", ExpectedExecution::cached()),
+                                ],
+                            );
+                            ControlFlow::Break(())
+                        }
+                    }
+                },
+            )
+            .expect("watch error");
+
+            assert_eq!(2, runs);
+        },
+    )
+}
@@ -0,0 +1,109 @@
+mod testing;
+
+use blaze_core::{run, target_logs, LogsOptions, RunOptions};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn setup() -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "project": "project"
+            },
+            "settings": {
+                "defaultSelector": ["project"]
+            }
+        }),
+        [(
+            "project",
+            json!({
+                "targets": {
+                    "build": {
+                        "executor": "std:commands",
+                        "options": {
+                            "commands": ["echo building project && echo some warning >&2"],
+                            "shell": true
+                        },
+                        "cache": {}
+                    }
+                }
+            }),
+        )],
+        [],
+    )
+}
+
+fn last_logs(root: &std::path::Path) -> (String, String) {
+    let logs = target_logs(root, LogsOptions::new("project:build"), Default::default())
+        .expect("could not get target logs");
+    (
+        logs.run_id().to_owned(),
+        String::from_utf8_lossy(logs.content()).into_owned(),
+    )
+}
+
+#[test]
+fn target_output_is_captured() {
+    with_test_workspace(setup(), |root| {
+        Executions::from_run_result(run(root, RunOptions::new("build"), Default::default()))
+            .assert_targets([("project:build", ExpectedExecution::success())]);
+
+        let (_, content) = last_logs(root);
+        assert!(content.contains("building project"));
+        assert!(content.contains("some warning"));
+    })
+}
+
+#[test]
+fn log_files_are_named_after_a_hash_of_the_double() {
+    with_test_workspace(setup(), |root| {
+        Executions::from_run_result(run(root, RunOptions::new("build"), Default::default()))
+            .assert_targets([("project:build", ExpectedExecution::success())]);
+
+        let directories = std::fs::read_dir(root.join(".blaze/logs"))
+            .expect("could not read logs folder")
+            .map(|entry| entry.expect("could not read logs folder entry").path())
+            .collect::<Vec<_>>();
+        assert_eq!(directories.len(), 1);
+
+        let directory_name = directories[0]
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("invalid logs directory name");
+        assert!(directory_name.chars().all(|c| c.is_ascii_hexdigit()));
+
+        let (run_id, content) = last_logs(root);
+        let raw_content =
+            std::fs::read_to_string(directories[0].join(run_id)).expect("could not read log file");
+        assert_eq!(raw_content, format!("# target: project:build\n{content}"));
+    })
+}
+
+#[test]
+fn target_output_is_replayed_when_cached() {
+    with_test_workspace(setup(), |root| {
+        Executions::from_run_result(run(root, RunOptions::new("build"), Default::default()))
+            .assert_targets([("project:build", ExpectedExecution::success())]);
+
+        let (first_run_id, first_content) = last_logs(root);
+
+        Executions::from_run_result(run(root, RunOptions::new("build"), Default::default()))
+            .assert_targets([("project:build", ExpectedExecution::cached())]);
+
+        let (second_run_id, second_content) = last_logs(root);
+
+        assert_ne!(first_run_id, second_run_id);
+        assert_eq!(first_content, second_content);
+    })
+}
+
+#[test]
+fn logs_of_target_never_executed_are_an_error() {
+    with_test_workspace(setup(), |root| {
+        let error = target_logs(root, LogsOptions::new("project:build"), Default::default())
+            .err()
+            .expect("logs must not exist");
+        assert!(format!("{error:#}").contains("no logs were found for target project:build"));
+    })
+}
@@ -0,0 +1,139 @@
+mod testing;
+
+use std::path::Path;
+
+use blaze_core::{run, ExecutionDetails, RunOptions};
+use serde_json::{json, Value};
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+fn setup(command: &str, retry: Value) -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "project": "project"
+            },
+            "settings": {
+                "defaultSelector": ["project"]
+            }
+        }),
+        [(
+            "project",
+            json!({
+                "targets": {
+                    "test": {
+                        "executor": "std:commands",
+                        "options": {
+                            // each attempt is recorded as a line in the attempts file.
+                            "commands": [format!("echo attempt >> attempts; {command}")],
+                            "shell": true
+                        },
+                        "retry": retry
+                    }
+                }
+            }),
+        )],
+        [],
+    )
+}
+
+fn attempts(root: &Path) -> usize {
+    std::fs::read_to_string(root.join("project/attempts"))
+        .expect("could not read attempts file")
+        .lines()
+        .count()
+}
+
+#[test]
+fn flaky_target_succeeds_after_retries() {
+    with_test_workspace(
+        setup(
+            "test $(wc -l < attempts) -ge 3",
+            json!({
+                "maxAttempts": 3,
+                "delay": {
+                    "unit": "Milliseconds",
+                    "amount": 50
+                },
+                "backoff": "Exponential"
+            }),
+        ),
+        |root| {
+            let result = run(root, RunOptions::new("test"), Default::default());
+
+            assert!(matches!(
+                result.as_ref().expect("run error").execution()["project:test"].result,
+                Some(Ok(ExecutionDetails::Executed { attempts: 3, .. }))
+            ));
+
+            Executions::from_run_result(result)
+                .assert_targets([("project:test", ExpectedExecution::success())]);
+
+            assert_eq!(3, attempts(root));
+        },
+    )
+}
+
+#[test]
+fn target_fails_when_all_attempts_fail() {
+    with_test_workspace(setup("exit 1", json!({ "maxAttempts": 2 })), |root| {
+        let result = run(root, RunOptions::new("test"), Default::default());
+
+        let error = result.as_ref().expect("run error").execution()["project:test"]
+            .result
+            .as_ref()
+            .expect("project:test must be executed")
+            .as_ref()
+            .expect_err("project:test must fail");
+
+        assert!(format!("{error:#}").contains("has failed after 2 attempts"));
+
+        Executions::from_run_result(result)
+            .assert_targets([("project:test", ExpectedExecution::failure())]);
+
+        assert_eq!(2, attempts(root));
+    })
+}
+
+#[test]
+fn only_matching_exit_codes_are_retried() {
+    with_test_workspace(
+        setup("exit 2", json!({ "maxAttempts": 3, "exitCodes": [1] })),
+        |root| {
+            Executions::from_run_result(run(root, RunOptions::new("test"), Default::default()))
+                .assert_targets([("project:test", ExpectedExecution::failure())]);
+
+            assert_eq!(1, attempts(root));
+        },
+    )
+}
+
+#[test]
+fn matching_exit_codes_are_retried() {
+    with_test_workspace(
+        setup("exit 2", json!({ "maxAttempts": 3, "exitCodes": [1, 2] })),
+        |root| {
+            Executions::from_run_result(run(root, RunOptions::new("test"), Default::default()))
+                .assert_targets([("project:test", ExpectedExecution::failure())]);
+
+            assert_eq!(3, attempts(root));
+        },
+    )
+}
+
+#[test]
+fn negative_exit_codes_are_accepted() {
+    // exit codes are signed, for e.g Windows processes crashing with an access violation exit with a negative code.
+    with_test_workspace(
+        setup(
+            "exit 2",
+            json!({ "maxAttempts": 3, "exitCodes": [-1073741819] }),
+        ),
+        |root| {
+            Executions::from_run_result(run(root, RunOptions::new("test"), Default::default()))
+                .assert_targets([("project:test", ExpectedExecution::failure())]);
+
+            assert_eq!(1, attempts(root));
+        },
+    )
+}
@@ -0,0 +1,205 @@
+mod testing;
+
+use std::time::{Duration, Instant};
+
+use blaze_core::{common::selector::ProjectSelector, run, RunOptions, SelectorSource};
+use serde_json::json;
+use testing::{with_test_workspace, Executions, ExpectedExecution, TestWorkspaceConfiguration};
+
+#[test]
+fn target_is_killed_when_timeout_is_exceeded() {
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "app": "app",
+                    "lib": "lib"
+                }
+            }),
+            [
+                (
+                    "app",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "dependencies": [{ "target": "build", "projects": ["lib"] }]
+                            }
+                        }
+                    }),
+                ),
+                (
+                    "lib",
+                    json!({
+                        "targets": {
+                            "build": {
+                                "executor": "std:commands",
+                                "options": {
+                                    // the shell spawns a child process, which must be killed as well.
+                                    "commands": ["sleep 30; echo done"],
+                                    "shell": true
+                                },
+                                "timeout": {
+                                    "unit": "Milliseconds",
+                                    "amount": 500
+                                }
+                            }
+                        }
+                    }),
+                ),
+            ],
+            [],
+        ),
+        |root| {
+            let start = Instant::now();
+
+            let result = run(
+                root,
+                RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                    ProjectSelector::array(["app"]),
+                )),
+                Default::default(),
+            );
+
+            assert!(start.elapsed() < Duration::from_secs(20));
+
+            let error = result.as_ref().expect("run error").execution()["lib:build"]
+                .result
+                .as_ref()
+                .expect("lib:build must be executed")
+                .as_ref()
+                .expect_err("lib:build must fail");
+
+            assert!(format!("{error:#}").contains("timed out after 500ms"));
+
+            Executions::from_run_result(result).assert_targets([
+                ("app:build", ExpectedExecution::not_executed()),
+                ("lib:build", ExpectedExecution::failure()),
+            ]);
+        },
+    )
+}
+
+fn command_timeout_workspace(on_failure: &str) -> TestWorkspaceConfiguration {
+    TestWorkspaceConfiguration::new(
+        json!({
+            "name": "workspace",
+            "projects": {
+                "project": "project"
+            },
+            "settings": {
+                "defaultSelector": ["project"]
+            }
+        }),
+        [(
+            "project",
+            json!({
+                "targets": {
+                    "build": {
+                        "executor": "std:commands",
+                        "options": {
+                            "commands": [
+                                {
+                                    "program": "sleep",
+                                    "arguments": ["30"],
+                                    "onFailure": on_failure,
+                                    "timeout": {
+                                        "unit": "Milliseconds",
+                                        "amount": 500
+                                    }
+                                },
+                                {
+                                    "program": "touch",
+                                    "arguments": ["done"]
+                                }
+                            ]
+                        }
+                    }
+                }
+            }),
+        )],
+        [],
+    )
+}
+
+#[test]
+fn command_is_killed_when_timeout_is_exceeded() {
+    with_test_workspace(command_timeout_workspace("Exit"), |root| {
+        let start = Instant::now();
+
+        Executions::from_run_result(run(root, RunOptions::new("build"), Default::default()))
+            .assert_targets([("project:build", ExpectedExecution::failure())]);
+
+        assert!(start.elapsed() < Duration::from_secs(20));
+        assert!(!root.join("project/done").exists());
+    })
+}
+
+#[test]
+fn command_timeout_applies_failure_strategy() {
+    with_test_workspace(command_timeout_workspace("Ignore"), |root| {
+        Executions::from_run_result(run(root, RunOptions::new("build"), Default::default()))
+            .assert_targets([("project:build", ExpectedExecution::success())]);
+
+        assert!(root.join("project/done").exists());
+    })
+}
+
+#[test]
+fn scheduling_changes_do_not_invalidate_cache() {
+    let project = |timeout_amount: usize, max_attempts: usize| {
+        json!({
+            "targets": {
+                "build": {
+                    "executor": "std:commands",
+                    "options": {
+                        "commands": ["echo built"],
+                        "shell": true
+                    },
+                    "cache": {},
+                    "timeout": {
+                        "unit": "Seconds",
+                        "amount": timeout_amount
+                    },
+                    "retry": {
+                        "maxAttempts": max_attempts
+                    }
+                }
+            }
+        })
+    };
+
+    with_test_workspace(
+        TestWorkspaceConfiguration::new(
+            json!({
+                "name": "workspace",
+                "projects": {
+                    "project": "project"
+                }
+            }),
+            [("project", project(10, 1))],
+            [],
+        ),
+        |root| {
+            let run_build = || {
+                Executions::from_run_result(run(
+                    root,
+                    RunOptions::new("build").with_selector_source(SelectorSource::Provided(
+                        ProjectSelector::array(["project"]),
+                    )),
+                    Default::default(),
+                ))
+            };
+
+            run_build().assert_targets([("project:build", ExpectedExecution::success())]);
+
+            std::fs::write(
+                root.join("project/project.json"),
+                project(20, 3).to_string(),
+            )
+            .expect("could not write project configuration");
+
+            run_build().assert_targets([("project:build", ExpectedExecution::cached())]);
+        },
+    )
+}
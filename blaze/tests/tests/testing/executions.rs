@@ -54,11 +54,18 @@ impl Executions {
     ) {
         let graph = self.result.as_ref().expect("run error");
 
-        Self::check_nodes(&targets.into_iter().collect::<Vec<_>>()[..], &graph);
+        Self::check_nodes(&targets.into_iter().collect::<Vec<_>>()[..], graph);
     }
 
-    fn check_nodes<'a>(
-        expected_targets: &[(&'a str, ExpectedExecution)],
+    pub fn assert_graph_targets<'a, T: IntoIterator<Item = (&'a str, ExpectedExecution)>>(
+        graph: &ExecutedGraph<ExecutionDetails>,
+        targets: T,
+    ) {
+        Self::check_nodes(&targets.into_iter().collect::<Vec<_>>()[..], graph);
+    }
+
+    fn check_nodes(
+        expected_targets: &[(&str, ExpectedExecution)],
         execution_graph: &ExecutedGraph<ExecutionDetails>,
     ) {
         assert_eq!(
@@ -0,0 +1,330 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use md5::Digest;
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+};
+
+type Entries = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// A minimal HTTP server that stores request bodies in memory, using the GET/PUT/DELETE cache protocol.
+pub struct CacheServer {
+    url: String,
+    entries: Entries,
+}
+
+impl CacheServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind cache server");
+        let url = format!(
+            "http://{}/cache/",
+            listener
+                .local_addr()
+                .expect("could not get cache server address")
+        );
+        let entries = Entries::default();
+        let server_entries = entries.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.expect("cache server connection error");
+                handle_connection(stream, &server_entries);
+            }
+        });
+
+        Self { url, entries }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn entries_count(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Get the content of an entry, by its name.
+    pub fn entry(&self, name: &str) -> Option<Vec<u8>> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(&format!("/cache/{name}"))
+            .cloned()
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Read an HTTP request. Header names are lowercased.
+fn read_request(stream: &TcpStream) -> Request {
+    let mut reader = BufReader::new(stream.try_clone().expect("could not clone stream"));
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .expect("could not read request line");
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .expect("could not read header");
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+        }
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .map(|value| value.parse().expect("invalid content length"))
+        .unwrap_or(0_usize);
+
+    let mut body = vec![0_u8; content_length];
+    reader.read_exact(&mut body).expect("could not read body");
+
+    Request {
+        method,
+        path,
+        headers,
+        body,
+    }
+}
+
+fn write_response(mut stream: TcpStream, status: &str, headers: &[(&str, String)], content: &[u8]) {
+    let mut head = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        content.len()
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(content);
+}
+
+fn handle_connection(stream: TcpStream, entries: &Entries) {
+    let Request {
+        method, path, body, ..
+    } = read_request(&stream);
+
+    let (status, content) = {
+        let mut entries = entries.lock().unwrap();
+        match method.as_str() {
+            "GET" => match entries.get(&path) {
+                Some(content) => ("200 OK", content.clone()),
+                None => ("404 Not Found", vec![]),
+            },
+            "PUT" => {
+                entries.insert(path, body);
+                ("204 No Content", vec![])
+            }
+            "DELETE" => match entries.remove(&path) {
+                Some(_) => ("204 No Content", vec![]),
+                None => ("404 Not Found", vec![]),
+            },
+            _ => ("405 Method Not Allowed", vec![]),
+        }
+    };
+
+    write_response(stream, status, &[], &content);
+}
+
+/// Authentication required by a [`FileServer`].
+#[derive(Clone)]
+pub enum ServerAuthentication {
+    Basic { username: String, password: String },
+    Bearer { token: String },
+    Digest { username: String, password: String },
+}
+
+const DIGEST_REALM: &str = "files";
+const DIGEST_NONCE: &str = "dcd98b7102dd2f0e8b11d0f600bfb0c0";
+
+impl ServerAuthentication {
+    fn is_authorized(&self, request: &Request) -> bool {
+        let Some(authorization) = request.headers.get("authorization") else {
+            return false;
+        };
+        match self {
+            Self::Basic { username, password } => {
+                *authorization
+                    == format!(
+                        "Basic {}",
+                        STANDARD.encode(format!("{username}:{password}"))
+                    )
+            }
+            Self::Bearer { token } => *authorization == format!("Bearer {token}"),
+            Self::Digest { username, password } => {
+                let Some(params) = authorization.strip_prefix("Digest ") else {
+                    return false;
+                };
+                let params = params
+                    .split(',')
+                    .filter_map(|param| param.split_once('='))
+                    .map(|(name, value)| (name.trim(), value.trim().trim_matches('"')))
+                    .collect::<HashMap<_, _>>();
+                let param = |name: &str| params.get(name).copied().unwrap_or_default();
+
+                let ha1 = md5_hex(&format!("{username}:{DIGEST_REALM}:{password}"));
+                let ha2 = md5_hex(&format!("{}:{}", request.method, param("uri")));
+                let expected = md5_hex(&format!(
+                    "{ha1}:{DIGEST_NONCE}:{}:{}:auth:{ha2}",
+                    param("nc"),
+                    param("cnonce")
+                ));
+
+                param("username") == username
+                    && param("nonce") == DIGEST_NONCE
+                    && param("qop") == "auth"
+                    && param("uri") == request.path
+                    && param("response") == expected
+            }
+        }
+    }
+
+    fn challenge(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::Digest { .. } => vec![(
+                "WWW-Authenticate",
+                format!("Digest realm=\"{DIGEST_REALM}\", qop=\"auth\", nonce=\"{DIGEST_NONCE}\""),
+            )],
+            _ => vec![],
+        }
+    }
+}
+
+fn md5_hex(input: &str) -> String {
+    format!("{:x}", md5::Md5::digest(input.as_bytes()))
+}
+
+/// A request received by a [`FileServer`], along with the response status.
+#[derive(Clone, Debug)]
+pub struct ServedRequest {
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub status: u16,
+}
+
+struct ServedFile {
+    content: Vec<u8>,
+    etag: Option<String>,
+}
+
+#[derive(Default)]
+struct FileServerState {
+    files: HashMap<String, ServedFile>,
+    authentication: Option<ServerAuthentication>,
+    requests: Vec<ServedRequest>,
+}
+
+/// A minimal HTTP server that serves static files, with optional authentication and `ETag` support.
+pub struct FileServer {
+    url: String,
+    state: Arc<Mutex<FileServerState>>,
+}
+
+impl FileServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind file server");
+        let url = format!(
+            "http://{}",
+            listener
+                .local_addr()
+                .expect("could not get file server address")
+        );
+        let state = Arc::new(Mutex::new(FileServerState::default()));
+        let server_state = state.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.expect("file server connection error");
+                serve_file(stream, &server_state);
+            }
+        });
+
+        Self { url, state }
+    }
+
+    /// Base URL of the server, without a trailing slash.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Address of the server (`<host>:<port>`).
+    pub fn address(&self) -> &str {
+        self.url.trim_start_matches("http://")
+    }
+
+    pub fn with_authentication(self, authentication: ServerAuthentication) -> Self {
+        self.state.lock().unwrap().authentication = Some(authentication);
+        self
+    }
+
+    pub fn serve<P: AsRef<str>, C: Into<Vec<u8>>>(&self, path: P, content: C, etag: Option<&str>) {
+        self.state.lock().unwrap().files.insert(
+            path.as_ref().to_owned(),
+            ServedFile {
+                content: content.into(),
+                etag: etag.map(str::to_owned),
+            },
+        );
+    }
+
+    pub fn requests(&self) -> Vec<ServedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+fn serve_file(stream: TcpStream, state: &Mutex<FileServerState>) {
+    let request = read_request(&stream);
+    let mut state = state.lock().unwrap();
+
+    let (status, headers, content) = match &state.authentication {
+        Some(authentication) if !authentication.is_authorized(&request) => {
+            ("401 Unauthorized", authentication.challenge(), vec![])
+        }
+        _ => match state.files.get(&request.path) {
+            Some(file)
+                if file.etag.is_some()
+                    && file.etag.as_ref() == request.headers.get("if-none-match") =>
+            {
+                ("304 Not Modified", vec![], vec![])
+            }
+            Some(file) => (
+                "200 OK",
+                file.etag
+                    .iter()
+                    .map(|etag| ("ETag", etag.clone()))
+                    .collect(),
+                file.content.clone(),
+            ),
+            None => ("404 Not Found", vec![], vec![]),
+        },
+    };
+
+    state.requests.push(ServedRequest {
+        path: request.path,
+        headers: request.headers,
+        status: status[..3].parse().expect("invalid status"),
+    });
+
+    drop(state);
+
+    write_response(stream, status, &headers, &content);
+}
@@ -2,10 +2,12 @@
 
 mod commands;
 mod executions;
+mod http_server;
 mod util;
 mod workspace;
 
 pub use commands::cmd;
 pub use executions::*;
+pub use http_server::{CacheServer, FileServer, ServedRequest, ServerAuthentication};
 pub use util::get_fixtures_root;
 pub use workspace::*;
@@ -69,7 +69,7 @@ pub fn with_test_folder<F: FnOnce(PathBuf) + UnwindSafe>(
 
 pub const FIXTURES_DIR: &str = "tests/fixtures";
 
-pub fn with_test_workspace<F: FnOnce(&Path) -> () + UnwindSafe>(
+pub fn with_test_workspace<F: FnOnce(&Path) + UnwindSafe>(
     config: TestWorkspaceConfiguration,
     test_routine: F,
 ) {
@@ -136,7 +136,7 @@ pub fn with_test_workspace<F: FnOnce(&Path) -> () + UnwindSafe>(
 fn random_workspace_name() -> String {
     format!(
         "blaze_test_workspace_{}",
-        &rand::thread_rng()
+        rand::thread_rng()
             .sample_iter(&Alphanumeric)
             .take(12)
             .map(char::from)
@@ -126,6 +126,7 @@ const sidebars = {
                     items: [
                         'file-system',
                         'git',
+                        'tarball',
                         'cargo',
                         'npm',
                     ].map(name => ({ type: 'doc', id: `executors/resolvers/${name}`}))